@@ -3,6 +3,8 @@ package communication
 import (
 	"net"
 	"time"
+
+	"github.com/GrGLeo/ctf/shared"
 )
 
 // TickMsg is used to send a time-based tick message.
@@ -47,19 +49,54 @@ type GameCloseMsg struct {
 	Code int
 }
 
-// EndGameMsg is sent when the game ends
+// EndGameMsg is sent when the game ends, with the final scoreboard
 type EndGameMsg struct {
-	Win bool
+	Win    bool
+	Scores []shared.ScoreEntry
+}
+
+// ScoreboardMsg is sent every second during the game
+type ScoreboardMsg struct {
+	Entries []shared.ScoreEntry
+}
+
+// SpellState is the state of a spell slot, as seen by the game model
+type SpellState struct {
+	Rank     int
+	Cooldown time.Duration
+	Ready    bool
 }
 
 // BoardMsg is used to transfer the board to game model
 type BoardMsg struct {
-	Points [2]int
-	Health [2]int
-	Mana   [2]int
-	Level  int
-	Xp     [2]int
-	Board  [21][51]int
+	Points      [2]int
+	Health      [2]int
+	Mana        [2]int
+	Level       int
+	Xp          [2]int
+	SkillPoints int
+	Spells      []SpellState
+	// Top left cell of the board on the whole map, as row and col
+	ViewOrigin  [2]int
+	Board       [21][51]int
+}
+
+// CastFailedMsg is sent when the server refuses a spell cast
+type CastFailedMsg struct {
+	Slot          int
+	NotEnoughMana bool
+}
+
+// RespawnMsg is sent on every board while the champion is dead
+type RespawnMsg struct {
+	Remaining time.Duration
+}
+
+// TickRateMsg is sent every second with the tick rate of the game server
+type TickRateMsg struct {
+	Period time.Duration
+	Rate   float64
+	Drift  time.Duration
 }
 
 type DeltaMsg struct {
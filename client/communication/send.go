@@ -1,10 +1,12 @@
 package communication
 
 import (
+	"errors"
 	"fmt"
 	"log"
 	"net"
 	"os"
+	"time"
 
 	"github.com/GrGLeo/ctf/shared"
 	tea "github.com/charmbracelet/bubbletea"
@@ -74,16 +76,27 @@ func SendAction(conn *net.TCPConn, action int) error {
 	return err
 }
 
-func SendSpellSelectionPacket(conn *net.TCPConn, spell1, spell2 int) error {
-	log.Printf("Sending spell selection: %d, %d", spell1, spell2)
-	spellPacket := shared.NewSpellSelectionPacket(spell1, spell2)
+func SendSpellSelectionPacket(conn *net.TCPConn, spell1, spell2, champion int) error {
+	log.Printf("Sending spell selection: %d, %d with champion %d", spell1, spell2, champion)
+	spellPacket := shared.NewSpellSelectionPacket(spell1, spell2, champion)
 	data := spellPacket.Serialize()
 	_, err := conn.Write(data)
 	return err
 }
 
+func SendMoveToPacket(conn *net.TCPConn, attackMove bool, row, col int) error {
+	log.Printf("Sending move to: %d, %d, attack move %t", row, col, attackMove)
+	moveToPacket := shared.NewMoveToPacket(attackMove, row, col)
+	data := moveToPacket.Serialize()
+	_, err := conn.Write(data)
+	return err
+}
+
 func ListenForPackets(conn *net.TCPConn, msgs chan<- tea.Msg) {
 	buf := make([]byte, 1024)
+	// Bytes read but not decoded yet: the game server sends several packets
+	// at once, and a board can span several reads
+	var pending []byte
 	for {
 		n, err := conn.Read(buf)
 		if err != nil {
@@ -92,47 +105,106 @@ func ListenForPackets(conn *net.TCPConn, msgs chan<- tea.Msg) {
 			return
 		}
 		log.Printf("Received %d bytes: %x", n, buf[:n])
-		// Send the packet as a message to the model
-		message, err := shared.DeSerialize(buf[:n])
+		pending = append(pending, buf[:n]...)
+		for len(pending) > 0 {
+			size, err := shared.PacketSize(pending)
+			if errors.Is(err, shared.ErrIncompletePacket) {
+				break
+			}
+			if err != nil {
+				log.Printf("Error splitting packets: %v, raw: %x", err, pending)
+				pending = nil
+				break
+			}
+			packet := pending[:size]
+			pending = pending[size:]
+			if err := sendPacketMsg(packet, msgs); err != nil {
+				log.Printf("Error deserializing packet: %v", err)
+				return
+			}
+		}
+	}
+}
+
+// sendPacketMsg sends a packet as a message to the model
+func sendPacketMsg(data []byte, msgs chan<- tea.Msg) error {
+	message, err := shared.DeSerialize(data)
+	if err != nil {
+		return err
+	}
+	log.Printf("Deserialized packet type: %T", message)
+	switch msg := message.(type) {
+	case *shared.RespPacket:
+		log.Printf("Sending RespMsg: %+v", msg)
+		msgs <- ResponseMsg{Code: msg.Success}
+	case *shared.LookRoomPacket:
+		log.Printf("Sending LookRoomMsg: %+v", msg)
+		msgs <- LookRoomMsg{Code: msg.Success, RoomID: msg.RoomID, RoomIP: msg.RoomIP}
+	case *shared.GameStartPacket:
+		log.Println("Game started packet found")
+		log.Printf("Sending GameStartMsg: %+v", msg)
+		msgs <- GameStartMsg{Code: msg.Success}
+	case *shared.GameClosePacket:
+		log.Printf("Sending GameCloseMsg: %+v", msg)
+		msgs <- GameCloseMsg{Code: msg.Success}
+	case *shared.BoardPacket:
+		board, err := DecodeRLE(msg.EncodedBoard)
 		if err != nil {
-			log.Printf("Error deserializing packet: %v", err)
-			return
+			log.Print(err.Error())
 		}
-		log.Printf("Deserialized packet type: %T", message)
-		switch msg := message.(type) {
-		case *shared.RespPacket:
-			log.Printf("Sending RespMsg: %+v", msg)
-			msgs <- ResponseMsg{Code: msg.Success}
-		case *shared.LookRoomPacket:
-			log.Printf("Sending LookRoomMsg: %+v", msg)
-			msgs <- LookRoomMsg{Code: msg.Success, RoomID: msg.RoomID, RoomIP: msg.RoomIP}
-		case *shared.GameStartPacket:
-			log.Println("Game started packet found")
-			log.Printf("Sending GameStartMsg: %+v", msg)
-			msgs <- GameStartMsg{Code: msg.Success}
-		case *shared.GameClosePacket:
-			log.Printf("Sending GameCloseMsg: %+v", msg)
-			msgs <- GameCloseMsg{Code: msg.Success}
-		case *shared.BoardPacket:
-			board, err := DecodeRLE(msg.EncodedBoard)
-			if err != nil {
-				log.Print(err.Error())
+		health := [2]int{msg.Health, msg.MaxHealth}
+		mana := [2]int{msg.Mana, msg.MaxMana}
+		xp := [2]int{msg.Xp, msg.XpNeeded}
+		spells := make([]SpellState, len(msg.Spells))
+		for i, spell := range msg.Spells {
+			spells[i] = SpellState{
+				Rank:     spell.Rank,
+				Cooldown: time.Duration(spell.CooldownMs) * time.Millisecond,
+				Ready:    spell.Ready,
 			}
-			health := [2]int{msg.Health, msg.MaxHealth}
-			mana := [2]int{msg.Mana, msg.MaxMana}
-			xp := [2]int{msg.Xp, msg.XpNeeded}
-			log.Printf("Sending BoardMsg: Health=%v, Level=%d, Xp=%v", health, msg.Level, xp)
-			msgs <- BoardMsg{Points: msg.Points, Health: health, Mana: mana, Level: msg.Level, Xp: xp, Board: board}
-		case *shared.DeltaPacket:
-			deltas := DecodeDeltas(msg.Deltas)
-			log.Printf("Sending DeltaMsg: TickID=%d, Deltas=%v", msg.TickID, deltas)
-			msgs <- DeltaMsg{Points: msg.Points, Deltas: deltas, TickID: msg.TickID}
-		case *shared.EndGamePacket:
-			log.Printf("Sending EndGameMsg: Win=%t", msg.Win)
-			msgs <- EndGameMsg{Win: msg.Win}
-		default:
-			log.Printf("Unknown type: %T, raw: %x", message, buf[:n])
-			msgs <- GamePacketMsg{Packet: buf[:n]}
 		}
+		log.Printf("Sending BoardMsg: Health=%v, Level=%d, Xp=%v, Spells=%v", health, msg.Level, xp, spells)
+		msgs <- BoardMsg{
+			Points:      msg.Points,
+			Health:      health,
+			Mana:        mana,
+			Level:       msg.Level,
+			Xp:          xp,
+			SkillPoints: msg.SkillPoints,
+			Spells:      spells,
+			ViewOrigin:  [2]int{msg.ViewRow, msg.ViewCol},
+			Board:       board,
+		}
+	case *shared.DeltaPacket:
+		deltas := DecodeDeltas(msg.Deltas)
+		log.Printf("Sending DeltaMsg: TickID=%d, Deltas=%v", msg.TickID, deltas)
+		msgs <- DeltaMsg{Points: msg.Points, Deltas: deltas, TickID: msg.TickID}
+	case *shared.EndGamePacket:
+		// Every player joins the blue team for now
+		win := msg.Winner == 1
+		log.Printf("Sending EndGameMsg: Win=%t", win)
+		msgs <- EndGameMsg{Win: win, Scores: msg.Scores}
+	case *shared.ScoreboardPacket:
+		log.Printf("Sending ScoreboardMsg: %+v", msg.Entries)
+		msgs <- ScoreboardMsg{Entries: msg.Entries}
+	case *shared.CastFailedPacket:
+		log.Printf("Sending CastFailedMsg: %+v", msg)
+		msgs <- CastFailedMsg{Slot: msg.Slot, NotEnoughMana: msg.Reason == 1}
+	case *shared.RespawnPacket:
+		remaining := time.Duration(msg.RemainingMs) * time.Millisecond
+		log.Printf("Sending RespawnMsg: Remaining=%v", remaining)
+		msgs <- RespawnMsg{Remaining: remaining}
+	case *shared.TickRatePacket:
+		tickRate := TickRateMsg{
+			Period: time.Duration(msg.PeriodMs) * time.Millisecond,
+			Rate:   float64(msg.RateCentihertz) / 100,
+			Drift:  time.Duration(msg.DriftMs) * time.Millisecond,
+		}
+		log.Printf("Sending TickRateMsg: %+v", tickRate)
+		msgs <- tickRate
+	default:
+		log.Printf("Unknown type: %T, raw: %x", message, data)
+		msgs <- GamePacketMsg{Packet: data}
 	}
+	return nil
 }
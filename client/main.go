@@ -133,8 +133,13 @@ func (m MetaModel) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 				conn, err := communication.MakeConnection(msg.RoomIP)
 				if err == nil {
 					m.GameConnection = conn
-					// Send spell selection after successful game connection
-					communication.SendSpellSelectionPacket(m.GameConnection, m.LobbyModel.SelectedSpells[0], m.LobbyModel.SelectedSpells[1])
+					// Send spell and champion selection after successful game connection
+					communication.SendSpellSelectionPacket(
+						m.GameConnection,
+						m.LobbyModel.SelectedSpells[0],
+						m.LobbyModel.SelectedSpells[1],
+						m.LobbyModel.SelectedChampion,
+					)
 					break
 				}
 			}
@@ -155,6 +160,17 @@ func (m MetaModel) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 			m.GameOverModel = model.NewGameOverModel(msg.Code)
 			m.GameOverModel.SetDimension(m.height, m.width)
 			return m, m.GameOverModel.Init()
+		case communication.EndGameMsg:
+			// Same codes as GameCloseMsg, 0: win 1: lose
+			code := 1
+			if msg.Win {
+				code = 0
+			}
+			m.state = GameOver
+			m.GameOverModel = model.NewGameOverModel(code)
+			m.GameOverModel.SetScores(msg.Scores)
+			m.GameOverModel.SetDimension(m.height, m.width)
+			return m, m.GameOverModel.Init()
 		default:
 			newmodel, cmd = m.GameModel.Update(msg)
 			m.GameModel = newmodel.(model.GameModel)
@@ -214,7 +230,8 @@ func main() {
 		log.Fatal(err)
 	}
 	defer f.Close()
-	p := tea.NewProgram(model, tea.WithAltScreen())
+	// The mouse sends the champion to a cell of the board
+	p := tea.NewProgram(model, tea.WithAltScreen(), tea.WithMouseCellMotion())
 	// Serve as a bridge to pass message from ListenForPackets to models
 	go func() {
 		for msg := range model.msgs {
@@ -0,0 +1,36 @@
+package model
+
+import "fmt"
+
+// Champion represents a champion of the roster, defined in game/stats.toml.
+type Champion struct {
+	ID        int
+	Name      string
+	Attack    string
+	Health    int
+	Mana      int
+	Armor     int
+	Signature string
+}
+
+// String returns a formatted string for the champion's stats.
+func (c Champion) String() string {
+	return fmt.Sprintf(
+		"Name: %s\nAttack: %s\nHealth: %d\nMana: %d\nArmor: %d\nSignature (R): %s",
+		c.Name,
+		c.Attack,
+		c.Health,
+		c.Mana,
+		c.Armor,
+		c.Signature,
+	)
+}
+
+// availableChampions holds the hardcoded data for the champions of the roster.
+// The signature spell comes with the champion and cannot be picked.
+var availableChampions = []Champion{
+	{ID: 0, Name: "Warrior", Attack: "melee", Health: 200, Mana: 100, Armor: 5, Signature: "Shield Wall, a short stunning wall"},
+	{ID: 1, Name: "Marksman", Attack: "ranged", Health: 150, Mana: 100, Armor: 3, Signature: "Long Shot, a long range shot"},
+	{ID: 2, Name: "Tank", Attack: "melee", Health: 300, Mana: 80, Armor: 12, Signature: "Bulwark, a wide wall with a long stun"},
+	{ID: 3, Name: "Mage", Attack: "ranged", Health: 160, Mana: 200, Armor: 3, Signature: "Meteor, a slow and heavy fireball"},
+}
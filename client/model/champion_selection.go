@@ -0,0 +1,112 @@
+package model
+
+import (
+	"fmt"
+	"strings"
+
+	"github.com/charmbracelet/bubbles/key"
+	"github.com/charmbracelet/bubbletea"
+	"github.com/charmbracelet/lipgloss"
+)
+
+// ChampionSelectionModel manages the state of the champion selection UI.
+type ChampionSelectionModel struct {
+	styles        *Styles
+	Champions     []Champion
+	FocusedIndex  int
+	SelectedIndex int
+	height, width int
+}
+
+func (m *ChampionSelectionModel) SetDimension(height, width int) {
+	m.height = height
+	m.width = width
+}
+
+func NewChampionSelection(styles *Styles) ChampionSelectionModel {
+	return ChampionSelectionModel{
+		styles:        styles,
+		Champions:     availableChampions,
+		FocusedIndex:  0,
+		SelectedIndex: 0,
+	}
+}
+
+func (m ChampionSelectionModel) Init() tea.Cmd {
+	return nil
+}
+
+type ChampionSelectedMsg struct {
+	ChampionID int
+}
+
+func (m ChampionSelectionModel) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
+	switch msg := msg.(type) {
+	case tea.KeyMsg:
+		switch {
+		case key.Matches(msg, upKey):
+			if m.FocusedIndex > 0 {
+				m.FocusedIndex--
+			}
+		case key.Matches(msg, downKey):
+			if m.FocusedIndex < len(m.Champions)-1 {
+				m.FocusedIndex++
+			}
+		case key.Matches(msg, enterKey):
+			m.SelectedIndex = m.FocusedIndex
+			championID := m.Champions[m.SelectedIndex].ID
+			return m, func() tea.Msg {
+				return ChampionSelectedMsg{ChampionID: championID}
+			}
+		}
+	}
+	return m, nil
+}
+
+func (m ChampionSelectionModel) View() string {
+	var left, right strings.Builder
+
+	// Left Panel: List of the champions
+	left.WriteString("Choose Your Champion\n\n")
+	for i, champion := range m.Champions {
+		cursor := "  "
+		if m.FocusedIndex == i {
+			cursor = lipgloss.NewStyle().
+				Foreground(lipgloss.Color("205")).
+				Render("> ")
+		}
+
+		selected := " "
+		if m.SelectedIndex == i {
+			selected = "X"
+		}
+
+		nameStyle := lipgloss.NewStyle().Foreground(lipgloss.Color("240"))
+		if m.FocusedIndex == i {
+			nameStyle = lipgloss.NewStyle().Foreground(lipgloss.Color("205")).Bold(true)
+		}
+
+		left.WriteString(fmt.Sprintf("%s [%s] %s\n", cursor, selected, nameStyle.Render(champion.Name)))
+	}
+
+	// Right Panel: Details of the focused champion
+	if m.FocusedIndex >= 0 && m.FocusedIndex < len(m.Champions) {
+		right.WriteString(m.Champions[m.FocusedIndex].String())
+	}
+
+	optionsStyle := lipgloss.NewStyle().
+		Align(lipgloss.Left).
+		Padding(1, 0)
+
+	detailsStyle := lipgloss.NewStyle().
+		Align(lipgloss.Left).
+		Border(lipgloss.NormalBorder(), true, true, true, true).
+		BorderForeground(m.styles.BorderColor).
+		Padding(1, 0)
+
+	return lipgloss.JoinHorizontal(
+		lipgloss.Center,
+		optionsStyle.Render(left.String()),
+		detailsStyle.Render(right.String()),
+	)
+}
@@ -3,12 +3,14 @@ package model
 import (
 	"fmt"
 	"log"
+	"math"
 	"net"
 	"strconv"
 	"strings"
 	"time"
 
 	"github.com/GrGLeo/ctf/client/communication"
+	"github.com/GrGLeo/ctf/shared"
 	"github.com/charmbracelet/bubbles/progress"
 	tea "github.com/charmbracelet/bubbletea"
 	"github.com/charmbracelet/lipgloss"
@@ -16,6 +18,7 @@ import (
 
 type GameModel struct {
 	currentBoard   [21][51]int
+	viewOrigin     [2]int
 	conn           *net.TCPConn
 	gameClock      time.Duration
 	height, width  int
@@ -27,6 +30,17 @@ type GameModel struct {
 	mana           [2]int
 	level          int
 	xp             [2]int
+	skillPoints    int
+	spells         []communication.SpellState
+	castFailed     string
+	castFailedAt   time.Time
+	scoreboard     []shared.ScoreEntry
+	showScoreboard bool
+	// The respawn countdown follows each board while the champion is dead
+	dead           bool
+	respawnSeen    bool
+	respawnIn      time.Duration
+	tickRate       float64
 	points         [2]int
 	attackMode     bool
 	dashed         bool
@@ -76,7 +90,34 @@ func (m GameModel) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 		m.mana = msg.Mana
 		m.level = msg.Level
 		m.xp = msg.Xp
+		m.skillPoints = msg.SkillPoints
+		m.spells = msg.Spells
 		m.currentBoard = msg.Board
+		m.viewOrigin = msg.ViewOrigin
+		// No countdown came after the previous board, the champion is back
+		if !m.respawnSeen {
+			m.dead = false
+		}
+		m.respawnSeen = false
+	case communication.TickRateMsg:
+		m.tickRate = msg.Rate
+		return m, nil
+	case communication.RespawnMsg:
+		m.dead = true
+		m.respawnSeen = true
+		m.respawnIn = msg.Remaining
+		return m, nil
+	case communication.ScoreboardMsg:
+		m.scoreboard = msg.Entries
+		return m, nil
+	case communication.CastFailedMsg:
+		reason := "on cooldown"
+		if msg.NotEnoughMana {
+			reason = "not enough mana"
+		}
+		m.castFailed = fmt.Sprintf("Spell %d: %s", msg.Slot+1, reason)
+		m.castFailedAt = time.Now()
+		return m, nil
 	case communication.DeltaMsg:
 		m.gameClock = time.Duration(50*int(msg.TickID)) * time.Millisecond
 		points := msg.Points
@@ -101,12 +142,46 @@ func (m GameModel) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 		case "d":
 			communication.SendAction(m.conn, 4)
 			return m, nil
+		case "y":
+			// Diagonal moves, on the y u b n keys of roguelikes
+			communication.SendAction(m.conn, 12)
+			return m, nil
+		case "u":
+			communication.SendAction(m.conn, 13)
+			return m, nil
+		case "b":
+			communication.SendAction(m.conn, 14)
+			return m, nil
+		case "n":
+			communication.SendAction(m.conn, 15)
+			return m, nil
 		case "q":
 			communication.SendAction(m.conn, 5)
 			return m, nil
 		case "e":
 			communication.SendAction(m.conn, 6)
 			return m, nil
+		case "1":
+			// Spend a skill point on the spell of slot 1
+			communication.SendAction(m.conn, 8)
+			return m, nil
+		case "2":
+			communication.SendAction(m.conn, 9)
+			return m, nil
+		case "r":
+			// Signature spell of the champion
+			communication.SendAction(m.conn, 10)
+			return m, nil
+		case "3":
+			communication.SendAction(m.conn, 11)
+			return m, nil
+		case "tab":
+			m.showScoreboard = !m.showScoreboard
+			if m.showScoreboard {
+				// Refreshed with the next board instead of the next second
+				communication.SendAction(m.conn, 16)
+			}
+			return m, nil
 		case "v":
 			if m.attackMode {
 				m.attackMode = false
@@ -116,6 +191,21 @@ func (m GameModel) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 			communication.SendAction(m.conn, 7)
 			return m, nil
 		}
+	case tea.MouseMsg:
+		if msg.Action != tea.MouseActionPress {
+			return m, nil
+		}
+		if msg.Button != tea.MouseButtonLeft && msg.Button != tea.MouseButtonRight {
+			return m, nil
+		}
+		row, col, ok := m.boardCell(msg.X, msg.Y)
+		if !ok {
+			return m, nil
+		}
+		// Left click moves to the cell, right click attack moves
+		attackMove := msg.Button == tea.MouseButtonRight
+		communication.SendMoveToPacket(m.conn, attackMove, m.viewOrigin[0]+row, m.viewOrigin[1]+col)
+		return m, nil
 	case communication.CooldownTickMsg:
 		var percent float64
 		if m.dashed {
@@ -135,10 +225,49 @@ func (m GameModel) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 func (m GameModel) View() string {
 	log.Println(m.points)
 	log.Printf("Player Health: %d | %d\n", m.health[0], m.health[1])
+	return lipgloss.Place(
+		m.width,
+		m.height,
+		lipgloss.Center,
+		lipgloss.Center,
+		m.content(),
+	)
+}
+
+// boardCell finds the cell of the board under the mouse, in the layout of View
+func (m GameModel) boardCell(x, y int) (int, int, bool) {
+	content := m.content()
+	left := centerOffset(m.width, lipgloss.Width(content))
+	top := centerOffset(m.height, lipgloss.Height(content))
+	// The hud is above the board, and the border is only drawn in attack mode
+	row := y - top - 1
+	col := x - left
+	if m.attackMode {
+		row--
+		col--
+	}
+	if row < 0 || row >= len(m.currentBoard) || col < 0 || col >= len(m.currentBoard[0]) {
+		return 0, 0, false
+	}
+	return row, col, true
+}
+
+// centerOffset is the margin lipgloss.Place leaves before centered content
+func centerOffset(space, size int) int {
+	gap := space - size
+	if gap <= 0 {
+		return 0
+	}
+	return int(math.Round(float64(gap) * 0.5))
+}
+
+// content draws the hud, the board and the champion status
+func (m GameModel) content() string {
 	// Define styles
 	bgStyle := lipgloss.NewStyle().Background(lipgloss.Color("0"))
 	p1Style := lipgloss.NewStyle().Background(lipgloss.Color("21"))
 	TowerDest := lipgloss.NewStyle().Background(lipgloss.Color("91"))
+	InnerTowerDest := lipgloss.NewStyle().Background(lipgloss.Color("53"))
 	bushStyle := lipgloss.NewStyle().Background(lipgloss.Color("34"))
 	p4Style := lipgloss.NewStyle().Background(lipgloss.Color("1"))
 	grayStyle := lipgloss.NewStyle().Background(lipgloss.Color("240"))
@@ -167,10 +296,15 @@ func (m GameModel) View() string {
 
 	minutes := int(m.gameClock.Minutes())
 	seconds := int(m.gameClock.Seconds()) % 60
-	clockStr := HudStyle.Render(fmt.Sprintf("%02d:%02d", minutes, seconds))
+	clockText := fmt.Sprintf("%02d:%02d", minutes, seconds)
+	if m.tickRate > 0 {
+		clockText += fmt.Sprintf(" %.1f tps", m.tickRate)
+	}
+	clockStr := HudStyle.Render(clockText)
 
+	// The hud and the clock span the 51 columns of the board
 	hud := lipgloss.Place(
-		46,
+		51-lipgloss.Width(clockStr),
 		1,
 		lipgloss.Center,
 		lipgloss.Center,
@@ -221,6 +355,8 @@ func (m GameModel) View() string {
 				builder.WriteString(bgStyle.Render("𐁙")) // Render for freezing spell
 			case 16:
 				builder.WriteString(MonsterStyle.Render(" ")) // Render for monster
+			case 17:
+				builder.WriteString(InnerTowerDest.Render(" ")) // Render for destroyed inner tower
 			case 100, 101, 102, 103, 104, 105, 106, 107: // Friendly minion health (1/8 to 8/8)
 				healthIndex := cell - 100
 				builder.WriteString(p1Style.Render(minionHealthChars[healthIndex]))
@@ -232,6 +368,16 @@ func (m GameModel) View() string {
 		builder.WriteString("\n") // New line at the end of each row
 	}
 
+	if m.dead {
+		if m.respawnIn > 0 {
+			builder.WriteString(fmt.Sprintf("Respawn in %.1fs", m.respawnIn.Seconds()))
+		} else {
+			// The spawn zone is full
+			builder.WriteString("Waiting for a free spawn cell")
+		}
+		builder.WriteString("\n")
+	}
+
 	var healthBar string
 	if m.health[1] > 0 {
 		healthPercent := (float32(m.health[0]) / float32(m.health[1]))
@@ -273,6 +419,16 @@ func (m GameModel) View() string {
 	)
 	builder.WriteString(xpHUD)
 	builder.WriteString("\n")
+	builder.WriteString(spellsInfo(m.spells, m.skillPoints))
+	if time.Since(m.castFailedAt) < castFailedDisplay {
+		builder.WriteString("  " + m.castFailed)
+	}
+	builder.WriteString("\n")
+
+	if m.showScoreboard {
+		builder.WriteString(scoreboardView(m.scoreboard))
+		builder.WriteString("\n")
+	}
 
 	var progressBar string
 	if m.percent != 0.0 {
@@ -281,13 +437,39 @@ func (m GameModel) View() string {
 	builder.WriteString(progressBar)
 	gameStyle := lipgloss.NewStyle().Border(lipgloss.NormalBorder(), m.attackMode).BorderForeground(lipgloss.Color("#ff0000"))
 
-	return lipgloss.Place(
-		m.width,
-		m.height,
-		lipgloss.Center,
-		lipgloss.Center,
-		gameStyle.Render(builder.String()),
-	)
+	return gameStyle.Render(builder.String())
+}
+
+// How long a refused cast is shown under the spells
+const castFailedDisplay = time.Second
+
+// spellsInfo shows the rank and cooldown of each spell slot, and the skill
+// points left to spend
+func spellsInfo(spells []communication.SpellState, skillPoints int) string {
+	keys := []string{"Q", "E", "R"}
+	var slots []string
+	for i, spell := range spells {
+		key := "?"
+		if i < len(keys) {
+			key = keys[i]
+		}
+		var state string
+		switch {
+		case spell.Rank == 0:
+			state = "-"
+		case spell.Cooldown > 0:
+			state = fmt.Sprintf("%.1fs", spell.Cooldown.Seconds())
+		case !spell.Ready:
+			state = "no mana"
+		default:
+			state = "up"
+		}
+		slots = append(slots, fmt.Sprintf("%s%d %s", key, spell.Rank, state))
+	}
+	if skillPoints > 0 {
+		slots = append(slots, fmt.Sprintf("SP %d", skillPoints))
+	}
+	return strings.Join(slots, " | ")
 }
 
 func doTick() tea.Cmd {
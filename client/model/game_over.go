@@ -3,12 +3,14 @@ package model
 import (
 	"time"
 
+	"github.com/GrGLeo/ctf/shared"
 	tea "github.com/charmbracelet/bubbletea"
 	"github.com/charmbracelet/lipgloss"
 )
 
 type GameOverModel struct {
 	win           int
+	scores        []shared.ScoreEntry
 	height, width int
 }
 
@@ -16,6 +18,11 @@ func NewGameOverModel(win int) GameOverModel {
 	return GameOverModel{win: win}
 }
 
+// SetScores sets the final scoreboard sent with the end of the game
+func (m *GameOverModel) SetScores(scores []shared.ScoreEntry) {
+	m.scores = scores
+}
+
 func (m GameOverModel) Init() tea.Cmd {
 	return tea.Tick(5*time.Second, func(t time.Time) tea.Msg {
 		return BackToLobbyMsg{}
@@ -42,6 +49,9 @@ func (m GameOverModel) View() string {
 	} else {
 		s = "You Lose!"
 	}
+	if len(m.scores) > 0 {
+		s = lipgloss.JoinVertical(lipgloss.Center, s, "", scoreboardView(m.scores))
+	}
 
 	return lipgloss.Place(
 		m.width,
@@ -18,10 +18,12 @@ type LobbyModel struct {
 	queueModel          QueueModel
 	createModel         CreateModel
 	spellSelectionModel SpellSelectionModel
+	championModel       ChampionSelectionModel
 	conn                *net.TCPConn
 	looking             bool
 	width, height       int
 	SelectedSpells      [2]int
+	SelectedChampion    int
 }
 
 func NewLobbyModel(conn *net.TCPConn) LobbyModel {
@@ -29,6 +31,7 @@ func NewLobbyModel(conn *net.TCPConn) LobbyModel {
 	createModel := NewCreateModel(conn)
 	s := DefaultStyles()
 	spellSelectionModel := NewSpellSelection(s)
+	championModel := NewChampionSelection(s)
 
 	return LobbyModel{
 		styles:              s,
@@ -36,6 +39,7 @@ func NewLobbyModel(conn *net.TCPConn) LobbyModel {
 		queueModel:          queueModel,
 		createModel:         createModel,
 		spellSelectionModel: spellSelectionModel,
+		championModel:       championModel,
 		conn:                conn,
 	}
 }
@@ -52,6 +56,7 @@ func (m *LobbyModel) SetDimension(height, width int) {
 	m.queueModel.SetDimension(height, width)
 	m.createModel.SetDimension(height, width)
 	m.spellSelectionModel.SetDimension(height, width)
+	m.championModel.SetDimension(height, width)
 }
 
 func (m *LobbyModel) SetLooking(search bool) {
@@ -72,15 +77,18 @@ func (m LobbyModel) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 	case tea.KeyMsg:
 		switch msg.String() {
 		case "left":
-			m.tabSelected = (m.tabSelected - 1 + 3) % 3
+			m.tabSelected = (m.tabSelected - 1 + 4) % 4
 		case "right":
-			m.tabSelected = (m.tabSelected + 1) % 3
+			m.tabSelected = (m.tabSelected + 1) % 4
 		case "q", "esc", "ctrl+c":
 			return m, tea.Quit
 		}
 	case SpellsSelectedMsg:
 		m.SelectedSpells = msg.SpellIDs
 		log.Printf("LobbyModel received selected spells: %v", m.SelectedSpells)
+	case ChampionSelectedMsg:
+		m.SelectedChampion = msg.ChampionID
+		log.Printf("LobbyModel received selected champion: %d", m.SelectedChampion)
 	}
 
 	if m.tabSelected == 0 {
@@ -88,6 +96,10 @@ func (m LobbyModel) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 		ssm, cmd = m.spellSelectionModel.Update(msg)
 		m.spellSelectionModel = ssm.(SpellSelectionModel)
 	} else if m.tabSelected == 1 {
+		var chm tea.Model
+		chm, cmd = m.championModel.Update(msg)
+		m.championModel = chm.(ChampionSelectionModel)
+	} else if m.tabSelected == 2 {
 		var qm tea.Model
 		qm, cmd = m.queueModel.Update(msg)
 		m.queueModel = qm.(QueueModel)
@@ -106,10 +118,11 @@ func (m LobbyModel) View() string {
 
 	// Render Tabs based on selection
 	spellSelectionTabStr := "Spell Selection"
+	championTabStr := "Champion Selection"
 	joinGameTabStr := "Join a game"
 	createGameTabStr := "Create a game"
 
-	tabs := []string{spellSelectionTabStr, joinGameTabStr, createGameTabStr}
+	tabs := []string{spellSelectionTabStr, championTabStr, joinGameTabStr, createGameTabStr}
 
 	for i, tab := range tabs {
 		if i == m.tabSelected {
@@ -122,6 +135,8 @@ func (m LobbyModel) View() string {
 	if m.tabSelected == 0 {
 		content = m.spellSelectionModel.View()
 	} else if m.tabSelected == 1 {
+		content = m.championModel.View()
+	} else if m.tabSelected == 2 {
 		content = m.queueModel.View()
 	} else {
 		content = m.createModel.View()
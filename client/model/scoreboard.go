@@ -0,0 +1,35 @@
+package model
+
+import (
+	"fmt"
+	"strings"
+
+	"github.com/GrGLeo/ctf/shared"
+)
+
+// scoreboardView renders one line per player: kills, deaths and assists,
+// then the farm, towers and damage counters
+func scoreboardView(entries []shared.ScoreEntry) string {
+	teams := []string{"Red", "Blue"}
+	var lines []string
+	lines = append(lines, fmt.Sprintf("%-6s %-5s %-8s %-5s %-5s %-6s %-7s %-7s", "Player", "Team", "K/D/A", "CS", "Camps", "Towers", "Dealt", "Taken"))
+	for _, entry := range entries {
+		team := "?"
+		if entry.Team < len(teams) {
+			team = teams[entry.Team]
+		}
+		kda := fmt.Sprintf("%d/%d/%d", entry.Kills, entry.Deaths, entry.Assists)
+		lines = append(lines, fmt.Sprintf(
+			"%-6d %-5s %-8s %-5d %-5d %-6d %-7d %-7d",
+			entry.PlayerID,
+			team,
+			kda,
+			entry.MinionKills,
+			entry.MonsterKills,
+			entry.TowersDestroyed,
+			entry.DamageDealt,
+			entry.DamageTaken,
+		))
+	}
+	return strings.Join(lines, "\n")
+}
@@ -47,11 +47,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     });
 
-    let mut rng = rand::thread_rng();
-    let mut message_counter = 1;
+    let mut rng = rand::rng();
     loop {
         // Generate a random action value between 1 and 5
-        let action_value: u8 = rng.gen_range(1..=5);
+        let action_value: u8 = rng.random_range(1..=5);
 
         // Create a new ActionPacket
         let packet = ActionPacket {
@@ -70,7 +69,6 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
         println!("Client sent action packet: {:?}", packet);
 
-        message_counter += 1;
         sleep(Duration::from_secs(1)).await;
     }
 
@@ -1,12 +1,23 @@
 use std::collections::HashMap;
 use std::fs;
+use std::time::Duration;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct BaseStats {
     pub health: u16,
     pub armor: u16,
+    #[serde(default)]
+    pub magic_resist: u16,
+}
+
+/// Default auto-attack of a unit, ranged attacks fire a homing projectile
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum AttackType {
+    Melee,
+    Ranged { projectile_speed: u32 },
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -16,12 +27,42 @@ pub struct ChampionStats {
     pub health: u16,
     pub mana: u16,
     pub armor: u16,
+    #[serde(default)]
+    pub magic_resist: u16,
+    /// Armor of the target ignored by auto attacks, after the percent penetration
+    #[serde(default)]
+    pub armor_penetration_flat: u16,
+    /// Share of the armor of the target ignored by auto attacks, from 0 to 1
+    #[serde(default)]
+    pub armor_penetration_percent: f32,
+    /// Chance of an auto attack to be a critical strike, from 0 to 1
+    #[serde(default)]
+    pub crit_chance: f32,
+    #[serde(default = "default_crit_multiplier")]
+    pub crit_multiplier: f32,
     pub xp_per_level: Vec<u32>,
     pub level_up_health_increase: u16,
     pub level_up_attack_damage_increase: u16,
     pub level_up_armor_increase: u16,
     pub attack_range_row: u16,
     pub attack_range_col: u16,
+    pub attack: AttackType,
+    /// Ticks needed to walk one cell
+    pub movement_speed: u16,
+}
+
+fn default_crit_multiplier() -> f32 {
+    1.75
+}
+
+/// A playable champion archetype of the roster
+#[derive(Debug, Deserialize, Clone)]
+pub struct ChampionKit {
+    pub id: u8,
+    pub name: String,
+    pub signature_spell: u8,
+    #[serde(flatten)]
+    pub stats: ChampionStats,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -30,10 +71,85 @@ pub struct MinionStats {
     pub attack_speed_ms: u64,
     pub health: u16,
     pub armor: u16,
+    #[serde(default)]
+    pub magic_resist: u16,
     pub aggro_range_row: u16,
     pub aggro_range_col: u16,
     pub attack_range_row: u16,
     pub attack_range_col: u16,
+    pub attack: AttackType,
+    /// Ticks needed to walk one cell
+    pub movement_speed: u16,
+}
+
+impl MinionStats {
+    /// Returns the stats grown by the scaling ratios for each elapsed scaling interval
+    pub fn scaled(&self, game_time: Duration, rules: &MinionRules) -> MinionStats {
+        let steps = match rules.scaling_interval_secs {
+            0 => 0,
+            interval => game_time.as_secs() / interval,
+        } as f32;
+        let mut stats = self.clone();
+        stats.health = (self.health as f32 * (1.0 + steps * rules.health_scaling)) as u16;
+        stats.attack_damage =
+            (self.attack_damage as f32 * (1.0 + steps * rules.attack_damage_scaling)) as u16;
+        stats
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MinionKind {
+    Melee,
+    Caster,
+    Siege,
+    Super,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct MinionRoster {
+    pub melee: MinionStats,
+    pub caster: MinionStats,
+    pub siege: MinionStats,
+    #[serde(rename = "super")]
+    pub super_minion: MinionStats,
+}
+
+impl MinionRoster {
+    pub fn stats(&self, kind: MinionKind) -> &MinionStats {
+        match kind {
+            MinionKind::Melee => &self.melee,
+            MinionKind::Caster => &self.caster,
+            MinionKind::Siege => &self.siege,
+            MinionKind::Super => &self.super_minion,
+        }
+    }
+}
+
+/// Position of a tower in its lane, from the river to the base
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TowerTier {
+    Outer,
+    Inner,
+    Inhibitor,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct TowerRoster {
+    pub outer: TowerStats,
+    pub inner: TowerStats,
+    pub inhibitor: TowerStats,
+}
+
+impl TowerRoster {
+    pub fn stats(&self, tier: TowerTier) -> &TowerStats {
+        match tier {
+            TowerTier::Outer => &self.outer,
+            TowerTier::Inner => &self.inner,
+            TowerTier::Inhibitor => &self.inhibitor,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -42,42 +158,206 @@ pub struct TowerStats {
     pub attack_speed_secs: u64,
     pub health: u16,
     pub armor: u16,
+    #[serde(default)]
+    pub magic_resist: u16,
     pub attack_range_row: u16,
     pub attack_range_col: u16,
+    /// Extra damage of each consecutive hit on the same champion, in percent of the attack damage
+    pub damage_ramp_percent: u16,
+    /// Consecutive hits on a champion that still raise the damage
+    pub max_damage_ramp_hits: u8,
 }
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct MonsterStats {
     pub id: String,
-    pub spawn_row:  u16,
-    pub spawn_col: u16, 
     pub attack_damage: u16,
     pub attack_speed_ms: u64,
     pub health: u16,
     pub armor: u16,
+    #[serde(default)]
+    pub magic_resist: u16,
+    /// Camp-mates within this range join the fight
     pub aggro_range_row: u8,
     pub aggro_range_col: u8,
-    pub attack_range_row: u8,
-    pub attack_range_col: u8,
     pub leash_range: u8,
     pub xp_reward: u8,
     pub respawn_timer_secs: u16,
+    /// Ticks needed to walk one cell
+    pub movement_speed: u16,
+    /// Buff given on kill to the last hitter, or to its whole team
+    #[serde(default)]
+    pub reward_buff: Option<KillBuffStats>,
+    #[serde(default)]
+    pub ability: Option<MonsterAbilityStats>,
 }
 
+/// Timed bonus granted by killing a monster
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct KillBuffStats {
+    pub id: String,
+    pub duration_secs: u64,
+    /// Every champion of the killer's team gets the buff
+    #[serde(default)]
+    pub team_wide: bool,
+    #[serde(default)]
+    pub bonus_attack_damage: u16,
+    /// Health restored every second
+    #[serde(default)]
+    pub health_regen: u16,
+}
+
+/// Slam hitting every champion around the monster while it fights
 #[derive(Debug, Deserialize, Clone)]
-pub struct SpellStats {
-    pub id: u8,
+pub struct MonsterAbilityStats {
+    pub damage: u16,
+    pub radius: u16,
+    pub cooldown_secs: u64,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct CampMember {
+    pub monster: String,
+    /// Spawn cell relative to the camp position of the map
+    pub offset: (i16, i16),
+}
+
+/// A group of monsters sharing their aggro and respawning together
+#[derive(Debug, Deserialize, Clone)]
+pub struct CampStats {
+    pub id: String,
+    pub members: Vec<CampMember>,
+    /// Delay before the camp respawns, from the death of its last monster
+    pub respawn_timer_secs: u64,
+    /// Delay before the first spawn, from the spawn of the initial camps
+    #[serde(default)]
+    pub first_spawn_secs: u64,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct SpellRankStats {
     pub mana_cost: u16,
     pub cooldown_secs: u8,
-    pub range: u16,
-    pub speed: u32,
-    pub width: u8,
     pub damage_ratio: f32,
     pub base_damage: u16,
     #[serde(default)]
     pub stun_duration: Option<u8>,
 }
 
+/// Shape of a spell, several spells can share one with their own stats
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SpellKind {
+    FreezeWall,
+    Fireball,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct SpellStats {
+    pub id: u8,
+    pub kind: SpellKind,
+    pub range: u16,
+    pub speed: u32,
+    pub width: u8,
+    /// Signature spells come with a champion, they cannot be picked for slots 1 and 2
+    #[serde(default)]
+    pub signature: bool,
+    pub ranks: Vec<SpellRankStats>,
+}
+
+impl SpellStats {
+    /// Returns the values for a given rank, ranks start at 1.
+    /// Out of bound ranks are clamped to the closest defined rank.
+    pub fn rank_stats(&self, rank: u8) -> &SpellRankStats {
+        let index = (rank.max(1) as usize - 1).min(self.ranks.len() - 1);
+        &self.ranks[index]
+    }
+
+    pub fn max_rank(&self) -> u8 {
+        self.ranks.len() as u8
+    }
+}
+
+/// A group of minions in a wave, only spawned every `every` waves
+#[derive(Debug, Deserialize, Clone)]
+pub struct WaveGroup {
+    pub kind: MinionKind,
+    pub count: u8,
+    #[serde(default = "default_wave_frequency")]
+    pub every: u32,
+}
+
+fn default_wave_frequency() -> u32 {
+    1
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct TimingRules {
+    pub game_tick_ms: u64,
+    pub minion_wave_interval_secs: u64,
+}
+
+impl TimingRules {
+    /// Time between two ticks, the budget of a tick
+    pub fn tick_period(&self) -> Duration {
+        Duration::from_millis(self.game_tick_ms)
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct MinionRules {
+    pub spawn_delay_ms: u64,
+    pub super_minions_per_wave: u8,
+    pub scaling_interval_secs: u64,
+    pub health_scaling: f32,
+    pub attack_damage_scaling: f32,
+    pub waves: Vec<WaveGroup>,
+}
+
+impl MinionRules {
+    /// Returns the kinds of minions spawned in a lane for a wave, in spawn order.
+    /// Waves are numbered from 1.
+    pub fn wave_composition(&self, wave_number: u32) -> Vec<MinionKind> {
+        self.waves
+            .iter()
+            .filter(|group| wave_number.is_multiple_of(group.every))
+            .flat_map(|group| std::iter::repeat_n(group.kind, group.count as usize))
+            .collect()
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct PathfindingRules {
+    /// Cells expanded by all the path searches of a tick
+    pub nodes_per_tick: usize,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ChampionRules {
+    pub champion_respawn_base_time: u64,
+    pub champion_respawn_time_per_level: u64,
+    /// Champions who hit a champion this long before its death get an assist
+    pub assist_window_secs: u64,
+}
+
+impl ChampionRules {
+    /// Time a champion of this level waits before respawning
+    pub fn respawn_delay(&self, level: u8) -> Duration {
+        Duration::from_secs(
+            self.champion_respawn_base_time
+                + self.champion_respawn_time_per_level * level.saturating_sub(1) as u64,
+        )
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct Rules {
+    pub timing_rules: TimingRules,
+    pub minion_rules: MinionRules,
+    pub pathfinding_rules: PathfindingRules,
+    pub champion_rules: ChampionRules,
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct SpellFile {
     spell: Vec<SpellStats>,
@@ -86,28 +366,88 @@ pub struct SpellFile {
 #[derive(Debug, Deserialize, Clone)]
 pub struct GameConfig {
     pub base: BaseStats,
-    pub champion: ChampionStats,
-    pub minion: MinionStats,
-    pub tower: TowerStats,
+    pub champions: Vec<ChampionKit>,
+    pub minions: MinionRoster,
+    pub tower: TowerRoster,
     pub neutral_monsters: Vec<MonsterStats>,
+    #[serde(default)]
+    pub neutral_camps: Vec<CampStats>,
     #[serde(skip)]
     pub spells: HashMap<u8, SpellStats>,
+    #[serde(skip)]
+    pub rules: Rules,
 }
 
 impl GameConfig {
-    pub fn load(config_path: &str, spell_path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+    pub fn load(
+        config_path: &str,
+        spell_path: &str,
+        rules_path: &str,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
         let content = fs::read_to_string(config_path)?;
         let mut config: GameConfig = toml::from_str(&content)?;
 
         let spell_content = fs::read_to_string(spell_path)?;
         let spells_file: SpellFile = toml::from_str(&spell_content)?;
 
+        if let Some(spell) = spells_file
+            .spell
+            .iter()
+            .find(|spell| spell.ranks.is_empty())
+        {
+            return Err(format!("Spell {} has no rank defined", spell.id).into());
+        }
+
+        if config.champions.is_empty() {
+            return Err("No champion defined in the roster".into());
+        }
+        for (index, kit) in config.champions.iter().enumerate() {
+            if config.champions[..index].iter().any(|c| c.id == kit.id) {
+                return Err(format!("Champion id {} is defined twice", kit.id).into());
+            }
+            match spells_file
+                .spell
+                .iter()
+                .find(|spell| spell.id == kit.signature_spell)
+            {
+                None => {
+                    return Err(format!(
+                        "Champion {} has an unknown signature spell {}",
+                        kit.name, kit.signature_spell
+                    )
+                    .into());
+                }
+                Some(spell) if !spell.signature => {
+                    return Err(format!(
+                        "Champion {} uses the selectable spell {} as signature spell",
+                        kit.name, kit.signature_spell
+                    )
+                    .into());
+                }
+                Some(_) => {}
+            }
+        }
+
         config.spells = spells_file
             .spell
             .into_iter()
             .map(|spell_conf| (spell_conf.id, spell_conf))
             .collect();
 
+        let rules_content = fs::read_to_string(rules_path)?;
+        config.rules = toml::from_str(&rules_content)?;
+        if config.rules.timing_rules.game_tick_ms == 0 {
+            return Err("The game tick must last at least 1ms".into());
+        }
+        if config.rules.minion_rules.wave_composition(1).is_empty() {
+            return Err("The first minion wave is empty".into());
+        }
+
         Ok(config)
     }
+
+    /// Returns the champion of the roster matching the id
+    pub fn champion_kit(&self, id: u8) -> Option<&ChampionKit> {
+        self.champions.iter().find(|kit| kit.id == id)
+    }
 }
@@ -1,6 +1,8 @@
 use thiserror::Error;
 
 use crate::game::PlayerId;
+use crate::game::cell::Team;
+use crate::game::spell::CastError;
 
 #[derive(Debug, Error, PartialEq, Eq)]
 pub enum GameError {
@@ -10,12 +12,40 @@ pub enum GameError {
     NotFoundCell,
     #[error("Invalid input: {0}")]
     InvalidInput(String),
-    #[error("Player is dead")]
-    PlayerIsDead,
-    #[error("Invalid Animation was called")]
-    InvalidAnimation,
-    #[error("Error generating ID")]
-    GenerateIdError,
-    #[error("Entity is stunned")]
-    IsStunned,
+    #[error("The game is full")]
+    GameFull,
+    #[error("Unknown champion {0}")]
+    UnknownChampion(u8),
+    #[error("No skill point available")]
+    NoSkillPoint,
+    #[error("Spell is already at max rank")]
+    SpellMaxRank,
+    #[error("Spell in slot {0} failed: {1}")]
+    CastFailed(u8, CastError),
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum MapError {
+    #[error("Cannot read map {0}: {1}")]
+    Read(String, String),
+    #[error("Cannot parse map {0}: {1}")]
+    Parse(String, String),
+    #[error("No map with id {0} in the registry")]
+    NotFound(u8),
+    #[error("Map id {0} is defined twice")]
+    DuplicateId(u8),
+    #[error("The map registry is empty")]
+    EmptyRegistry,
+    #[error("Layout is {0}x{1} but the map declares {2}x{3}")]
+    InvalidSize(usize, usize, usize, usize),
+    #[error("{0} at ({1}, {2}) is outside of the map")]
+    OutOfBounds(String, u16, u16),
+    #[error("No {0} defined for team {1:?}")]
+    MissingObject(String, Team),
+    #[error("Map does not support teams of {0} players")]
+    UnsupportedTeamSize(usize),
+    #[error("Cannot generate the map: {0}")]
+    InvalidGeneratorSettings(String),
+    #[error("Generated map has {0} issues, the first one is: {1}")]
+    InvalidGeneratedMap(usize, String),
 }
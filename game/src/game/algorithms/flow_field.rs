@@ -0,0 +1,415 @@
+use crate::game::Board;
+use crate::game::algorithms::pathfinding::{is_adjacent_to_goal, step_cost};
+use crate::game::cell::Cell;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+
+const UNREACHABLE: u32 = u32::MAX;
+const NEIGHBORS: [(i32, i32); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// Terrain a unit could stand on once other units move away. Units are not
+/// obstacles of a flow field, they are avoided when choosing the next step
+pub fn is_walkable(cell: &Cell) -> bool {
+    use crate::game::cell::{BaseTerrain, CellContent};
+    matches!(cell.base, BaseTerrain::Floor | BaseTerrain::Bush)
+        && !matches!(
+            cell.content,
+            Some(CellContent::Tower(_, _)) | Some(CellContent::Base(_))
+        )
+}
+
+/// Breaks ties between steps as close to the goal, favoring straight lines
+fn manhattan(a: (u16, u16), b: (u16, u16)) -> u16 {
+    a.0.abs_diff(b.0) + a.1.abs_diff(b.1)
+}
+
+/// Distance from every cell of the board to a goal, walking around the terrain.
+/// The field is built with Dijkstra, possibly over several ticks, and repaired
+/// when cells of the board become blocked or free
+#[derive(Debug)]
+pub struct FlowField {
+    goal: (u16, u16),
+    rows: usize,
+    cols: usize,
+    distances: Vec<u32>,
+    open: BinaryHeap<Reverse<(u32, usize)>>,
+}
+
+impl FlowField {
+    /// Creates an empty field, built by `advance`
+    pub fn new(board: &Board, goal: (u16, u16)) -> Self {
+        let mut field = FlowField {
+            goal,
+            rows: board.rows,
+            cols: board.cols,
+            distances: Vec::new(),
+            open: BinaryHeap::new(),
+        };
+        field.reset();
+        field
+    }
+
+    /// Creates a field and builds it entirely
+    pub fn build(board: &Board, goal: (u16, u16)) -> Self {
+        let mut field = FlowField::new(board, goal);
+        field.advance(board, usize::MAX);
+        field
+    }
+
+    fn reset(&mut self) {
+        self.distances = vec![UNREACHABLE; self.rows * self.cols];
+        self.open.clear();
+        let goal = self.index(self.goal);
+        self.distances[goal] = 0;
+        self.open.push(Reverse((0, goal)));
+    }
+
+    pub fn goal(&self) -> (u16, u16) {
+        self.goal
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.open.is_empty()
+    }
+
+    fn index(&self, position: (u16, u16)) -> usize {
+        position.0 as usize * self.cols + position.1 as usize
+    }
+
+    fn position(&self, index: usize) -> (u16, u16) {
+        ((index / self.cols) as u16, (index % self.cols) as u16)
+    }
+
+    fn neighbors(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        let (row, col) = ((index / self.cols) as i32, (index % self.cols) as i32);
+        NEIGHBORS.iter().filter_map(move |(d_row, d_col)| {
+            let (row, col) = (row + d_row, col + d_col);
+            (row >= 0 && col >= 0 && (row as usize) < self.rows && (col as usize) < self.cols)
+                .then(|| row as usize * self.cols + col as usize)
+        })
+    }
+
+    fn step_cost(&self, from: usize, to: usize) -> u32 {
+        step_cost(self.position(from), self.position(to))
+    }
+
+    /// Shortest distance to the goal through a neighbor of the cell
+    fn best_neighbor_distance(&self, index: usize) -> Option<u32> {
+        self.neighbors(index)
+            .filter(|neighbor| self.distances[*neighbor] != UNREACHABLE)
+            .map(|neighbor| self.distances[neighbor] + self.step_cost(neighbor, index))
+            .min()
+    }
+
+    /// Whether units can walk through the cell. The goal is always part of the
+    /// field, even when it is a tower or a base
+    fn is_open(&self, board: &Board, index: usize) -> bool {
+        let position = self.position(index);
+        position == self.goal
+            || board
+                .get_cell(position.0 as usize, position.1 as usize)
+                .is_some_and(is_walkable)
+    }
+
+    /// Expands at most `budget` cells of the field, returns the number of cells expanded
+    pub fn advance(&mut self, board: &Board, budget: usize) -> usize {
+        let mut expanded = 0;
+        while expanded < budget
+            && let Some(Reverse((distance, index))) = self.open.pop()
+        {
+            // Stale entry, the cell was reached by a shorter way
+            if distance > self.distances[index] {
+                continue;
+            }
+            expanded += 1;
+            let neighbors: Vec<usize> = self.neighbors(index).collect();
+            for neighbor in neighbors {
+                let cost = distance + self.step_cost(index, neighbor);
+                if cost < self.distances[neighbor] && self.is_open(board, neighbor) {
+                    self.distances[neighbor] = cost;
+                    self.open.push(Reverse((cost, neighbor)));
+                }
+            }
+        }
+        expanded
+    }
+
+    /// Cost of the path from the position to the goal, in step costs. None if
+    /// the goal cannot be reached or the field is not built yet
+    pub fn distance(&self, position: (u16, u16)) -> Option<u32> {
+        if !self.is_complete()
+            || position.0 as usize >= self.rows
+            || position.1 as usize >= self.cols
+        {
+            return None;
+        }
+        let distance = self.distances[self.index(position)];
+        (distance != UNREACHABLE).then_some(distance)
+    }
+
+    /// Neighbors of `from` closer to the goal, the best first. Units are
+    /// ignored, so the cells may be taken
+    pub fn downhill_steps(&self, from: (u16, u16)) -> Vec<(u16, u16)> {
+        let Some(distance) = self.distance(from) else {
+            return Vec::new();
+        };
+        let mut steps: Vec<(u16, u16)> = self
+            .neighbors(self.index(from))
+            .filter(|neighbor| self.distances[*neighbor] < distance)
+            .map(|neighbor| self.position(neighbor))
+            .collect();
+        // The straightest step is preferred between cells as close to the goal
+        steps.sort_by_key(|position| {
+            (
+                self.distances[self.index(*position)],
+                manhattan(*position, self.goal),
+            )
+        });
+        steps
+    }
+
+    /// Best neighbor of `from` toward the goal, for a unit standing on `from`.
+    /// Cells taken by another unit are avoided
+    pub fn next_step(&self, board: &Board, from: (u16, u16)) -> Option<(u16, u16)> {
+        self.downhill_steps(from).into_iter().find(|position| {
+            board
+                .get_cell(position.0 as usize, position.1 as usize)
+                .is_some_and(|cell| cell.is_passable())
+        })
+    }
+
+    /// Complete path from `start` to a cell next to the goal, other units are
+    /// ignored. Follows the `find_path_on_board` format: the start is excluded,
+    /// and a start already next to the goal gives `[start]`
+    pub fn path_from(&self, start: (u16, u16)) -> Option<VecDeque<(u16, u16)>> {
+        let mut distance = self.distance(start)?;
+        let mut path = VecDeque::new();
+        if start == self.goal || is_adjacent_to_goal(start, self.goal) {
+            path.push_back(start);
+            return Some(path);
+        }
+        let mut position = start;
+        while !is_adjacent_to_goal(position, self.goal) {
+            let next = self
+                .neighbors(self.index(position))
+                .filter(|neighbor| self.distances[*neighbor] < distance)
+                .min_by_key(|neighbor| {
+                    let next = self.position(*neighbor);
+                    (self.distances[*neighbor], manhattan(next, self.goal))
+                })?;
+            position = self.position(next);
+            distance = self.distances[next];
+            path.push_back(position);
+        }
+        Some(path)
+    }
+
+    /// Repairs the field after the terrain of `cells` changed on the board.
+    /// Only the cells whose distance depends on a changed cell are computed again
+    pub fn update_cells(&mut self, board: &Board, cells: &[(u16, u16)]) {
+        if !self.is_complete() {
+            // The build starts again with the new terrain
+            self.reset();
+            return;
+        }
+        let mut blocked: Vec<usize> = Vec::new();
+        for position in cells {
+            if position.0 as usize >= self.rows
+                || position.1 as usize >= self.cols
+                || *position == self.goal
+            {
+                continue;
+            }
+            let index = self.index(*position);
+            let open = self.is_open(board, index);
+            if !open && self.distances[index] != UNREACHABLE {
+                blocked.push(index);
+            } else if open
+                && let Some(distance) = self.best_neighbor_distance(index)
+                && distance < self.distances[index]
+            {
+                // A freed cell lowers the distances around it
+                self.distances[index] = distance;
+                self.open.push(Reverse((distance, index)));
+            }
+        }
+        if !blocked.is_empty() {
+            self.repair_blocked(board, &blocked);
+        }
+        self.advance(board, usize::MAX);
+    }
+
+    /// Finds the cells that only reached the goal through a blocked cell, then
+    /// reaches them again from the rest of the field
+    fn repair_blocked(&mut self, board: &Board, blocked: &[usize]) {
+        let mut affected = vec![false; self.distances.len()];
+        let mut queue: BinaryHeap<Reverse<(u32, usize)>> = BinaryHeap::new();
+        for index in blocked {
+            queue.push(Reverse((self.distances[*index], *index)));
+        }
+        // Cells are checked from the closest to the goal, so the neighbors a cell
+        // relies on are already known as affected or not
+        let mut lost: Vec<usize> = Vec::new();
+        while let Some(Reverse((distance, index))) = queue.pop() {
+            if affected[index] {
+                continue;
+            }
+            let supported = self.neighbors(index).any(|neighbor| {
+                !affected[neighbor]
+                    && self.distances[neighbor] != UNREACHABLE
+                    && self.distances[neighbor] + self.step_cost(neighbor, index) == distance
+            });
+            if supported && !blocked.contains(&index) {
+                continue;
+            }
+            affected[index] = true;
+            lost.push(index);
+            let neighbors: Vec<usize> = self
+                .neighbors(index)
+                .filter(|neighbor| {
+                    !affected[*neighbor]
+                        && self.distances[*neighbor] != UNREACHABLE
+                        && self.distances[*neighbor] > distance
+                })
+                .collect();
+            for neighbor in neighbors {
+                queue.push(Reverse((self.distances[neighbor], neighbor)));
+            }
+        }
+        for index in lost.iter() {
+            self.distances[*index] = UNREACHABLE;
+        }
+        for index in lost {
+            if !self.is_open(board, index) {
+                continue;
+            }
+            if let Some(distance) = self.best_neighbor_distance(index) {
+                self.distances[index] = distance;
+                self.open.push(Reverse((distance, index)));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::algorithms::pathfinding::{DIAGONAL_STEP_COST, STRAIGHT_STEP_COST};
+    use crate::game::cell::{BaseTerrain, CellContent, Team};
+
+    fn assert_same_distances(field: &FlowField, board: &Board) {
+        let rebuilt = FlowField::build(board, field.goal());
+        assert_eq!(field.distances, rebuilt.distances);
+    }
+
+    #[test]
+    fn test_build_gives_distances_around_walls() {
+        let mut board = Board::new(10, 10);
+        for row in 0..9 {
+            board.change_base(BaseTerrain::Wall, row, 5);
+        }
+        let field = FlowField::build(&board, (0, 9));
+        assert_eq!(field.distance((0, 6)), Some(3 * STRAIGHT_STEP_COST));
+        // Around the bottom of the wall, through (9, 5)
+        assert_eq!(
+            field.distance((0, 4)),
+            Some(5 * DIAGONAL_STEP_COST + 13 * STRAIGHT_STEP_COST)
+        );
+        assert_eq!(field.distance((3, 5)), None);
+    }
+
+    #[test]
+    fn test_build_over_several_ticks() {
+        let board = Board::new(20, 20);
+        let mut field = FlowField::new(&board, (10, 10));
+        assert_eq!(field.distance((0, 0)), None);
+        let mut ticks = 0;
+        while !field.is_complete() {
+            assert!(field.advance(&board, 50) <= 50);
+            ticks += 1;
+        }
+        assert!(ticks >= 8);
+        assert_eq!(field.distance((0, 0)), Some(10 * DIAGONAL_STEP_COST));
+    }
+
+    #[test]
+    fn test_path_crosses_the_whole_map() {
+        let mut board = Board::new(200, 200);
+        for row in 0..199 {
+            board.change_base(BaseTerrain::Wall, row, 100);
+        }
+        let field = FlowField::build(&board, (0, 199));
+        let path = field.path_from((0, 0)).unwrap();
+        let end = *path.back().unwrap();
+        assert!(is_adjacent_to_goal(end, (0, 199)));
+        assert!(path.contains(&(199, 100)));
+        assert_eq!(path.len(), 397);
+    }
+
+    #[test]
+    fn test_next_step_avoids_units() {
+        let mut board = Board::new(10, 10);
+        let field = FlowField::build(&board, (5, 9));
+        assert_eq!(field.next_step(&board, (5, 5)), Some((5, 6)));
+
+        board.place_cell(CellContent::Minion(1, Team::Blue), 5, 6);
+        let step = field.next_step(&board, (5, 5)).unwrap();
+        assert!(step == (4, 6) || step == (6, 6));
+    }
+
+    #[test]
+    fn test_update_cells_when_blocked_and_freed() {
+        let mut board = Board::new(12, 12);
+        let mut field = FlowField::build(&board, (0, 11));
+
+        // A tower cuts the straight way
+        let wall: Vec<(u16, u16)> = (0..11).map(|row| (row, 6)).collect();
+        for (row, col) in wall.iter() {
+            board.place_cell(
+                CellContent::Tower(1, Team::Red),
+                *row as usize,
+                *col as usize,
+            );
+        }
+        field.update_cells(&board, &wall);
+        assert_same_distances(&field, &board);
+        assert_eq!(
+            field.distance((0, 5)),
+            Some(6 * DIAGONAL_STEP_COST + 16 * STRAIGHT_STEP_COST)
+        );
+
+        // The tower falls
+        for (row, col) in wall.iter() {
+            board.clear_cell(*row as usize, *col as usize);
+        }
+        field.update_cells(&board, &wall);
+        assert_same_distances(&field, &board);
+        assert_eq!(field.distance((0, 5)), Some(6 * STRAIGHT_STEP_COST));
+    }
+
+    #[test]
+    fn test_update_cells_encloses_a_region() {
+        let mut board = Board::new(10, 10);
+        let mut field = FlowField::build(&board, (9, 9));
+        let mut ring = Vec::new();
+        for i in 0..4 {
+            ring.push((3, i));
+            ring.push((i, 3));
+        }
+        for (row, col) in ring.iter() {
+            board.change_base(BaseTerrain::Wall, *row as usize, *col as usize);
+        }
+        field.update_cells(&board, &ring);
+        assert_same_distances(&field, &board);
+        assert_eq!(field.distance((0, 0)), None);
+    }
+}
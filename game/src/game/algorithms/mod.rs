@@ -1,2 +1,3 @@
 pub mod bresenham;
+pub mod flow_field;
 pub mod pathfinding;
@@ -1,5 +1,7 @@
 use crate::game::Board;
-use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use crate::game::algorithms::flow_field::FlowField;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
 
 pub fn get_valid_neighbors(board: &Board, row: u16, col: u16) -> Vec<(u16, u16)> {
     let mut valid_neighbors: Vec<(u16, u16)> = Vec::new();
@@ -10,18 +12,34 @@ pub fn get_valid_neighbors(board: &Board, row: u16, col: u16) -> Vec<(u16, u16)>
             }
             let neighbors_row = row as isize + i;
             let neighbors_col = col as isize + j;
-            if let Some(cell) = board.get_cell(neighbors_row as usize, neighbors_col as usize) {
-                if cell.is_passable() {
-                    valid_neighbors.push((neighbors_row as u16, neighbors_col as u16))
-                }
+            if let Some(cell) = board.get_cell(neighbors_row as usize, neighbors_col as usize)
+                && cell.is_passable()
+            {
+                valid_neighbors.push((neighbors_row as u16, neighbors_col as u16))
             }
         }
     }
     valid_neighbors
 }
 
-pub fn calculate_heuristic(row: u16, col: u16, goal_row: u16, goal_col: u16) -> u16 {
-    (row.abs_diff(goal_row)).max(col.abs_diff(goal_col))
+/// Cost of an orthogonal step, a diagonal step costs about sqrt(2) times more
+pub const STRAIGHT_STEP_COST: u32 = 10;
+pub const DIAGONAL_STEP_COST: u32 = 14;
+
+/// Cost of a step between two adjacent cells
+pub fn step_cost(from: (u16, u16), to: (u16, u16)) -> u32 {
+    if from.0 != to.0 && from.1 != to.1 {
+        DIAGONAL_STEP_COST
+    } else {
+        STRAIGHT_STEP_COST
+    }
+}
+
+/// Octile distance: the cost of the shortest path on a board without obstacles
+pub fn calculate_heuristic(row: u16, col: u16, goal_row: u16, goal_col: u16) -> u32 {
+    let d_row = row.abs_diff(goal_row) as u32;
+    let d_col = col.abs_diff(goal_col) as u32;
+    DIAGONAL_STEP_COST * d_row.min(d_col) + STRAIGHT_STEP_COST * d_row.abs_diff(d_col)
 }
 
 pub fn is_adjacent_to_goal(pos: (u16, u16), goal: (u16, u16)) -> bool {
@@ -32,12 +50,12 @@ pub fn is_adjacent_to_goal(pos: (u16, u16), goal: (u16, u16)) -> bool {
     (dr <= 1 && dc <= 1) && (dr > 0 || dc > 0)
 }
 
-#[derive(Clone, Copy, Eq, PartialEq, Hash)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 pub struct PathNode {
     position: (u16, u16),
-    g_cost: u16,
-    h_cost: u16,
-    f_cost: u16,
+    g_cost: u32,
+    h_cost: u32,
+    f_cost: u32,
 }
 
 impl Ord for PathNode {
@@ -55,113 +73,305 @@ impl PartialOrd for PathNode {
     }
 }
 
+/// Outcome of an A* search limited to a number of expanded cells
+#[derive(Debug, PartialEq)]
+pub enum SearchResult {
+    Found(VecDeque<(u16, u16)>),
+    NoPath,
+    /// The search stopped before finding a path or proving there is none
+    OutOfBudget,
+}
+
+/// A* search keeping its buffers between calls. Each search gets a new
+/// generation, so the buffers never need to be cleared
+#[derive(Debug, Default)]
+pub struct PathSearch {
+    cols: usize,
+    generation: u32,
+    visited: Vec<u32>,
+    closed: Vec<u32>,
+    g_costs: Vec<u32>,
+    parents: Vec<(u16, u16)>,
+    open_set: BinaryHeap<PathNode>,
+}
+
+impl PathSearch {
+    fn prepare(&mut self, board: &Board) {
+        let size = board.rows * board.cols;
+        if self.visited.len() != size || self.cols != board.cols || self.generation == u32::MAX {
+            self.cols = board.cols;
+            self.generation = 0;
+            self.visited = vec![0; size];
+            self.closed = vec![0; size];
+            self.g_costs = vec![0; size];
+            self.parents = vec![(0, 0); size];
+        }
+        self.generation += 1;
+        self.open_set.clear();
+    }
+
+    fn index(&self, position: (u16, u16)) -> usize {
+        position.0 as usize * self.cols + position.1 as usize
+    }
+
+    /// Searches a complete path from start to a cell next to the goal, expanding
+    /// at most `budget` cells. The start is excluded from the path
+    pub fn search(
+        &mut self,
+        board: &Board,
+        start: (u16, u16),
+        goal: (u16, u16),
+        budget: usize,
+    ) -> (SearchResult, usize) {
+        if start == goal || is_adjacent_to_goal(start, goal) {
+            let mut path = VecDeque::new();
+            path.push_front(start);
+            return (SearchResult::Found(path), 0);
+        }
+        self.prepare(board);
+        let generation = self.generation;
+
+        let start_index = self.index(start);
+        let h_cost = calculate_heuristic(start.0, start.1, goal.0, goal.1);
+        self.visited[start_index] = generation;
+        self.g_costs[start_index] = 0;
+        self.open_set.push(PathNode {
+            position: start,
+            g_cost: 0,
+            h_cost,
+            f_cost: h_cost,
+        });
+
+        let mut expanded = 0;
+        while let Some(current_node) = self.open_set.pop() {
+            let current_index = self.index(current_node.position);
+            if self.closed[current_index] == generation {
+                continue;
+            }
+
+            if is_adjacent_to_goal(current_node.position, goal) {
+                let mut path = VecDeque::new();
+                let mut current_pos = current_node.position;
+                while current_pos != start {
+                    path.push_front(current_pos);
+                    current_pos = self.parents[self.index(current_pos)];
+                }
+                return (SearchResult::Found(path), expanded);
+            }
+            if expanded >= budget {
+                return (SearchResult::OutOfBudget, expanded);
+            }
+            expanded += 1;
+            self.closed[current_index] = generation;
+
+            for neighbor_pos in
+                get_valid_neighbors(board, current_node.position.0, current_node.position.1)
+            {
+                let neighbor_index = self.index(neighbor_pos);
+                if self.closed[neighbor_index] == generation {
+                    continue;
+                }
+                let tentative_g_cost =
+                    current_node.g_cost + step_cost(current_node.position, neighbor_pos);
+                if self.visited[neighbor_index] == generation
+                    && tentative_g_cost >= self.g_costs[neighbor_index]
+                {
+                    continue;
+                }
+                self.visited[neighbor_index] = generation;
+                self.g_costs[neighbor_index] = tentative_g_cost;
+                self.parents[neighbor_index] = current_node.position;
+
+                let neighbor_h_cost =
+                    calculate_heuristic(neighbor_pos.0, neighbor_pos.1, goal.0, goal.1);
+                self.open_set.push(PathNode {
+                    position: neighbor_pos,
+                    g_cost: tentative_g_cost,
+                    h_cost: neighbor_h_cost,
+                    f_cost: tentative_g_cost + neighbor_h_cost,
+                });
+            }
+        }
+
+        (SearchResult::NoPath, expanded)
+    }
+}
+
+/// Complete path from start to a cell next to the goal, going around units.
+/// The start is excluded from the path, a start next to the goal gives `[start]`
 pub fn find_path_on_board(
     board: &Board,
     start: (u16, u16),
     goal: (u16, u16),
 ) -> Option<VecDeque<(u16, u16)>> {
-    const MAX_DEPTH: usize = 100;
-    if start == goal {
-        let mut path = VecDeque::new();
-        path.push_front(start);
-        return Some(path);
-    }
-    if is_adjacent_to_goal(start, goal) {
-        let mut path = VecDeque::new();
-        path.push_front(start);
-        return Some(path);
-    }
-
-    let mut open_set: BinaryHeap<PathNode> = BinaryHeap::new();
-    let mut closed_set: HashSet<(u16, u16)> = HashSet::new();
-    let mut g_costs: HashMap<(u16, u16), u16> = HashMap::new();
-    let mut parents: HashMap<(u16, u16), (u16, u16)> = HashMap::new();
-
-    let start_node_g_cost = 0;
-    let start_node_h_cost = calculate_heuristic(start.0, start.1, goal.0, goal.1);
-    let start_node_f_cost = start_node_g_cost + start_node_h_cost;
-
-    let start_node = PathNode {
-        position: start,
-        g_cost: start_node_g_cost,
-        h_cost: start_node_h_cost,
-        f_cost: start_node_f_cost,
-    };
-
-    open_set.push(start_node);
-    g_costs.insert(start, start_node_g_cost);
-
-    while let Some(current_node) = open_set.pop() {
-        if closed_set.contains(&current_node.position) {
-            continue;
-        }
+    match PathSearch::default()
+        .search(board, start, goal, usize::MAX)
+        .0
+    {
+        SearchResult::Found(path) => Some(path),
+        _ => None,
+    }
+}
 
-        if is_adjacent_to_goal(current_node.position, goal) || parents.len() >= MAX_DEPTH {
-            let mut path = VecDeque::new();
-            let mut current_pos = current_node.position;
-            while current_pos != start {
-                path.push_front(current_pos);
-                current_pos = *parents
-                    .get(&current_pos)
-                    .expect("Path reconstruction error: missing parent");
-            }
+/// Paths of every unit of the game. Fixed goals, as lane waypoints and camps,
+/// get a flow field built over several ticks. Other goals use A*. Both share a
+/// number of cells expanded per tick
+#[derive(Debug)]
+pub struct Pathfinder {
+    fields: HashMap<(u16, u16), FlowField>,
+    pending: VecDeque<(u16, u16)>,
+    search: PathSearch,
+    nodes_per_tick: usize,
+    remaining: usize,
+    /// Paths asked during the current tick
+    searches: usize,
+}
 
-            return Some(path);
+impl Pathfinder {
+    pub fn new(nodes_per_tick: usize) -> Self {
+        Pathfinder {
+            fields: HashMap::new(),
+            pending: VecDeque::new(),
+            search: PathSearch::default(),
+            nodes_per_tick,
+            remaining: nodes_per_tick,
+            searches: 0,
         }
+    }
 
-        closed_set.insert(current_node.position);
+    /// Queues a flow field toward the goal, built by `build_fields`
+    pub fn request_field(&mut self, board: &Board, goal: (u16, u16)) {
+        if let Entry::Vacant(entry) = self.fields.entry(goal) {
+            entry.insert(FlowField::new(board, goal));
+            self.pending.push_back(goal);
+        }
+    }
 
-        let neighbors_pos =
-            get_valid_neighbors(board, current_node.position.0, current_node.position.1);
+    pub fn field(&self, goal: (u16, u16)) -> Option<&FlowField> {
+        self.fields.get(&goal).filter(|field| field.is_complete())
+    }
 
-        for neighbor_pos in neighbors_pos {
-            if closed_set.contains(&neighbor_pos) {
-                continue;
-            }
+    /// Gives back the budget of a new tick
+    pub fn start_tick(&mut self) {
+        self.remaining = self.nodes_per_tick;
+        self.searches = 0;
+    }
 
-            let tentative_g_cost = current_node.g_cost + 1;
-
-            match g_costs.get(&neighbor_pos) {
-                Some(existing_g_cost) => {
-                    if tentative_g_cost < *existing_g_cost {
-                        parents.insert(neighbor_pos, current_node.position);
-                        g_costs.insert(neighbor_pos, tentative_g_cost);
-
-                        let neighbor_h_cost =
-                            calculate_heuristic(neighbor_pos.0, neighbor_pos.1, goal.0, goal.1);
-                        let neighbor_f_cost = tentative_g_cost + neighbor_h_cost;
-
-                        let neighbor_node = PathNode {
-                            position: neighbor_pos,
-                            g_cost: tentative_g_cost,
-                            h_cost: neighbor_h_cost,
-                            f_cost: neighbor_f_cost,
-                        };
-                        open_set.push(neighbor_node);
-                    } else {
+    pub fn remaining_budget(&self) -> usize {
+        self.remaining
+    }
+
+    /// Paths asked since the start of the tick, through a flow field or A*
+    pub fn searches(&self) -> usize {
+        self.searches
+    }
+
+    /// Cells expanded since the start of the tick
+    pub fn nodes_expanded(&self) -> usize {
+        self.nodes_per_tick - self.remaining
+    }
+
+    /// Spends the rest of the tick budget on the queued flow fields
+    pub fn build_fields(&mut self, board: &Board) {
+        while self.remaining > 0
+            && let Some(goal) = self.pending.front().copied()
+        {
+            match self.fields.get_mut(&goal) {
+                Some(field) => {
+                    self.remaining -= field.advance(board, self.remaining);
+                    if field.is_complete() {
+                        self.pending.pop_front();
                     }
                 }
                 None => {
-                    parents.insert(neighbor_pos, current_node.position);
-                    g_costs.insert(neighbor_pos, tentative_g_cost);
-
-                    let neighbor_h_cost =
-                        calculate_heuristic(neighbor_pos.0, neighbor_pos.1, goal.0, goal.1);
-                    let neighbor_f_cost = tentative_g_cost + neighbor_h_cost;
-
-                    let neighbor_node = PathNode {
-                        position: neighbor_pos,
-                        g_cost: tentative_g_cost,
-                        h_cost: neighbor_h_cost,
-                        f_cost: neighbor_f_cost,
-                    };
-                    open_set.push(neighbor_node);
+                    self.pending.pop_front();
                 }
             }
         }
     }
 
-    None
+    /// Next cell toward the goal for a unit on `from`, when the goal has a flow field
+    pub fn next_step(
+        &self,
+        board: &Board,
+        from: (u16, u16),
+        goal: (u16, u16),
+    ) -> Option<(u16, u16)> {
+        self.field(goal)?.next_step(board, from)
+    }
+
+    /// Neighbors of `from` closer to the goal, taken or not, when the goal has a flow field
+    pub fn downhill_steps(&self, from: (u16, u16), goal: (u16, u16)) -> Option<Vec<(u16, u16)>> {
+        Some(self.field(goal)?.downhill_steps(from))
+    }
+
+    /// Complete path toward the goal. Follows the flow field of the goal when
+    /// there is one, otherwise runs A* with the budget left for this tick.
+    /// Returns None when there is no path or no budget left
+    pub fn find_path(
+        &mut self,
+        board: &Board,
+        start: (u16, u16),
+        goal: (u16, u16),
+    ) -> Option<VecDeque<(u16, u16)>> {
+        match self.search_path(board, start, goal) {
+            SearchResult::Found(path) => Some(path),
+            SearchResult::NoPath | SearchResult::OutOfBudget => None,
+        }
+    }
+
+    /// Same as `find_path`, but tells a goal out of reach from a search that
+    /// ran out of budget and can be tried again on the next tick
+    pub fn search_path(
+        &mut self,
+        board: &Board,
+        start: (u16, u16),
+        goal: (u16, u16),
+    ) -> SearchResult {
+        self.searches += 1;
+        if let Some(field) = self.field(goal) {
+            return match field.path_from(start) {
+                Some(path) => SearchResult::Found(path),
+                None => SearchResult::NoPath,
+            };
+        }
+        self.search_around_units(board, start, goal)
+    }
+
+    /// Path around the units standing in the way, even when the goal has a
+    /// flow field. Returns None when there is no path or no budget left
+    pub fn find_detour(
+        &mut self,
+        board: &Board,
+        start: (u16, u16),
+        goal: (u16, u16),
+    ) -> Option<VecDeque<(u16, u16)>> {
+        self.searches += 1;
+        match self.search_around_units(board, start, goal) {
+            SearchResult::Found(path) => Some(path),
+            SearchResult::NoPath | SearchResult::OutOfBudget => None,
+        }
+    }
+
+    fn search_around_units(
+        &mut self,
+        board: &Board,
+        start: (u16, u16),
+        goal: (u16, u16),
+    ) -> SearchResult {
+        let (result, expanded) = self.search.search(board, start, goal, self.remaining);
+        self.remaining = self.remaining.saturating_sub(expanded);
+        result
+    }
+
+    /// Repairs every flow field after the terrain of `cells` changed, as when
+    /// a tower is destroyed
+    pub fn cells_changed(&mut self, board: &Board, cells: &[(u16, u16)]) {
+        // Fields still queued start their build again
+        for field in self.fields.values_mut() {
+            field.update_cells(board, cells);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -171,6 +381,7 @@ mod pathfinding_tests {
     use super::*;
     use crate::game::board::Board;
     use crate::game::cell::{BaseTerrain, CellContent, Team};
+    use std::collections::HashSet;
 
     #[test]
     fn test_get_valid_neighbors_middle_of_open_board() {
@@ -253,7 +464,7 @@ mod pathfinding_tests {
     fn test_heuristic_same_cell() {
         let start = (10, 10);
         let goal = (10, 10);
-        let expected_heuristic: u16 = 0; // The cost to get from a cell to itself is 0.
+        let expected_heuristic: u32 = 0; // The cost to get from a cell to itself is 0.
 
         let actual_heuristic = calculate_heuristic(start.0, start.1, goal.0, goal.1);
 
@@ -268,14 +479,14 @@ mod pathfinding_tests {
     fn test_heuristic_adjacent_horizontal() {
         let start = (10, 10);
         let goal = (10, 11);
-        // Diagonal Distance: max(|10-10|, |10-11|) = max(0, 1) = 1
-        let expected_heuristic: u16 = 1;
+        // One orthogonal step
+        let expected_heuristic: u32 = STRAIGHT_STEP_COST;
 
         let actual_heuristic = calculate_heuristic(start.0, start.1, goal.0, goal.1);
 
         assert_eq!(
             actual_heuristic, expected_heuristic,
-            "Heuristic for adjacent horizontal cells should be 1."
+            "Heuristic for adjacent horizontal cells should be one straight step."
         );
     }
 
@@ -283,14 +494,14 @@ mod pathfinding_tests {
     fn test_heuristic_adjacent_diagonal() {
         let start = (10, 10);
         let goal = (11, 11);
-        // Diagonal Distance: max(|10-11|, |10-11|) = max(1, 1) = 1
-        let expected_heuristic: u16 = 1;
+        // One diagonal step
+        let expected_heuristic: u32 = DIAGONAL_STEP_COST;
 
         let actual_heuristic = calculate_heuristic(start.0, start.1, goal.0, goal.1);
 
         assert_eq!(
             actual_heuristic, expected_heuristic,
-            "Heuristic for adjacent diagonal cells should be 1 when using Diagonal Distance."
+            "Heuristic for adjacent diagonal cells should be one diagonal step."
         );
     }
 
@@ -298,14 +509,14 @@ mod pathfinding_tests {
     fn test_heuristic_further_apart() {
         let start = (10, 10);
         let goal = (15, 18);
-        // Diagonal Distance: max(|10-15|, |10-18|) = max(5, 8) = 8
-        let expected_heuristic: u16 = 8;
+        // 5 diagonal steps then 3 straight steps
+        let expected_heuristic: u32 = 5 * DIAGONAL_STEP_COST + 3 * STRAIGHT_STEP_COST;
 
         let actual_heuristic = calculate_heuristic(start.0, start.1, goal.0, goal.1);
 
         assert_eq!(
             actual_heuristic, expected_heuristic,
-            "Heuristic for cells further apart should match the expected value for Diagonal Distance."
+            "Heuristic for cells further apart should match the octile distance."
         );
     }
 
@@ -516,7 +727,7 @@ mod pathfinding_tests {
             path1
                 .back()
                 .copied()
-                .map_or(false, |pos| is_adjacent_to_goal(pos, goal)),
+                .is_some_and(|pos| is_adjacent_to_goal(pos, goal)),
             "Minion 1 path should end at a cell adjacent to the goal."
         );
 
@@ -537,7 +748,7 @@ mod pathfinding_tests {
             path2
                 .back()
                 .copied()
-                .map_or(false, |pos| is_adjacent_to_goal(pos, goal)),
+                .is_some_and(|pos| is_adjacent_to_goal(pos, goal)),
             "Minion 2 path should end at a cell adjacent to the goal."
         );
 
@@ -602,7 +813,7 @@ mod pathfinding_tests {
             .collect();
 
         let max_ticks = 50; // Prevent infinite loops in case of issues
-        let mut all_reached = false;
+        let mut all_reached;
 
         println!("--- Starting Dynamic Pathfinding Simulation ---");
 
@@ -652,7 +863,7 @@ mod pathfinding_tests {
                         // Check if the next step is passable *before* attempting the move
                         if board
                             .get_cell(next_step.0 as usize, next_step.1 as usize)
-                            .map_or(false, |cell| cell.is_passable())
+                            .is_some_and(|cell| cell.is_passable())
                         {
                             // Attempt to move the minion: Clear old cell, update position, place on new cell
                             board
@@ -788,4 +999,54 @@ mod pathfinding_tests {
             );
         }
     }
+
+    #[test]
+    fn test_find_path_across_the_whole_board() {
+        let mut board = Board::new(150, 150);
+        // Walls force a path much longer than the old 100 cells limit
+        for col in 0..149 {
+            board.change_base(BaseTerrain::Wall, 50, col);
+            board.change_base(BaseTerrain::Wall, 100, col + 1);
+        }
+        let path = find_path_on_board(&board, (0, 0), (149, 0)).unwrap();
+        assert!(is_adjacent_to_goal(*path.back().unwrap(), (149, 0)));
+        assert!(path.contains(&(50, 149)) && path.contains(&(100, 0)));
+    }
+
+    #[test]
+    fn test_pathfinder_keeps_to_its_budget() {
+        let board = Board::new(60, 60);
+        let mut pathfinder = Pathfinder::new(100);
+        pathfinder.request_field(&board, (59, 59));
+
+        // The field is built over several ticks, A* uses the budget meanwhile
+        pathfinder.start_tick();
+        let path = pathfinder.find_path(&board, (0, 0), (59, 59)).unwrap();
+        assert_eq!(path.len(), 58);
+        assert_eq!(pathfinder.remaining_budget(), 42);
+        assert_eq!(pathfinder.find_path(&board, (0, 59), (59, 0)), None);
+        assert_eq!(pathfinder.remaining_budget(), 0);
+        assert_eq!(pathfinder.searches(), 2);
+        assert_eq!(pathfinder.nodes_expanded(), 100);
+        pathfinder.build_fields(&board);
+        assert!(pathfinder.field((59, 59)).is_none());
+
+        let mut ticks = 0;
+        while pathfinder.field((59, 59)).is_none() {
+            pathfinder.start_tick();
+            pathfinder.build_fields(&board);
+            ticks += 1;
+        }
+        assert_eq!(ticks, 36);
+        // The field gives paths without using the budget
+        pathfinder.start_tick();
+        let path = pathfinder.find_path(&board, (0, 30), (59, 59)).unwrap();
+        assert_eq!(path.len(), 58);
+        assert_eq!(pathfinder.remaining_budget(), 100);
+        assert_eq!(pathfinder.searches(), 1);
+        assert_eq!(
+            pathfinder.next_step(&board, (0, 30), (59, 59)),
+            Some((1, 31))
+        );
+    }
 }
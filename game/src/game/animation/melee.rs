@@ -46,14 +46,6 @@ impl AnimationTrait for MeleeAnimation {
         self.player_id
     }
 
-    fn get_animation_type(&self) -> CellAnimation {
-        self.animation_type.clone()
-    }
-
-    fn attach_target(&mut self, target_id: PlayerId) {
-        self.player_id = target_id
-    }
-
     fn get_last_drawn_pos(&self) -> Option<(u16, u16)> {
         match (self.last_drawn_row, self.last_drawn_col) {
             (Some(r), Some(c)) => Some((r, c)),
@@ -111,23 +103,11 @@ mod tests {
     #[test]
     fn test_melee_animation_trait_methods() {
         let player_id = 20;
-        let mut animation = MeleeAnimation::new(player_id);
+        let animation = MeleeAnimation::new(player_id);
 
         // Test get_owner_id
         assert_eq!(animation.get_owner_id(), player_id);
 
-        // Test get_animation_type
-        assert_eq!(animation.get_animation_type(), CellAnimation::MeleeHit);
-
-        // Test attach_target (Note: implementation sets the owner_id to target_id)
-        let target_id = 30;
-        animation.attach_target(target_id);
-        assert_eq!(
-            animation.get_owner_id(),
-            target_id,
-            "attach_target should update the owner_id"
-        );
-
         // Test get_last_drawn_pos (initially None)
         assert!(animation.get_last_drawn_pos().is_none());
 
@@ -1,9 +1,8 @@
 use std::fmt::Debug;
 
-use super::{PlayerId, cell::CellAnimation};
+use super::cell::CellAnimation;
 
 pub mod melee;
-pub mod tower;
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum AnimationCommand {
@@ -28,12 +27,6 @@ pub trait AnimationTrait: Send + Sync + Debug {
     // Method to get the ID of the entity that owns/triggered this animation
     fn get_owner_id(&self) -> usize;
 
-    // Method to attach the target id, for next position calculation
-    fn attach_target(&mut self, target_id: PlayerId);
-
-    // Method to get the animation type (MeleeHit, TowerHit, etc.)
-    fn get_animation_type(&self) -> CellAnimation;
-
     // A method to get the last drawn position, so GameManager knows what to clear
     fn get_last_drawn_pos(&self) -> Option<(u16, u16)>;
 }
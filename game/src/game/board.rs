@@ -1,18 +1,10 @@
+use log::trace;
+
 use crate::game::cell::Team;
 use crate::game::minion_manager::MinionManager;
 
 use super::cell::{BaseTerrain, Cell, CellAnimation, CellContent, EncodedCellValue};
-use serde::Deserialize;
-use std::fs::File;
-use std::io::Read;
-use std::usize;
-
-#[derive(Deserialize)]
-struct BoardLayout {
-    rows: usize,
-    cols: usize,
-    layout: Vec<Vec<String>>,
-}
+use super::map::MapData;
 
 #[derive(Debug)]
 pub struct Board {
@@ -22,35 +14,25 @@ pub struct Board {
 }
 
 impl Board {
-    pub fn from_json(file_path: &str) -> Result<Self, Box<dyn std::error::Error>> {
-        let mut file = File::open(file_path)?;
-        let mut contents = String::new();
-        file.read_to_string(&mut contents)?;
-
-        let board_layout: BoardLayout = serde_json::from_str(&contents)?;
-        let mut grid = Vec::with_capacity(board_layout.rows);
-        for (i, row) in board_layout.layout.iter().enumerate() {
-            let mut grid_row = Vec::with_capacity(board_layout.cols);
+    pub fn from_map(map: &MapData) -> Self {
+        let mut grid = Vec::with_capacity(map.rows);
+        for (i, row) in map.layout.iter().enumerate() {
+            let mut grid_row = Vec::with_capacity(map.cols);
             for (j, cell) in row.iter().enumerate() {
-                let base = match cell.as_str() {
-                    "wall" => BaseTerrain::Wall,
-                    "floor" => BaseTerrain::Floor,
-                    "bush" => BaseTerrain::Bush,
-                    _ => BaseTerrain::Floor, // Default case
-                };
+                // Unknown tiles are reported by mapcheck
+                let base = BaseTerrain::from_tile(cell).unwrap_or(BaseTerrain::Floor);
                 grid_row.push(Cell::new(base, (i as u16, j as u16)));
             }
             grid.push(grid_row);
         }
-        let board = Board {
+        Board {
             grid,
-            rows: board_layout.rows,
-            cols: board_layout.cols,
-        };
-
-        Ok(board)
+            rows: map.rows,
+            cols: map.cols,
+        }
     }
 
+    #[cfg(test)]
     pub fn new(rows: usize, cols: usize) -> Self {
         let mut grid = Vec::with_capacity(rows);
         for i in 0..rows {
@@ -83,11 +65,18 @@ impl Board {
         new_cell.content = content;
     }
 
+    /// Exchanges the content of two cells, as when two units swap places
+    pub fn swap_cells(&mut self, first: (usize, usize), second: (usize, usize)) {
+        let first_content = self.grid[first.0][first.1].content.take();
+        self.grid[first.0][first.1].content = self.grid[second.0][second.1].content.take();
+        self.grid[second.0][second.1].content = first_content;
+    }
+
     pub fn place_cell(&mut self, content: CellContent, champ_row: usize, champ_col: usize) {
-        if let Some(row) = self.grid.get_mut(champ_row) {
-            if let Some(cell) = row.get_mut(champ_col) {
-                cell.content = Some(content);
-            }
+        if let Some(row) = self.grid.get_mut(champ_row)
+            && let Some(cell) = row.get_mut(champ_col)
+        {
+            cell.content = Some(content);
         }
     }
 
@@ -101,10 +90,10 @@ impl Board {
         animation_row: usize,
         animation_col: usize,
     ) {
-        if let Some(row) = self.grid.get_mut(animation_row) {
-            if let Some(cell) = row.get_mut(animation_col) {
-                cell.animation = Some(animation);
-            }
+        if let Some(row) = self.grid.get_mut(animation_row)
+            && let Some(cell) = row.get_mut(animation_col)
+        {
+            cell.animation = Some(animation);
         }
     }
 
@@ -119,8 +108,38 @@ impl Board {
         view_height: u16,
         view_width: u16,
     ) -> Vec<Vec<&Cell>> {
+        let (min_row, max_row, min_col, max_col) =
+            self.view_bounds(player_row, player_col, view_height, view_width);
+
+        self.grid[min_row as usize..=max_row as usize]
+            .iter()
+            .map(|row| &row[min_col as usize..=max_col as usize])
+            .map(|slice| slice.iter().collect())
+            .collect()
+    }
+
+    /// Top left cell of the view returned by `center_view`, on the whole board
+    pub fn view_origin(
+        &self,
+        player_row: u16,
+        player_col: u16,
+        view_height: u16,
+        view_width: u16,
+    ) -> (u16, u16) {
+        let (min_row, _, min_col, _) =
+            self.view_bounds(player_row, player_col, view_height, view_width);
+        (min_row, min_col)
+    }
+
+    fn view_bounds(
+        &self,
+        player_row: u16,
+        player_col: u16,
+        view_height: u16,
+        view_width: u16,
+    ) -> (u16, u16, u16, u16) {
         let grid_height = self.grid.len() as u16;
-        let grid_width = self.grid.get(0).map_or(0, |r| r.len() as u16);
+        let grid_width = self.grid.first().map_or(0, |r| r.len() as u16);
 
         let half_height = view_height / 2;
         let half_width = view_width / 2;
@@ -135,7 +154,7 @@ impl Board {
         }
         // Adjust if view hit the bottom
         if max_row == grid_height - 1 {
-            min_row = (grid_height - view_height).max(0);
+            min_row = grid_height - view_height;
         }
 
         // Calculate potential min and max col
@@ -147,14 +166,10 @@ impl Board {
         }
         // Adjust if view hit the right
         if max_col == grid_width - 1 {
-            min_col = (grid_width - view_width).max(0);
+            min_col = grid_width - view_width;
         }
 
-        self.grid[min_row as usize..=max_row as usize]
-            .iter()
-            .map(|row| &row[min_col as usize..=max_col as usize])
-            .map(|slice| slice.iter().collect())
-            .collect()
+        (min_row, max_row, min_col, max_col)
     }
 
     pub fn run_length_encode(
@@ -175,15 +190,15 @@ impl Board {
         }
 
         let mut current_cell_value: EncodedCellValue;
-        if let Some(first_cell) = flattened_grid.get(0) {
+        if let Some(first_cell) = flattened_grid.first() {
             current_cell_value = get_encoded_cell_value(first_cell, minion_manager);
         } else {
             return Vec::new(); // Should not happen if flattened_grid is not empty
         }
         let mut count = 1;
 
-        for i in 1..flattened_grid.len() {
-            let encoded_value = get_encoded_cell_value(flattened_grid[i], minion_manager);
+        for cell in flattened_grid.iter().skip(1) {
+            let encoded_value = get_encoded_cell_value(cell, minion_manager);
             if encoded_value == current_cell_value {
                 count += 1;
             } else {
@@ -193,7 +208,7 @@ impl Board {
             }
         }
         rle.push(format!("{}:{}", current_cell_value as u8, count));
-        println!("Encoded board: {}", rle.join("|"));
+        trace!("Encoded board: {}", rle.join("|"));
         rle.join("|").into_bytes()
     }
 }
@@ -214,13 +229,12 @@ fn get_encoded_cell_value(cell: &Cell, minion_manager: &MinionManager) -> Encode
                 if let Some(minion) = minion_manager.minions.get(minion_id) {
                     let health_percentage =
                         (minion.stats.health as f32 / minion.stats.max_health as f32) * 100.0;
-                    let health_level = ((health_percentage / 12.5).ceil() as u8).max(1).min(8); // Map to 1-8
+                    let health_level = ((health_percentage / 12.5).ceil() as u8).clamp(1, 8); // Map to 1-8
                     EncodedCellValue::from_health_level(health_level, *team)
                 } else {
                     EncodedCellValue::MinionPlaceholder
                 }
             }
-            CellContent::Flag(_, _) => EncodedCellValue::Flag,
             CellContent::Tower(_, _) => EncodedCellValue::Tower,
             CellContent::Base(team) => match team {
                 Team::Blue => EncodedCellValue::BaseBlue,
@@ -234,6 +248,7 @@ fn get_encoded_cell_value(cell: &Cell, minion_manager: &MinionManager) -> Encode
             BaseTerrain::Floor => EncodedCellValue::Floor,
             BaseTerrain::Bush => EncodedCellValue::Bush,
             BaseTerrain::TowerDestroyed => EncodedCellValue::TowerDestroyed,
+            BaseTerrain::InnerTowerDestroyed => EncodedCellValue::InnerTowerDestroyed,
         }
     }
 }
@@ -314,7 +329,7 @@ mod tests {
 
     #[test]
     fn test_get_cell() {
-        let mut board = Board::new(5, 5);
+        let board = Board::new(5, 5);
 
         // Test valid coordinates
         let cell = board.get_cell(2, 2);
@@ -382,6 +397,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_view_origin_follows_the_clamped_view() {
+        let board = Board::new(50, 50);
+
+        // The view is centered on the player away from the edges
+        assert_eq!(board.view_origin(25, 25, 5, 7), (23, 22));
+        // And stays on the board near them
+        assert_eq!(board.view_origin(0, 1, 5, 7), (0, 0));
+        assert_eq!(board.view_origin(49, 48, 5, 7), (45, 43));
+    }
+
     #[test]
     fn test_center_view() {
         // Create a larger board to test view centering and edge cases
@@ -516,8 +542,8 @@ mod tests {
             let mut current_cell_value: EncodedCellValue =
                 EncodedCellValue::from(flattened_grid[0]);
             let mut count = 1;
-            for i in 1..flattened_grid.len() {
-                let encoded_value = EncodedCellValue::from(flattened_grid[i]);
+            for cell in flattened_grid.iter().skip(1) {
+                let encoded_value = EncodedCellValue::from(*cell);
                 if encoded_value == current_cell_value {
                     count += 1;
                 } else {
@@ -0,0 +1,121 @@
+use std::time::{Duration, Instant};
+
+use crate::config::KillBuffStats;
+
+use super::{Buff, HasBuff};
+
+/// Timed bonus given by the kill of a monster
+#[derive(Debug, Clone)]
+pub struct KillBuff {
+    pub stats: KillBuffStats,
+    pub applied_at: Instant,
+    last_regen: Instant,
+}
+
+impl KillBuff {
+    pub fn new(stats: KillBuffStats) -> KillBuff {
+        KillBuff {
+            stats,
+            applied_at: Instant::now(),
+            last_regen: Instant::now(),
+        }
+    }
+}
+
+impl Buff for KillBuff {
+    fn id(&self) -> &str {
+        &self.stats.id
+    }
+
+    fn on_apply(&mut self, target: &mut dyn HasBuff) {
+        target.add_attack_damage(self.stats.bonus_attack_damage);
+    }
+
+    fn on_tick(&mut self, target: &mut dyn HasBuff) -> bool {
+        if self.last_regen.elapsed() >= Duration::from_secs(1) {
+            target.heal(self.stats.health_regen);
+            self.last_regen = Instant::now();
+        }
+        self.applied_at.elapsed() > Duration::from_secs(self.stats.duration_secs)
+    }
+
+    fn on_remove(&mut self, target: &mut dyn HasBuff) {
+        target.remove_attack_damage(self.stats.bonus_attack_damage);
+    }
+
+    fn clone_box(&self) -> Box<dyn Buff> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Dummy {
+        attack_damage: u16,
+        health: u16,
+    }
+
+    impl HasBuff for Dummy {
+        fn is_stunned(&self) -> bool {
+            false
+        }
+
+        fn set_stunned(&mut self, _stunned: bool, _duration: Option<Duration>) {}
+
+        fn add_attack_damage(&mut self, amount: u16) {
+            self.attack_damage += amount;
+        }
+
+        fn remove_attack_damage(&mut self, amount: u16) {
+            self.attack_damage -= amount;
+        }
+
+        fn heal(&mut self, amount: u16) {
+            self.health += amount;
+        }
+    }
+
+    fn create_buff_stats() -> KillBuffStats {
+        KillBuffStats {
+            id: "golem_might".to_string(),
+            duration_secs: 60,
+            team_wide: false,
+            bonus_attack_damage: 10,
+            health_regen: 4,
+        }
+    }
+
+    #[test]
+    fn test_kill_buff_adds_and_removes_attack_damage() {
+        let mut target = Dummy::default();
+        let mut buff = KillBuff::new(create_buff_stats());
+
+        buff.on_apply(&mut target);
+        assert_eq!(target.attack_damage, 10);
+
+        buff.on_remove(&mut target);
+        assert_eq!(target.attack_damage, 0);
+    }
+
+    #[test]
+    fn test_kill_buff_regenerates_every_second_until_expired() {
+        let mut target = Dummy::default();
+        let mut buff = KillBuff::new(create_buff_stats());
+
+        assert!(!buff.on_tick(&mut target));
+        assert_eq!(target.health, 0, "No regeneration before a second elapsed");
+
+        buff.last_regen = Instant::now() - Duration::from_secs(1);
+        assert!(!buff.on_tick(&mut target));
+        assert_eq!(target.health, 4);
+
+        buff.applied_at = Instant::now() - Duration::from_secs(61);
+        assert!(
+            buff.on_tick(&mut target),
+            "Buff should expire after its duration"
+        );
+    }
+}
@@ -1,9 +1,15 @@
+pub mod kill_buff;
 pub mod stun_buff;
 use std::{fmt::Debug, time::Duration};
 
 pub trait HasBuff {
     fn is_stunned(&self) -> bool;
     fn set_stunned(&mut self, stunned: bool, duration: Option<Duration>);
+
+    /// Units without kill buffs ignore the stat changes
+    fn add_attack_damage(&mut self, _amount: u16) {}
+    fn remove_attack_damage(&mut self, _amount: u16) {}
+    fn heal(&mut self, _amount: u16) {}
 }
 
 pub trait Buff: Send + Sync + Debug {
@@ -1,12 +1,15 @@
+use serde::{Deserialize, Serialize};
 use strum_macros::EnumIter;
 
-pub type PlayerId = usize;
-pub type MinionId = usize;
-pub type MonsterId = usize;
-pub type FlagId = usize;
-pub type TowerId = usize;
+/// Id of a unit, unique across every kind of unit, see `EntityRegistry`
+pub type EntityId = usize;
+pub type PlayerId = EntityId;
+pub type MinionId = EntityId;
+pub type MonsterId = EntityId;
+pub type TowerId = EntityId;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Team {
     Blue,
     Red,
@@ -18,6 +21,20 @@ pub enum BaseTerrain {
     Floor,
     Bush,
     TowerDestroyed,
+    /// Remains of an inner or inhibitor tower, the base is getting close
+    InnerTowerDestroyed,
+}
+
+impl BaseTerrain {
+    /// Terrain of a tile of the map layout
+    pub fn from_tile(tile: &str) -> Option<Self> {
+        match tile {
+            "wall" => Some(BaseTerrain::Wall),
+            "floor" => Some(BaseTerrain::Floor),
+            "bush" => Some(BaseTerrain::Bush),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -25,7 +42,6 @@ pub enum CellContent {
     Champion(PlayerId, Team),
     Minion(MinionId, Team),
     Monster(MonsterId),
-    Flag(FlagId, Team),
     Tower(TowerId, Team),
     Base(Team),
 }
@@ -61,6 +77,7 @@ impl Cell {
         match self.base {
             BaseTerrain::Wall => false,
             BaseTerrain::TowerDestroyed => false,
+            BaseTerrain::InnerTowerDestroyed => false,
             BaseTerrain::Floor => self.content.is_none(),
             BaseTerrain::Bush => self.content.is_none(),
         }
@@ -75,7 +92,6 @@ pub enum EncodedCellValue {
     Bush = 2,
     TowerDestroyed = 3,
     Champion = 4,
-    Flag = 7,
     Tower = 8,
     MeleeHitAnimation = 9,
     TowerHitAnimation = 10,
@@ -85,6 +101,7 @@ pub enum EncodedCellValue {
     FreezeWallAnimation = 14,
     FireBallAnimation = 15,
     Monster = 16,
+    InnerTowerDestroyed = 17,
     // Minion health values (100-115)
     MinionBlueHealth1 = 100,
     MinionBlueHealth2 = 101,
@@ -149,7 +166,6 @@ impl From<&Cell> for EncodedCellValue {
                 CellContent::Champion(_, _) => EncodedCellValue::Champion,
                 CellContent::Minion(_, _) => EncodedCellValue::MinionPlaceholder, // Placeholder for now
                 CellContent::Monster(_) => EncodedCellValue::Monster,
-                CellContent::Flag(_, _) => EncodedCellValue::Flag,
                 CellContent::Tower(_, _) => EncodedCellValue::Tower,
                 CellContent::Base(team) => match team {
                     Team::Blue => EncodedCellValue::BaseBlue,
@@ -162,6 +178,7 @@ impl From<&Cell> for EncodedCellValue {
                 BaseTerrain::Floor => EncodedCellValue::Floor,
                 BaseTerrain::Bush => EncodedCellValue::Bush,
                 BaseTerrain::TowerDestroyed => EncodedCellValue::TowerDestroyed,
+                BaseTerrain::InnerTowerDestroyed => EncodedCellValue::InnerTowerDestroyed,
             }
         }
     }
@@ -302,14 +319,6 @@ mod tests {
             EncodedCellValue::MinionPlaceholder
         );
 
-        let flag_cell = Cell {
-            position: dummy_position, // Added position
-            base: BaseTerrain::Bush,  // Base shouldn't matter when content is present
-            content: Some(CellContent::Flag(1, Team::Red)),
-            animation: None,
-        };
-        assert_eq!(EncodedCellValue::from(&flag_cell), EncodedCellValue::Flag);
-
         let tower_cell = Cell {
             position: dummy_position,          // Added position
             base: BaseTerrain::TowerDestroyed, // Base shouldn't matter when content is present
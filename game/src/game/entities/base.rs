@@ -11,6 +11,8 @@ pub struct Base {
     pub team: Team,
     pub stats: Stats,
     pub position: (i32, i32),
+    /// Invulnerable until its team loses every tower of a lane
+    pub protected: bool,
 }
 
 impl Base {
@@ -23,12 +25,21 @@ impl Base {
             mana: 0,
             max_mana: 0,
             armor: base_stats.armor,
+            magic_resist: base_stats.magic_resist,
+            // Only champions get penetration and critical strikes
+            armor_penetration_flat: 0,
+            armor_penetration_percent: 0.,
+            crit_chance: 0.,
+            crit_multiplier: 1.,
+            // Bases do not move
+            movement_speed: 0,
         };
 
         Base {
             team,
             stats,
             position,
+            protected: false,
         }
     }
 }
@@ -36,12 +47,12 @@ impl Base {
 impl Fighter for Base {
     fn take_effect(&mut self, effects: Vec<GameplayEffect>) {
         for effect in effects.into_iter() {
-            match effect {
-                GameplayEffect::Damage(damage) => {
-                    self.stats.health = self.stats.health.saturating_sub(damage as u16);
-                }
-                // Tower cannot be affected by buff or debuff
-                _ => {}
+            // Base cannot be affected by buff or debuff
+            if let GameplayEffect::Damage(damage) = effect
+                && !self.protected
+            {
+                let damage_taken = self.stats.damage_taken(&damage);
+                self.stats.health = self.stats.health.saturating_sub(damage_taken);
             }
         }
     }
@@ -62,12 +73,14 @@ mod tests {
     use super::*;
     use crate::config::BaseStats;
     use crate::game::cell::Team;
+    use crate::game::entities::damage::{Damage, DamageSource};
     use crate::game::entities::projectile::GameplayEffect;
 
     fn create_default_base_stats() -> BaseStats {
         BaseStats {
             health: 5000,
             armor: 10,
+            magic_resist: 10,
         }
     }
 
@@ -84,13 +97,41 @@ mod tests {
     fn test_take_damage() {
         let base_stats = create_default_base_stats();
         let mut base = Base::new(Team::Red, (10, 10), base_stats);
-        base.take_effect(vec![GameplayEffect::Damage(100)]);
-        assert_eq!(base.stats.health, 4900);
+        // 10 armor takes 100 damage down to 91
+        base.take_effect(vec![GameplayEffect::Damage(Damage::physical(
+            100,
+            DamageSource::Champion(1),
+        ))]);
+        assert_eq!(base.stats.health, 4909);
 
-        base.take_effect(vec![GameplayEffect::Damage(5000)]);
+        base.take_effect(vec![GameplayEffect::Damage(Damage::physical(
+            6000,
+            DamageSource::Champion(1),
+        ))]);
         assert_eq!(base.stats.health, 0);
 
-        base.take_effect(vec![GameplayEffect::Damage(100)]);
+        base.take_effect(vec![GameplayEffect::Damage(Damage::physical(
+            100,
+            DamageSource::Champion(1),
+        ))]);
         assert_eq!(base.stats.health, 0);
     }
+
+    #[test]
+    fn test_protected_base_ignores_damage() {
+        let mut base = Base::new(Team::Red, (10, 10), create_default_base_stats());
+        base.protected = true;
+        base.take_effect(vec![GameplayEffect::Damage(Damage::physical(
+            100,
+            DamageSource::Champion(1),
+        ))]);
+        assert_eq!(base.stats.health, 5000);
+
+        base.protected = false;
+        base.take_effect(vec![GameplayEffect::Damage(Damage::physical(
+            100,
+            DamageSource::Champion(1),
+        ))]);
+        assert_eq!(base.stats.health, 4909);
+    }
 }
@@ -0,0 +1,105 @@
+use crate::game::cell::{MinionId, MonsterId, PlayerId, TowerId};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DamageType {
+    /// Reduced by armor, auto attacks of every unit
+    Physical,
+    /// Reduced by magic resist, spells
+    Magic,
+}
+
+/// Unit behind a hit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DamageSource {
+    Champion(PlayerId),
+    Minion(MinionId),
+    Tower(TowerId),
+    Monster(MonsterId),
+}
+
+/// A hit before the mitigation of its target
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Damage {
+    pub amount: u16,
+    pub damage_type: DamageType,
+    pub source: DamageSource,
+    /// Armor ignored after the percent penetration
+    pub armor_penetration_flat: u16,
+    /// Share of the armor ignored, from 0 to 1
+    pub armor_penetration_percent: f32,
+    pub critical: bool,
+}
+
+impl Damage {
+    pub fn physical(amount: u16, source: DamageSource) -> Self {
+        Damage {
+            amount,
+            damage_type: DamageType::Physical,
+            source,
+            armor_penetration_flat: 0,
+            armor_penetration_percent: 0.,
+            critical: false,
+        }
+    }
+
+    pub fn magic(amount: u16, source: DamageSource) -> Self {
+        Damage {
+            damage_type: DamageType::Magic,
+            ..Damage::physical(amount, source)
+        }
+    }
+
+    /// Same hit with another amount, used by towers ramping up their damage
+    pub fn with_amount(self, amount: u16) -> Self {
+        Damage { amount, ..self }
+    }
+
+    /// Damage left once the armor or the magic resist of the target is applied.
+    /// Each point of resistance takes 1% more health to go through, so 100
+    /// armor halves physical damage
+    pub fn mitigated(&self, armor: u16, magic_resist: u16) -> u16 {
+        let resistance = match self.damage_type {
+            DamageType::Physical => {
+                let armor = armor as f32 * (1. - self.armor_penetration_percent.clamp(0., 1.));
+                (armor - self.armor_penetration_flat as f32).max(0.)
+            }
+            DamageType::Magic => magic_resist as f32,
+        };
+        (self.amount as f32 * 100. / (100. + resistance)).round() as u16
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_armor_reduces_physical_damage() {
+        let damage = Damage::physical(100, DamageSource::Minion(1));
+        assert_eq!(damage.mitigated(0, 0), 100);
+        assert_eq!(damage.mitigated(10, 0), 91);
+        assert_eq!(damage.mitigated(100, 0), 50);
+        // Magic resist does nothing against physical damage
+        assert_eq!(damage.mitigated(0, 100), 100);
+    }
+
+    #[test]
+    fn test_magic_resist_reduces_magic_damage() {
+        let damage = Damage::magic(100, DamageSource::Champion(1));
+        assert_eq!(damage.mitigated(100, 0), 100);
+        assert_eq!(damage.mitigated(0, 25), 80);
+    }
+
+    #[test]
+    fn test_percent_then_flat_penetration() {
+        let damage = Damage {
+            armor_penetration_percent: 0.5,
+            armor_penetration_flat: 10,
+            ..Damage::physical(100, DamageSource::Champion(1))
+        };
+        // 60 armor, halved to 30, then 10 less
+        assert_eq!(damage.mitigated(60, 0), 83);
+        // Penetration never makes armor negative
+        assert_eq!(damage.mitigated(10, 0), 100);
+    }
+}
@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use std::{
     collections::{HashMap, VecDeque},
     time::{Duration, Instant},
@@ -7,20 +8,44 @@ use strum_macros::EnumIter;
 use crate::{
     errors::GameError,
     game::{
-        Board, Cell, CellContent, MinionId,
-        algorithms::pathfinding::{find_path_on_board, is_adjacent_to_goal},
+        Board, Cell, CellContent, EntityId, MinionId,
+        algorithms::pathfinding::{Pathfinder, is_adjacent_to_goal},
         animation::{AnimationTrait, melee::MeleeAnimation},
-        buffs::{Buff, HasBuff},
-        cell::Team,
+        buffs::HasBuff,
+        cell::{CellAnimation, Team},
+        map::LaneLayout,
+        projectile_manager::ProjectileManager,
+        registry::Ai,
     },
 };
 
-use super::{AttackAction, Fighter, Stats, Target, projectile::GameplayEffect, reduced_damage};
-use crate::config::MinionStats;
+use super::{
+    AttackAction, Fighter, MoveTimer, Stats, Target, Unit, damage::DamageSource,
+    projectile::GameplayEffect,
+};
+use crate::config::{AttackType, MinionStats};
 
 type MinionPath = (u16, u16);
 
-#[derive(Debug, PartialEq, Eq, EnumIter)]
+/// Ticks a minion waits behind a unit before searching a path around it
+const REPATH_DELAY_TICKS: u8 = 5;
+/// Ticks after which two allies waiting on each other swap places
+const DEADLOCK_TICKS: u8 = 15;
+
+/// How a minion fared with its last step, read by the allies behind it
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Traffic {
+    #[default]
+    Moving,
+    /// Stands still on purpose: fighting, at the end of its lane, or queued
+    /// behind such an ally
+    Settled,
+    /// Waits for a taken cell on its way since a number of ticks
+    Blocked { step: (u16, u16), ticks: u8 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Lane {
     Top,
     Mid,
@@ -31,7 +56,6 @@ pub enum Lane {
 pub struct Minion {
     pub minion_id: MinionId,
     pub team_id: Team,
-    lane: Lane,
     path: Option<VecDeque<(u16, u16)>>,
     pub stats: Stats,
     minion_stats: MinionStats,
@@ -40,21 +64,14 @@ pub struct Minion {
     checkpoint: usize,
     last_attacked: Instant,
     stun_timer: Option<Instant>,
-    pub active_buffs: HashMap<String, Box<dyn Buff>>,
+    move_timer: MoveTimer,
+    pub traffic: Traffic,
     pub row: u16,
     pub col: u16,
 }
 
 impl Minion {
-    pub fn get_health(&self) -> u16 {
-        self.stats.health
-    }
-
-    pub fn get_max_health(&self) -> u16 {
-        self.stats.max_health
-    }
-
-    pub fn new(minion_id: MinionId, team_id: Team, lane: Lane, minion_stats: MinionStats) -> Self {
+    pub fn new(minion_id: MinionId, lane: &LaneLayout, minion_stats: MinionStats) -> Self {
         let stats = Stats {
             attack_damage: minion_stats.attack_damage,
             attack_speed: Duration::from_millis(minion_stats.attack_speed_ms),
@@ -63,78 +80,67 @@ impl Minion {
             mana: 0,
             max_mana: 0,
             armor: minion_stats.armor,
+            magic_resist: minion_stats.magic_resist,
+            // Only champions get penetration and critical strikes
+            armor_penetration_flat: 0,
+            armor_penetration_percent: 0.,
+            crit_chance: 0.,
+            crit_multiplier: 1.,
+            movement_speed: minion_stats.movement_speed,
         };
-
-        let (row, col, paths) = match team_id {
-            Team::Blue => match lane {
-                Lane::Top => (
-                    184,
-                    10,
-                    vec![(120, 8), (39, 7), (7, 39), (8, 120), (10, 184)],
-                ),
-                Lane::Mid => (
-                    184,
-                    17,
-                    vec![(148, 67), (115, 82), (82, 115), (67, 148), (17, 184)],
-                ),
-                Lane::Bottom => (
-                    191,
-                    17,
-                    vec![(191, 79), (196, 150), (150, 196), (79, 191), (17, 191)],
-                ),
-            },
-            Team::Red => match lane {
-                Lane::Top => (
-                    10,
-                    184,
-                    vec![(8, 120), (7, 39), (39, 7), (120, 8), (184, 10)],
-                ),
-                Lane::Mid => (
-                    17,
-                    184,
-                    vec![(67, 148), (82, 115), (115, 82), (148, 67), (184, 17)],
-                ),
-                Lane::Bottom => (
-                    17,
-                    191,
-                    vec![(79, 191), (150, 196), (196, 150), (191, 79), (191, 17)],
-                ),
-            },
-        };
-        let path = paths[0];
+        let (row, col) = lane.spawn;
+        let path = lane.waypoints.first().copied().unwrap_or(lane.spawn);
 
         Self {
             minion_id,
-            team_id,
-            lane,
+            team_id: lane.team,
             path: None,
             stats,
             minion_stats,
             current_path: path,
-            minion_path: paths,
+            minion_path: lane.waypoints.clone(),
             checkpoint: 0,
             last_attacked: Instant::now(),
             stun_timer: None,
-            active_buffs: HashMap::new(),
+            move_timer: MoveTimer::default(),
+            traffic: Traffic::Moving,
             row,
             col,
         }
     }
 
     fn change_goal(&mut self) {
-        if self.checkpoint < self.minion_path.len() {
+        if self.checkpoint + 1 < self.minion_path.len() {
             self.checkpoint += 1;
-            self.current_path = self.minion_path[self.checkpoint as usize];
+            self.current_path = self.minion_path[self.checkpoint];
         }
     }
 
     pub fn is_dead(&self) -> bool {
-        if self.stats.health <= 0 { true } else { false }
+        self.stats.health == 0
+    }
+
+    /// Goal of the minion: the next waypoint of its lane
+    pub fn lane_goal(&self) -> (u16, u16) {
+        self.current_path
     }
 
-    pub fn movement_phase(&mut self, board: &mut Board) -> Result<(), GameError> {
+    /// Moves the minion one step. `traffic` holds the state of the other
+    /// minions, when the minion must swap places with an ally to go on, the
+    /// ally is returned and the caller does the swap
+    pub fn movement_phase(
+        &mut self,
+        board: &mut Board,
+        pathfinder: &mut Pathfinder,
+        traffic: &HashMap<MinionId, Traffic>,
+    ) -> Result<Option<MinionId>, GameError> {
+        self.move_timer.recharge(self.stats.movement_speed);
         if self.is_stunned() {
-            return Ok(());
+            self.traffic = Traffic::Settled;
+            return Ok(None);
+        }
+        if !self.move_timer.can_step(self.stats.movement_speed, false) {
+            return Ok(None);
         }
         if is_adjacent_to_goal((self.row, self.col), self.current_path) {
             self.change_goal();
@@ -145,10 +151,15 @@ impl Minion {
                 let col_step = (next_step.1 as i16 - self.col as i16).signum() as isize;
                 match self.move_minion(board, row_step, col_step) {
                     Ok(_) => {
+                        // The minion waits for a diagonal step
+                        if (self.row, self.col) != next_step {
+                            path.push_front(next_step);
+                        }
                         if !path.is_empty() {
                             self.path = Some(path);
                         }
-                        return Ok(());
+                        self.traffic = Traffic::Moving;
+                        return Ok(None);
                     }
                     Err(_) => self.path = None,
                 }
@@ -158,97 +169,189 @@ impl Minion {
         }
         // scan aggro range 10*10 aggro range for now
         // and move toward closest target
-        let target_pos = if let Some(cell) = self.get_potential_target(board) {
-            cell.position
-        } else {
-            self.current_path
-        };
+        let enemy_pos = self.get_potential_target(board).map(|cell| cell.position);
+        // Ranged minions hold their position once the target is in range
+        if let Some(enemy_pos) = enemy_pos
+            && matches!(self.minion_stats.attack, AttackType::Ranged { .. })
+            && self.in_attack_range(enemy_pos)
+        {
+            self.traffic = Traffic::Settled;
+            return Ok(None);
+        }
+        let target_pos = enemy_pos.unwrap_or(self.current_path);
         // If already adjacent to the cell we don't need to move
         if is_adjacent_to_goal((self.row, self.col), target_pos) {
-            return Ok(());
+            self.traffic = Traffic::Settled;
+            return Ok(None);
+        }
+        // Waypoints of the lane have a flow field
+        if enemy_pos.is_none()
+            && let Some(steps) = pathfinder.downhill_steps((self.row, self.col), target_pos)
+        {
+            return self.walk_lane(board, pathfinder, traffic, &steps);
         }
+        self.traffic = Traffic::Moving;
         // else simply move one step toward current goal
         let row_step = (target_pos.0 as i16 - self.row as i16).signum() as isize;
         let col_step = (target_pos.1 as i16 - self.col as i16).signum() as isize;
         match self.move_minion(board, row_step, col_step) {
-            Ok(_) => return Ok(()),
+            Ok(_) => Ok(None),
             Err(_) => {
                 if let Some(calculated_path) =
-                    find_path_on_board(board, (self.row, self.col), target_pos)
+                    pathfinder.find_path(board, (self.row, self.col), target_pos)
                 {
                     self.path = Some(calculated_path);
-                    return Ok(());
+                    Ok(None)
                 } else {
-                    return Err(GameError::CannotMoveHere(self.minion_id));
+                    Err(GameError::CannotMoveHere(self.minion_id))
+                }
+            }
+        }
+    }
+
+    /// Follows the flow field of the lane. `steps` are the cells closer to the
+    /// waypoint, taken or not. When all of them are taken, the minion queues
+    /// behind a settled ally, swaps with an ally coming the other way, or waits
+    /// before going around the unit
+    fn walk_lane(
+        &mut self,
+        board: &mut Board,
+        pathfinder: &mut Pathfinder,
+        traffic: &HashMap<MinionId, Traffic>,
+        steps: &[(u16, u16)],
+    ) -> Result<Option<MinionId>, GameError> {
+        let position = (self.row, self.col);
+        let free_step = steps.iter().find(|(row, col)| {
+            board
+                .get_cell(*row as usize, *col as usize)
+                .is_some_and(|cell| cell.is_passable())
+        });
+        if let Some(&(row, col)) = free_step {
+            self.traffic = Traffic::Moving;
+            return self
+                .move_minion(
+                    board,
+                    row as isize - self.row as isize,
+                    col as isize - self.col as isize,
+                )
+                .map(|_| None);
+        }
+        let Some(&step) = steps.first() else {
+            self.traffic = Traffic::Settled;
+            return Ok(None);
+        };
+
+        let ticks = match self.traffic {
+            Traffic::Blocked { ticks, .. } => ticks.saturating_add(1),
+            _ => 1,
+        };
+        let ally = match board.get_cell(step.0 as usize, step.1 as usize) {
+            Some(Cell {
+                content: Some(CellContent::Minion(id, team)),
+                ..
+            }) if *team == self.team_id => traffic.get(id).map(|state| (*id, *state)),
+            _ => None,
+        };
+        match ally {
+            // The ally holds its cell, the minion queues behind it
+            Some((_, Traffic::Settled)) => {
+                self.traffic = Traffic::Settled;
+                return Ok(None);
+            }
+            // Head-on in a corridor, or both stuck for too long: swap places
+            Some((
+                ally_id,
+                Traffic::Blocked {
+                    step: ally_step,
+                    ticks: ally_ticks,
+                },
+            )) if ally_step == position
+                || (ticks >= DEADLOCK_TICKS && ally_ticks >= DEADLOCK_TICKS) =>
+            {
+                let diagonal = step.0 != self.row && step.1 != self.col;
+                if !self
+                    .move_timer
+                    .try_step(self.stats.movement_speed, diagonal)
+                {
+                    return Ok(None);
                 }
+                self.traffic = Traffic::Moving;
+                return Ok(Some(ally_id));
             }
+            _ => {}
+        }
+
+        self.traffic = Traffic::Blocked { step, ticks };
+        // Another unit that does not move, the minion goes around it
+        if ticks % REPATH_DELAY_TICKS == 0
+            && let Some(path) = pathfinder.find_detour(board, position, self.current_path)
+        {
+            self.path = Some(path);
         }
+        Ok(None)
+    }
+
+    /// Exchanges the places of two minions on the board
+    pub fn swap_with(&mut self, ally: &mut Minion, board: &mut Board) {
+        board.swap_cells(
+            (self.row as usize, self.col as usize),
+            (ally.row as usize, ally.col as usize),
+        );
+        std::mem::swap(&mut self.row, &mut ally.row);
+        std::mem::swap(&mut self.col, &mut ally.col);
+        self.path = None;
+        ally.path = None;
+        ally.traffic = Traffic::Moving;
     }
 
     pub fn attack_phase(
         &mut self,
         board: &mut Board,
+        projectile_manager: &mut ProjectileManager,
         new_animations: &mut Vec<Box<dyn AnimationTrait>>,
-        pending_effects: &mut Vec<(Option<super::super::PlayerId>, Target, Vec<GameplayEffect>)>,
+        pending_effects: &mut Vec<(Target, Vec<GameplayEffect>)>,
     ) {
         if self.is_stunned() {
             return;
         }
-        if let Some(enemy) = self.get_potential_target(board) {
-            match &enemy.content {
-                Some(content) => match content {
-                    CellContent::Tower(id, _) => {
-                        if let Some(attack) = self.can_attack() {
-                            match attack {
-                                AttackAction::Melee { damage, animation } => {
-                                    new_animations.push(animation);
-                                    pending_effects.push((
-                                        None,
-                                        Target::Tower(*id),
-                                        vec![GameplayEffect::Damage(damage)],
-                                    ))
-                                }
-                                _ => {}
-                            }
-                        }
-                    }
-                    CellContent::Minion(id, _) => {
-                        if let Some(attack) = self.can_attack() {
-                            match attack {
-                                AttackAction::Melee { damage, animation } => {
-                                    new_animations.push(animation);
-                                    pending_effects.push((
-                                        None,
-                                        Target::Minion(*id),
-                                        vec![GameplayEffect::Damage(damage)],
-                                    ))
-                                }
-                                _ => {}
-                            }
-                        }
-                    }
-                    CellContent::Champion(id, _) => {
-                        if let Some(attack) = self.can_attack() {
-                            match attack {
-                                AttackAction::Melee { damage, animation } => {
-                                    new_animations.push(animation);
-                                    pending_effects.push((
-                                        None,
-                                        Target::Champion(*id),
-                                        vec![GameplayEffect::Damage(damage)],
-                                    ))
-                                }
-                                _ => {}
-                            }
-                        }
-                    }
-                    _ => return,
-                },
-                None => return,
+        if let Some(enemy) = self.get_potential_target(board)
+            && let Some(content) = &enemy.content
+            && self.in_attack_range(enemy.position)
+        {
+            let target = match content {
+                CellContent::Tower(id, _) => Target::Tower(*id),
+                CellContent::Minion(id, _) => Target::Minion(*id),
+                CellContent::Champion(id, _) => Target::Champion(*id),
+                _ => return,
+            };
+            match self.can_attack() {
+                Some(AttackAction::Melee { damage, animation }) => {
+                    new_animations.push(animation);
+                    pending_effects.push((target, vec![GameplayEffect::Damage(damage)]))
+                }
+                Some(AttackAction::Projectile {
+                    damage,
+                    speed,
+                    visual,
+                }) => projectile_manager.create_homing_projectile(
+                    self.minion_id,
+                    self.team_id,
+                    target,
+                    (self.row, self.col),
+                    speed,
+                    vec![GameplayEffect::Damage(damage)],
+                    visual,
+                ),
+                None => {}
             }
         }
     }
 
+    fn in_attack_range(&self, position: (u16, u16)) -> bool {
+        self.row.abs_diff(position.0) <= self.minion_stats.attack_range_row
+            && self.col.abs_diff(position.1) <= self.minion_stats.attack_range_col
+    }
+
     fn move_minion(
         &mut self,
         board: &mut Board,
@@ -273,6 +376,13 @@ impl Minion {
 
         if let Some(new_cell) = board.get_cell(new_row as usize, new_col as usize) {
             if new_cell.is_passable() {
+                let diagonal = new_row != self.row && new_col != self.col;
+                if !self
+                    .move_timer
+                    .try_step(self.stats.movement_speed, diagonal)
+                {
+                    return Ok(());
+                }
                 board.move_cell(
                     self.row as usize,
                     self.col as usize,
@@ -283,10 +393,34 @@ impl Minion {
                 self.col = new_col;
                 Ok(())
             } else {
-                return Err(GameError::CannotMoveHere(self.minion_id));
+                Err(GameError::CannotMoveHere(self.minion_id))
             }
         } else {
-            return Err(GameError::NotFoundCell);
+            Err(GameError::NotFoundCell)
+        }
+    }
+}
+
+impl Unit for Minion {
+    fn id(&self) -> EntityId {
+        self.minion_id
+    }
+
+    fn team(&self) -> Option<Team> {
+        Some(self.team_id)
+    }
+
+    fn position(&self) -> (u16, u16) {
+        (self.row, self.col)
+    }
+
+    fn stats(&self) -> &Stats {
+        &self.stats
+    }
+
+    fn ai(&self) -> Ai {
+        Ai::Lane {
+            goal: self.lane_goal(),
         }
     }
 }
@@ -296,13 +430,10 @@ impl Fighter for Minion {
         for effect in effects.into_iter() {
             match effect {
                 GameplayEffect::Damage(damage) => {
-                    let reduced_damage = reduced_damage(damage, self.stats.armor);
-                    self.stats.health = self.stats.health.saturating_sub(reduced_damage as u16);
-                }
-                GameplayEffect::Buff(mut buff) => {
-                    buff.on_apply(self);
-                    self.active_buffs.insert(buff.id().to_string(), buff);
+                    let damage_taken = self.stats.damage_taken(&damage);
+                    self.stats.health = self.stats.health.saturating_sub(damage_taken);
                 }
+                GameplayEffect::Buff(..) => {}
             }
         }
     }
@@ -310,11 +441,20 @@ impl Fighter for Minion {
     fn can_attack(&mut self) -> Option<AttackAction> {
         if self.last_attacked + self.stats.attack_speed < Instant::now() {
             self.last_attacked = Instant::now();
-            let animation = MeleeAnimation::new(self.minion_id);
-            Some(AttackAction::Melee {
-                damage: self.stats.attack_damage,
-                animation: Box::new(animation),
-            })
+            match self.minion_stats.attack {
+                AttackType::Melee => {
+                    let animation = MeleeAnimation::new(self.minion_id);
+                    Some(AttackAction::Melee {
+                        damage: self.stats.attack(DamageSource::Minion(self.minion_id)),
+                        animation: Box::new(animation),
+                    })
+                }
+                AttackType::Ranged { projectile_speed } => Some(AttackAction::Projectile {
+                    damage: self.stats.attack(DamageSource::Minion(self.minion_id)),
+                    speed: projectile_speed,
+                    visual: CellAnimation::Projectile,
+                }),
+            }
         } else {
             None
         }
@@ -367,7 +507,7 @@ impl Fighter for Minion {
 impl HasBuff for Minion {
     fn is_stunned(&self) -> bool {
         self.stun_timer
-            .map_or(false, |timer_end| Instant::now() < timer_end)
+            .is_some_and(|timer_end| Instant::now() < timer_end)
     }
 
     fn set_stunned(&mut self, stunned: bool, duration: Option<Duration>) {
@@ -388,10 +528,13 @@ mod tests {
     use super::*;
     use crate::config::MinionStats;
     use crate::game::buffs::stun_buff::StunBuff;
+    use crate::game::entities::damage::{Damage, DamageSource};
+    use crate::game::entities::projectile::PathingLogic;
     use crate::game::{
         Board, MinionId,
         cell::Team,
         cell::{BaseTerrain, CellContent},
+        registry::{EntityKind, EntityRegistry},
     };
 
     fn create_dummy_board(rows: usize, cols: usize) -> Board {
@@ -416,31 +559,72 @@ mod tests {
         (next_row, next_col)
     }
 
+    fn create_lane_layout(team: Team, lane: Lane) -> LaneLayout {
+        let (spawn, waypoints) = match (team, lane) {
+            (Team::Blue, Lane::Top) => (
+                (184, 10),
+                vec![(120, 8), (39, 7), (7, 39), (8, 120), (10, 184)],
+            ),
+            (Team::Blue, Lane::Mid) => (
+                (184, 17),
+                vec![(148, 67), (115, 82), (82, 115), (67, 148), (17, 184)],
+            ),
+            (Team::Blue, Lane::Bottom) => (
+                (191, 17),
+                vec![(191, 79), (196, 150), (150, 196), (79, 191), (17, 191)],
+            ),
+            (Team::Red, Lane::Top) => (
+                (10, 184),
+                vec![(8, 120), (7, 39), (39, 7), (120, 8), (184, 10)],
+            ),
+            (Team::Red, Lane::Mid) => (
+                (17, 184),
+                vec![(67, 148), (82, 115), (115, 82), (148, 67), (184, 17)],
+            ),
+            (Team::Red, Lane::Bottom) => (
+                (17, 191),
+                vec![(79, 191), (150, 196), (196, 150), (191, 79), (191, 17)],
+            ),
+        };
+        LaneLayout {
+            team,
+            lane,
+            spawn,
+            waypoints,
+        }
+    }
+
     fn create_default_minion_stats() -> MinionStats {
         MinionStats {
             attack_damage: 6,
             attack_speed_ms: 2500,
             health: 40,
             armor: 0,
+            magic_resist: 0,
             aggro_range_row: 10,
             aggro_range_col: 10,
             attack_range_row: 3,
             attack_range_col: 3,
+            attack: AttackType::Melee,
+            movement_speed: 1,
         }
     }
 
     #[test]
     fn test_minion_stun_application() {
         let minion_stats = create_default_minion_stats();
-        let mut minion = Minion::new(1, Team::Blue, Lane::Mid, minion_stats);
+        let mut minion = Minion::new(1, &create_lane_layout(Team::Blue, Lane::Mid), minion_stats);
         let mut board = create_dummy_board(200, 200);
+        let mut projectile_manager = ProjectileManager::new();
         let mut new_animations = Vec::new();
         let mut pending_effects = Vec::new();
 
+        let mut registry = EntityRegistry::new();
+        let id = registry.spawn(EntityKind::Minion, Some(Team::Blue));
+
         // Apply a stun buff
         let stun_duration_secs = 5;
-        let stun_effect = GameplayEffect::Buff(Box::new(StunBuff::new(stun_duration_secs)));
-        minion.take_effect(vec![stun_effect]);
+        registry.add_buff(id, Box::new(StunBuff::new(stun_duration_secs)), &mut minion);
 
         // Assert minion is stunned
         assert!(
@@ -452,7 +636,8 @@ mod tests {
         let initial_col = minion.col;
 
         // Assert stunned minion cannot move
-        let move_result = minion.movement_phase(&mut board);
+        let move_result =
+            minion.movement_phase(&mut board, &mut Pathfinder::new(10_000), &HashMap::new());
         assert!(
             move_result.is_ok(),
             "Stunned minion should not be able to move"
@@ -472,7 +657,12 @@ mod tests {
             "Stunned minion should not be able to attack"
         );
         // attack_phase should also do nothing
-        minion.attack_phase(&mut board, &mut new_animations, &mut pending_effects);
+        minion.attack_phase(
+            &mut board,
+            &mut projectile_manager,
+            &mut new_animations,
+            &mut pending_effects,
+        );
         assert!(
             new_animations.is_empty(),
             "Stunned minion attack_phase should not create animations"
@@ -486,26 +676,19 @@ mod tests {
     #[test]
     fn test_minion_stun_expiration() {
         let minion_stats = create_default_minion_stats();
-        let mut minion = Minion::new(1, Team::Blue, Lane::Mid, minion_stats);
+        let mut minion = Minion::new(1, &create_lane_layout(Team::Blue, Lane::Mid), minion_stats);
         let mut board = create_dummy_board(200, 200);
         minion.row = 180;
         minion.col = 10;
 
-        // Apply a very short stun buff
-        let stun_effect = GameplayEffect::Buff(Box::new(StunBuff::new(0))); // Duration 0 for immediate expiration
-        minion.take_effect(vec![stun_effect]);
+        let mut registry = EntityRegistry::new();
+        let id = registry.spawn(EntityKind::Minion, Some(Team::Blue));
+
+        // Apply a very short stun buff, duration 0 for immediate expiration
+        registry.add_buff(id, Box::new(StunBuff::new(0)), &mut minion);
 
         // Manually process buffs to trigger expiration
-        let current_buffs = std::mem::take(&mut minion.active_buffs);
-        let mut kept_buffs = HashMap::new();
-        for (id, mut buff) in current_buffs.into_iter() {
-            if buff.on_tick(&mut minion) {
-                buff.on_remove(&mut minion);
-            } else {
-                kept_buffs.insert(id, buff);
-            }
-        }
-        minion.active_buffs = kept_buffs;
+        registry.tick_buffs(id, &mut minion);
 
         // Assert minion is no longer stunned
         assert!(
@@ -523,7 +706,8 @@ mod tests {
             minion.row as usize,
             minion.col as usize,
         );
-        let move_result = minion.movement_phase(&mut board);
+        let move_result =
+            minion.movement_phase(&mut board, &mut Pathfinder::new(10_000), &HashMap::new());
         assert!(
             move_result.is_ok(),
             "Unstunned minion should be able to move"
@@ -542,31 +726,57 @@ mod tests {
         );
     }
 
+    #[test]
     fn test_new_minion() {
         let minion_id = 1;
         let minion_stats = create_default_minion_stats();
 
         // Test Blue Team Minions
-        let blue_top_minion = Minion::new(minion_id, Team::Blue, Lane::Top, minion_stats.clone());
+        let blue_top_minion = Minion::new(
+            minion_id,
+            &create_lane_layout(Team::Blue, Lane::Top),
+            minion_stats.clone(),
+        );
         assert_eq!(blue_top_minion.minion_id, minion_id);
         assert_eq!(blue_top_minion.team_id, Team::Blue);
-        assert_eq!(blue_top_minion.lane, Lane::Top);
         assert_eq!(blue_top_minion.stats.health, minion_stats.health); // Check a few stats fields
         assert_eq!(blue_top_minion.row, 184);
         assert_eq!(blue_top_minion.col, 10);
         assert_eq!(blue_top_minion.current_path, (120, 8));
 
         // Test Red Team Minions
-        let red_top_minion = Minion::new(minion_id, Team::Red, Lane::Top, minion_stats.clone());
+        let red_top_minion = Minion::new(
+            minion_id,
+            &create_lane_layout(Team::Red, Lane::Top),
+            minion_stats.clone(),
+        );
         assert_eq!(red_top_minion.minion_id, minion_id);
         assert_eq!(red_top_minion.team_id, Team::Red);
-        assert_eq!(red_top_minion.lane, Lane::Top);
         assert_eq!(red_top_minion.stats.health, minion_stats.health);
         assert_eq!(red_top_minion.row, 10);
         assert_eq!(red_top_minion.col, 184);
         assert_eq!(red_top_minion.current_path, (8, 120));
     }
 
+    #[test]
+    fn test_minion_stops_at_last_waypoint() {
+        let lane = LaneLayout {
+            team: Team::Blue,
+            lane: Lane::Mid,
+            spawn: (5, 5),
+            waypoints: vec![(5, 8), (2, 8)],
+        };
+        let mut minion = Minion::new(1, &lane, create_default_minion_stats());
+        assert_eq!((minion.row, minion.col), (5, 5));
+        assert_eq!(minion.current_path, (5, 8));
+
+        minion.change_goal();
+        assert_eq!(minion.current_path, (2, 8));
+        // The last waypoint stays the goal
+        minion.change_goal();
+        assert_eq!(minion.current_path, (2, 8));
+    }
+
     #[test]
     fn test_move_minion_to_passable_cell() {
         let mut board = create_dummy_board(10, 10);
@@ -577,7 +787,11 @@ mod tests {
 
         // Create a minion and place it on the board
         let minion_stats = create_default_minion_stats();
-        let mut minion = Minion::new(minion_id, team_id, Lane::Mid, minion_stats);
+        let mut minion = Minion::new(
+            minion_id,
+            &create_lane_layout(team_id, Lane::Mid),
+            minion_stats,
+        );
         minion.row = initial_row; // Set initial position manually for testing
         minion.col = initial_col;
         let minion_content = CellContent::Minion(minion_id, team_id);
@@ -628,6 +842,7 @@ mod tests {
         // Test moving down (d_row = 1, d_col = 0) - Reset position first
         minion.row = initial_row;
         minion.col = initial_col;
+        minion.move_timer = MoveTimer::default();
         board.place_cell(
             minion_content.clone(),
             initial_row as usize,
@@ -678,6 +893,7 @@ mod tests {
         // Test moving up-left (d_row = -1, d_col = -1) - Reset position first
         minion.row = initial_row;
         minion.col = initial_col;
+        minion.move_timer = MoveTimer::default();
         board.place_cell(
             minion_content.clone(),
             initial_row as usize,
@@ -735,7 +951,11 @@ mod tests {
         let initial_col = 179;
 
         let minion_stats = create_default_minion_stats();
-        let mut minion = Minion::new(minion_id, team_id, Lane::Bottom, minion_stats);
+        let mut minion = Minion::new(
+            minion_id,
+            &create_lane_layout(team_id, Lane::Bottom),
+            minion_stats,
+        );
         minion.row = initial_row;
         minion.col = initial_col;
         minion.change_goal();
@@ -754,7 +974,11 @@ mod tests {
         let initial_col = 7;
 
         let minion_stats = create_default_minion_stats();
-        let mut minion = Minion::new(minion_id, team_id, Lane::Top, minion_stats);
+        let mut minion = Minion::new(
+            minion_id,
+            &create_lane_layout(team_id, Lane::Top),
+            minion_stats,
+        );
         minion.checkpoint = 2;
         minion.row = initial_row;
         minion.col = initial_col;
@@ -775,7 +999,11 @@ mod tests {
         let initial_col = 0;
 
         let minion_stats = create_default_minion_stats();
-        let mut minion = Minion::new(minion_id, team_id, Lane::Mid, minion_stats);
+        let mut minion = Minion::new(
+            minion_id,
+            &create_lane_layout(team_id, Lane::Mid),
+            minion_stats,
+        );
         minion.row = initial_row;
         minion.col = initial_col;
         let minion_content = CellContent::Minion(minion_id, team_id);
@@ -853,7 +1081,11 @@ mod tests {
         // Add tests for moving out of bounds from other edges/corners similarly...
         // Test moving down from row 9
         let minion_stats_bottom = create_default_minion_stats();
-        let mut minion_bottom = Minion::new(minion_id + 1, team_id, Lane::Mid, minion_stats_bottom);
+        let mut minion_bottom = Minion::new(
+            minion_id + 1,
+            &create_lane_layout(team_id, Lane::Mid),
+            minion_stats_bottom,
+        );
         let initial_row_bottom = 9;
         let initial_col_bottom = 5;
         minion_bottom.row = initial_row_bottom;
@@ -897,7 +1129,11 @@ mod tests {
         let initial_col = 5;
 
         let minion_stats = create_default_minion_stats();
-        let mut minion = Minion::new(minion_id, team_id, Lane::Mid, minion_stats);
+        let mut minion = Minion::new(
+            minion_id,
+            &create_lane_layout(team_id, Lane::Mid),
+            minion_stats,
+        );
         minion.row = initial_row;
         minion.col = initial_col;
         let minion_content = CellContent::Minion(minion_id, team_id);
@@ -1016,7 +1252,11 @@ mod tests {
         let goal_col1 = 20;
 
         let minion_stats = create_default_minion_stats();
-        let mut minion1 = Minion::new(minion_id, team_id, Lane::Mid, minion_stats);
+        let mut minion1 = Minion::new(
+            minion_id,
+            &create_lane_layout(team_id, Lane::Mid),
+            minion_stats,
+        );
         minion1.row = initial_row1;
         minion1.col = initial_col1;
         minion1.current_path = (goal_row1, goal_col1); // Set the goal
@@ -1039,7 +1279,7 @@ mod tests {
         );
 
         // Call minion_turn (assuming updated signature fn minion_turn(&mut self, board: &mut Board))
-        minion1.movement_phase(&mut board1);
+        let _ = minion1.movement_phase(&mut board1, &mut Pathfinder::new(10_000), &HashMap::new());
 
         // Assert the minion's position
         assert_eq!(
@@ -1076,7 +1316,11 @@ mod tests {
         let goal_col2 = 40;
 
         let minion_stats = create_default_minion_stats();
-        let mut minion2 = Minion::new(minion_id, team_id, Lane::Mid, minion_stats);
+        let mut minion2 = Minion::new(
+            minion_id,
+            &create_lane_layout(team_id, Lane::Mid),
+            minion_stats,
+        );
         minion2.row = initial_row2;
         minion2.col = initial_col2;
         minion2.current_path = (goal_row2, goal_col2); // Set the goal
@@ -1099,7 +1343,7 @@ mod tests {
         );
 
         // Call minion_turn
-        minion2.movement_phase(&mut board2);
+        let _ = minion2.movement_phase(&mut board2, &mut Pathfinder::new(10_000), &HashMap::new());
 
         // Assert the minion's position
         assert_eq!(
@@ -1136,7 +1380,11 @@ mod tests {
         let goal_col3 = 100; // Same column
 
         let minion_stats = create_default_minion_stats();
-        let mut minion3 = Minion::new(minion_id, team_id, Lane::Mid, minion_stats);
+        let mut minion3 = Minion::new(
+            minion_id,
+            &create_lane_layout(team_id, Lane::Mid),
+            minion_stats,
+        );
         minion3.row = initial_row3;
         minion3.col = initial_col3;
         minion3.current_path = (goal_row3, goal_col3); // Set the goal
@@ -1159,7 +1407,7 @@ mod tests {
         );
 
         // Call minion_turn
-        minion3.movement_phase(&mut board3);
+        let _ = minion3.movement_phase(&mut board3, &mut Pathfinder::new(10_000), &HashMap::new());
 
         // Assert the minion's position
         assert_eq!(
@@ -1197,7 +1445,11 @@ mod tests {
         let minion_row = 25; // Center minion on a large board
         let minion_col = 25;
         let minion_stats = create_default_minion_stats();
-        let mut minion = Minion::new(minion_id, minion_team, Lane::Mid, minion_stats);
+        let mut minion = Minion::new(
+            minion_id,
+            &create_lane_layout(minion_team, Lane::Mid),
+            minion_stats,
+        );
         minion.row = minion_row;
         minion.col = minion_col;
 
@@ -1245,7 +1497,11 @@ mod tests {
         let minion_row = 25; // Center minion
         let minion_col = 25;
         let minion_stats = create_default_minion_stats();
-        let mut minion = Minion::new(minion_id, minion_team, Lane::Mid, minion_stats);
+        let mut minion = Minion::new(
+            minion_id,
+            &create_lane_layout(minion_team, Lane::Mid),
+            minion_stats,
+        );
         minion.row = minion_row;
         minion.col = minion_col;
 
@@ -1344,7 +1600,11 @@ mod tests {
         let minion_row = 25; // Center minion
         let minion_col = 25;
         let minion_stats = create_default_minion_stats();
-        let mut minion = Minion::new(minion_id, minion_team, Lane::Mid, minion_stats);
+        let mut minion = Minion::new(
+            minion_id,
+            &create_lane_layout(minion_team, Lane::Mid),
+            minion_stats,
+        );
         minion.row = minion_row;
         minion.col = minion_col;
 
@@ -1445,7 +1705,11 @@ mod tests {
         let minion_row = 25; // Center minion
         let minion_col = 25;
         let minion_stats = create_default_minion_stats(); // aggro_range_row: 10, aggro_range_col: 10
-        let mut minion = Minion::new(minion_id, minion_team, Lane::Mid, minion_stats.clone()); // Use clone to avoid moving minion_stats
+        let mut minion = Minion::new(
+            minion_id,
+            &create_lane_layout(minion_team, Lane::Mid),
+            minion_stats.clone(),
+        ); // Use clone to avoid moving minion_stats
 
         minion.row = minion_row;
         minion.col = minion_col;
@@ -1484,4 +1748,89 @@ mod tests {
             "Should return None when enemies are outside the specified range (col)"
         );
     }
+
+    #[test]
+    fn test_caster_minion_fires_projectile() {
+        let mut board = create_dummy_board(20, 20);
+        let mut projectile_manager = ProjectileManager::new();
+        let mut new_animations = Vec::new();
+        let mut pending_effects = Vec::new();
+
+        let mut minion_stats = create_default_minion_stats();
+        minion_stats.attack = AttackType::Ranged {
+            projectile_speed: 2,
+        };
+        let mut minion = Minion::new(1, &create_lane_layout(Team::Blue, Lane::Mid), minion_stats);
+        minion.row = 10;
+        minion.col = 10;
+        board.place_cell(CellContent::Minion(1, Team::Blue), 10, 10);
+        board.place_cell(CellContent::Minion(2, Team::Red), 10, 13);
+
+        // Target is in attack range, the caster does not move closer
+        let move_result =
+            minion.movement_phase(&mut board, &mut Pathfinder::new(10_000), &HashMap::new());
+        assert!(move_result.is_ok());
+        assert_eq!((minion.row, minion.col), (10, 10));
+
+        minion.last_attacked = Instant::now() - minion.stats.attack_speed - Duration::from_secs(1);
+        minion.attack_phase(
+            &mut board,
+            &mut projectile_manager,
+            &mut new_animations,
+            &mut pending_effects,
+        );
+
+        assert!(new_animations.is_empty());
+        assert!(
+            pending_effects.is_empty(),
+            "Damage is dealt when the projectile hits"
+        );
+        assert_eq!(projectile_manager.projectiles.len(), 1);
+        let projectile = projectile_manager.projectiles.values().next().unwrap();
+        assert_eq!(projectile.team_id, Team::Blue);
+        assert_eq!(
+            projectile.payloads,
+            vec![GameplayEffect::Damage(Damage::physical(
+                6,
+                DamageSource::Minion(1)
+            ))]
+        );
+        assert!(matches!(
+            projectile.pathing,
+            PathingLogic::LockOn {
+                target_id: Target::Minion(2)
+            }
+        ));
+    }
+
+    #[test]
+    fn test_minion_does_not_attack_out_of_range() {
+        let mut board = create_dummy_board(20, 20);
+        let mut projectile_manager = ProjectileManager::new();
+        let mut new_animations = Vec::new();
+        let mut pending_effects = Vec::new();
+
+        let mut minion = Minion::new(
+            1,
+            &create_lane_layout(Team::Blue, Lane::Mid),
+            create_default_minion_stats(),
+        );
+        minion.row = 10;
+        minion.col = 10;
+        board.place_cell(CellContent::Minion(1, Team::Blue), 10, 10);
+        // In aggro range but outside of attack range
+        board.place_cell(CellContent::Minion(2, Team::Red), 10, 16);
+
+        minion.last_attacked = Instant::now() - minion.stats.attack_speed - Duration::from_secs(1);
+        minion.attack_phase(
+            &mut board,
+            &mut projectile_manager,
+            &mut new_animations,
+            &mut pending_effects,
+        );
+
+        assert!(new_animations.is_empty());
+        assert!(pending_effects.is_empty());
+        assert!(projectile_manager.projectiles.is_empty());
+    }
 }
@@ -1,14 +1,20 @@
 use std::time::Duration;
 
+use damage::{Damage, DamageSource};
 use projectile::GameplayEffect;
 
 use super::{
-    Board, Cell, MinionId, PlayerId, TowerId, animation::AnimationTrait, cell::CellAnimation,
+    Board, Cell, CellContent, EntityId, MinionId, MonsterId, PlayerId, TowerId,
+    algorithms::pathfinding::{DIAGONAL_STEP_COST, STRAIGHT_STEP_COST},
+    animation::AnimationTrait,
+    cell::CellAnimation,
+    registry::Ai,
 };
 use crate::game::cell::Team;
 
 pub mod base;
 pub mod champion;
+pub mod damage;
 pub mod minion;
 pub mod monster;
 pub mod projectile;
@@ -16,11 +22,11 @@ pub mod tower;
 
 pub enum AttackAction {
     Melee {
-        damage: u16,
+        damage: Damage,
         animation: Box<dyn AnimationTrait>,
     },
     Projectile {
-        damage: u16,
+        damage: Damage,
         speed: u32,
         visual: CellAnimation,
     },
@@ -32,10 +38,23 @@ pub enum Target {
     Minion(MinionId),
     Champion(PlayerId),
     Base(Team),
-    Monster(MinionId),
+    Monster(MonsterId),
 }
 
-#[derive(Debug)]
+impl Target {
+    /// Unit standing in a cell
+    pub fn from_content(content: &CellContent) -> Option<Target> {
+        match content {
+            CellContent::Tower(id, _) => Some(Target::Tower(*id)),
+            CellContent::Monster(id) => Some(Target::Monster(*id)),
+            CellContent::Minion(id, _) => Some(Target::Minion(*id)),
+            CellContent::Champion(id, _) => Some(Target::Champion(*id)),
+            CellContent::Base(team) => Some(Target::Base(*team)),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct Stats {
     attack_damage: u16,
     attack_speed: Duration,
@@ -44,14 +63,100 @@ pub struct Stats {
     pub mana: u16,
     pub max_mana: u16,
     armor: u16,
+    magic_resist: u16,
+    armor_penetration_flat: u16,
+    armor_penetration_percent: f32,
+    /// Chance of an auto attack to be a critical strike, from 0 to 1
+    crit_chance: f32,
+    crit_multiplier: f32,
+    /// Ticks needed to walk one cell, a diagonal step takes about 1.4 times longer
+    pub movement_speed: u16,
+}
+
+impl Stats {
+    /// Physical damage of an auto attack, rolling for a critical strike
+    fn attack(&self, source: DamageSource) -> Damage {
+        let critical = self.crit_chance > 0. && rand::random_bool(self.crit_chance.min(1.) as f64);
+        let amount = if critical {
+            (self.attack_damage as f32 * self.crit_multiplier) as u16
+        } else {
+            self.attack_damage
+        };
+        Damage {
+            armor_penetration_flat: self.armor_penetration_flat,
+            armor_penetration_percent: self.armor_penetration_percent,
+            critical,
+            ..Damage::physical(amount, source)
+        }
+    }
+
+    /// Health lost to a hit
+    fn damage_taken(&self, damage: &Damage) -> u16 {
+        damage.mitigated(self.armor, self.magic_resist)
+    }
+}
+
+/// Paces the steps of a unit. The timer gains one straight step every tick,
+/// and a step spends the cost of the step times the movement speed
+#[derive(Debug)]
+pub struct MoveTimer {
+    points: u32,
+}
+
+impl Default for MoveTimer {
+    // A unit can take a diagonal step right away
+    fn default() -> Self {
+        MoveTimer { points: u32::MAX }
+    }
+}
+
+impl MoveTimer {
+    fn step_points(movement_speed: u16, diagonal: bool) -> u32 {
+        let cost = if diagonal {
+            DIAGONAL_STEP_COST
+        } else {
+            STRAIGHT_STEP_COST
+        };
+        cost * movement_speed as u32
+    }
+
+    /// Called once per tick. Unused points are kept for one diagonal step at most
+    pub fn recharge(&mut self, movement_speed: u16) {
+        self.points = self
+            .points
+            .saturating_add(STRAIGHT_STEP_COST)
+            .min(Self::step_points(movement_speed, true));
+    }
+
+    pub fn can_step(&self, movement_speed: u16, diagonal: bool) -> bool {
+        self.points >= Self::step_points(movement_speed, diagonal)
+    }
+
+    /// Spends the points of a step, returns false when the unit must wait
+    pub fn try_step(&mut self, movement_speed: u16, diagonal: bool) -> bool {
+        if !self.can_step(movement_speed, diagonal) {
+            return false;
+        }
+        // A full timer holds one diagonal step, whatever its starting value
+        self.points = self.points.min(Self::step_points(movement_speed, true));
+        self.points -= Self::step_points(movement_speed, diagonal);
+        true
+    }
 }
 
 pub trait Fighter {
+    /// Applies the damages, buffs are kept and applied by the `EntityRegistry`
     fn take_effect(&mut self, effects: Vec<GameplayEffect>);
     fn can_attack(&mut self) -> Option<AttackAction>;
     fn get_potential_target<'a>(&self, board: &'a Board) -> Option<&'a Cell>;
 }
 
-pub fn reduced_damage(damage: u16, armor: u16) -> u16 {
-    damage / (1 + (armor / 100))
+/// What every unit of the entity registry exposes, whatever its store
+pub trait Unit {
+    fn id(&self) -> EntityId;
+    /// `None` for neutral monsters
+    fn team(&self) -> Option<Team>;
+    fn position(&self) -> (u16, u16);
+    fn stats(&self) -> &Stats;
+    fn ai(&self) -> Ai;
 }
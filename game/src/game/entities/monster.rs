@@ -3,11 +3,21 @@ use std::{
     time::{Duration, Instant},
 };
 
-use crate::{config::MonsterStats, errors::GameError, game::{algorithms::pathfinding::find_path_on_board, animation::melee::MeleeAnimation, buffs::Buff, cell::MonsterId, entities::AttackAction, Board, PlayerId}};
+use crate::{
+    config::{MonsterAbilityStats, MonsterStats},
+    game::{
+        Board, Champion, PlayerId,
+        algorithms::pathfinding::Pathfinder,
+        animation::melee::MeleeAnimation,
+        cell::{EntityId, MinionId, MonsterId, Team},
+        entities::AttackAction,
+        registry::Ai,
+    },
+};
 
-use super::{projectile::GameplayEffect, reduced_damage, Fighter, Stats};
+use super::{Fighter, MoveTimer, Stats, Unit, damage::DamageSource, projectile::GameplayEffect};
 
-#[derive(PartialEq, Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum MonsterState {
     Aggro,
     Idle,
@@ -15,28 +25,38 @@ pub enum MonsterState {
     Dead,
 }
 
+/// Unit a monster fights back, towers only deal damage
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MonsterTarget {
+    Champion(PlayerId),
+    Minion(MinionId),
+}
+
 #[derive(Debug)]
 pub struct Monster {
     pub id: MonsterId,
     pub monster_id: String,
     pub state: MonsterState,
-    pub target_champion_id: Option<PlayerId>,
+    pub target: Option<MonsterTarget>,
+    /// Units that hit the monster since it left its camp
+    pub attackers: Vec<MonsterTarget>,
     pub path: Option<VecDeque<(u16, u16)>>,
     pub stats: Stats,
     pub last_attacked: Instant,
-    stun_timer: Option<Instant>,
-    pub active_buffs: HashMap<String, Box<dyn Buff>>,
-    pub respawn_timer: Duration,
     pub death_time: Option<Instant>,
+    pub move_timer: MoveTimer,
     pub row: u16,
     pub col: u16,
     pub spawn_row: u16,
     pub spawn_col: u16,
     pub leash_range: u8,
+    pub aggro_range: (u16, u16),
+    ability: Option<MonsterAbilityStats>,
+    pub last_ability: Instant,
 }
 
 impl Monster {
-    pub fn new(id: MonsterId, monster_stats: MonsterStats) -> Monster {
+    pub fn new(id: MonsterId, monster_stats: MonsterStats, spawn: (u16, u16)) -> Monster {
         let stats = Stats {
             attack_damage: monster_stats.attack_damage,
             attack_speed: Duration::from_millis(monster_stats.attack_speed_ms),
@@ -45,44 +65,97 @@ impl Monster {
             mana: 0,
             max_mana: 0,
             armor: monster_stats.armor,
+            magic_resist: monster_stats.magic_resist,
+            // Only champions get penetration and critical strikes
+            armor_penetration_flat: 0,
+            armor_penetration_percent: 0.,
+            crit_chance: 0.,
+            crit_multiplier: 1.,
+            movement_speed: monster_stats.movement_speed,
         };
 
         Monster {
             id,
             monster_id: monster_stats.id,
             state: MonsterState::Idle,
-            target_champion_id: None,
+            target: None,
+            attackers: Vec::new(),
             path: None,
             stats,
             last_attacked: Instant::now(),
-            stun_timer: None,
-            active_buffs: HashMap::new(),
-            respawn_timer: Duration::from_secs(monster_stats.respawn_timer_secs as u64),
             death_time: None,
-            row: monster_stats.spawn_row,
-            col: monster_stats.spawn_col,
-            spawn_row: monster_stats.spawn_row,
-            spawn_col: monster_stats.spawn_col,
+            move_timer: MoveTimer::default(),
+            row: spawn.0,
+            col: spawn.1,
+            spawn_row: spawn.0,
+            spawn_col: spawn.1,
             leash_range: monster_stats.leash_range,
+            aggro_range: (
+                monster_stats.aggro_range_row as u16,
+                monster_stats.aggro_range_col as u16,
+            ),
+            ability: monster_stats.ability,
+            last_ability: Instant::now(),
         }
     }
 
-    pub fn attach_target(&mut self, player_id: PlayerId) {
-        // A dead monster cannot aggro or acquire a target
-        if self.state == MonsterState::Dead {
+    pub fn attach_target(&mut self, target: MonsterTarget) {
+        // A dead or returning monster cannot aggro or acquire a target
+        if matches!(self.state, MonsterState::Dead | MonsterState::Returning) {
             return;
         }
         self.state = MonsterState::Aggro;
-        match self.target_champion_id {
-            Some(_) => {},
-            None => self.target_champion_id = Some(player_id),
+        if !self.attackers.contains(&target) {
+            self.attackers.push(target);
         }
+        match self.target {
+            Some(_) => {}
+            None => self.target = Some(target),
+        }
+    }
+
+    /// Whether a cell is close enough to the camp to be fought on
+    pub fn in_leash(&self, (row, col): (u16, u16)) -> bool {
+        let leash_range = self.leash_range as u16;
+        row.abs_diff(self.spawn_row) < leash_range && col.abs_diff(self.spawn_col) < leash_range
+    }
+
+    /// Switches to the nearest attacker still in reach when the target is lost,
+    /// `position` gives the position of the attackers still alive
+    pub fn retarget(&mut self, position: impl Fn(MonsterTarget) -> Option<(u16, u16)>) {
+        let reachable: Vec<(MonsterTarget, (u16, u16))> = self
+            .attackers
+            .iter()
+            .filter_map(|attacker| position(*attacker).map(|cell| (*attacker, cell)))
+            .filter(|(_, cell)| self.in_leash(*cell))
+            .collect();
+        self.attackers = reachable.iter().map(|(attacker, _)| *attacker).collect();
+        self.target = reachable
+            .into_iter()
+            .min_by_key(|(_, (row, col))| row.abs_diff(self.row).max(col.abs_diff(self.col)))
+            .map(|(attacker, _)| attacker);
+        self.path = None;
     }
 
-    pub fn start_returning(&mut self, board: &Board) {
+    /// Heals a tenth of its health each tick while walking back to the camp
+    pub fn regenerate(&mut self) {
+        let amount = (self.stats.max_health / 10).max(1);
+        self.stats.health = self
+            .stats
+            .health
+            .saturating_add(amount)
+            .min(self.stats.max_health);
+    }
+
+    pub fn start_returning(&mut self, board: &Board, pathfinder: &mut Pathfinder) {
         self.state = MonsterState::Returning;
-        self.target_champion_id = None;
-        let mut path = find_path_on_board(board, (self.row, self.col), (self.spawn_row, self.spawn_col));
+        self.target = None;
+        self.attackers.clear();
+        let mut path = pathfinder.find_path(
+            board,
+            (self.row, self.col),
+            (self.spawn_row, self.spawn_col),
+        );
         if let Some(ref mut p) = path {
             p.push_back((self.spawn_row, self.spawn_col));
         }
@@ -95,17 +168,52 @@ impl Monster {
         self.path = None;
     }
 
-    pub fn can_respawn(&self) -> bool {
-        if let Some(death_timer) = self.death_time {
-            if death_timer.elapsed() > self.respawn_timer {
-                return true;
-            } else {
-                return false
-            }
-        } else {
-            // TODO: We need to return an error here, can timer should always be set.
-            return false
+    /// Slams the champions around the monster once the cooldown is over,
+    /// returns the damage and the champions hit
+    pub fn cast_ability(
+        &mut self,
+        champions: &HashMap<PlayerId, Champion>,
+    ) -> Option<(u16, Vec<PlayerId>)> {
+        let ability = self.ability.as_ref()?;
+        if self.last_ability.elapsed() < Duration::from_secs(ability.cooldown_secs) {
+            return None;
+        }
+        let hit: Vec<PlayerId> = champions
+            .iter()
+            .filter(|(_, champion)| {
+                !champion.is_dead()
+                    && champion.row.abs_diff(self.row) <= ability.radius
+                    && champion.col.abs_diff(self.col) <= ability.radius
+            })
+            .map(|(player_id, _)| *player_id)
+            .collect();
+        if hit.is_empty() {
+            return None;
         }
+        self.last_ability = Instant::now();
+        Some((ability.damage, hit))
+    }
+}
+
+impl Unit for Monster {
+    fn id(&self) -> EntityId {
+        self.id
+    }
+
+    fn team(&self) -> Option<Team> {
+        None
+    }
+
+    fn position(&self) -> (u16, u16) {
+        (self.row, self.col)
+    }
+
+    fn stats(&self) -> &Stats {
+        &self.stats
+    }
+
+    fn ai(&self) -> Ai {
+        Ai::Camp(self.state)
     }
 }
 
@@ -114,16 +222,15 @@ impl Fighter for Monster {
         for effect in effects.into_iter() {
             match effect {
                 GameplayEffect::Damage(damage) => {
-                    let reduced_damage = reduced_damage(damage, self.stats.armor);
-                    self.stats.health = self.stats.health.saturating_sub(reduced_damage as u16);
+                    let damage_taken = self.stats.damage_taken(&damage);
+                    self.stats.health = self.stats.health.saturating_sub(damage_taken);
                     if self.stats.health == 0 {
                         self.state = MonsterState::Dead;
-                        self.target_champion_id = None;
+                        self.target = None;
                         self.death_time = Some(Instant::now());
                     }
                 }
-                GameplayEffect::Buff(..) => {
-                }
+                GameplayEffect::Buff(..) => {}
             };
         }
     }
@@ -133,7 +240,7 @@ impl Fighter for Monster {
             self.last_attacked = Instant::now();
             let animation = MeleeAnimation::new(self.id);
             Some(AttackAction::Melee {
-                damage: self.stats.attack_damage,
+                damage: self.stats.attack(DamageSource::Monster(self.id)),
                 animation: Box::new(animation),
             })
         } else {
@@ -141,44 +248,50 @@ impl Fighter for Monster {
         }
     }
 
-    fn get_potential_target<'a>(&self, _board: &'a crate::game::Board) -> Option<&'a crate::game::Cell> {
+    fn get_potential_target<'a>(
+        &self,
+        _board: &'a crate::game::Board,
+    ) -> Option<&'a crate::game::Cell> {
         // No need for monster
         unimplemented!()
     }
 }
 
-
 #[cfg(test)]
 mod tests {
-    use crate::{config::MonsterStats, game::{entities::AttackAction, Board}};
+    use crate::{
+        config::MonsterStats,
+        game::{Board, entities::AttackAction},
+    };
 
     use super::*;
+    use crate::game::entities::damage::{Damage, DamageSource};
     use std::time::Duration;
 
     // Helper function to create a default monster definition for tests
     fn create_test_monster_def() -> MonsterStats {
         MonsterStats {
             id: "wolf_test".to_string(),
-            spawn_row: 1,
-            spawn_col: 1,
             health: 100,
             armor: 5,
+            magic_resist: 0,
             attack_damage: 10,
-            attack_range_row: 1,
-            attack_range_col: 1,
             aggro_range_row: 8,
             aggro_range_col: 8,
             leash_range: 10,
             xp_reward: 30,
             respawn_timer_secs: 60,
             attack_speed_ms: 1,
+            movement_speed: 1,
+            reward_buff: None,
+            ability: None,
         }
     }
 
     #[test]
     fn test_new_monster_initial_state() {
         let monster_def = create_test_monster_def();
-        let monster = Monster::new(1, monster_def.clone());
+        let monster = Monster::new(1, monster_def.clone(), (1, 1));
 
         assert_eq!(monster.id, 1);
         assert_eq!(monster.monster_id, "wolf_test");
@@ -188,7 +301,7 @@ mod tests {
         assert_eq!(monster.spawn_row, 1);
         assert_eq!(monster.spawn_col, 1);
         assert_eq!(monster.state, MonsterState::Idle);
-        assert!(monster.target_champion_id.is_none());
+        assert!(monster.target.is_none());
 
         // Check that last_attack_time is recent, allowing for a small delay
         assert!(monster.last_attacked.elapsed() < Duration::from_secs(1));
@@ -197,64 +310,76 @@ mod tests {
     #[test]
     fn test_take_effect_reduces_health() {
         let monster_def = create_test_monster_def();
-        let mut monster = Monster::new(1, monster_def);
+        let mut monster = Monster::new(1, monster_def, (1, 1));
 
-        monster.take_effect(vec![GameplayEffect::Damage(40)]);
+        monster.take_effect(vec![GameplayEffect::Damage(Damage::physical(
+            40,
+            DamageSource::Champion(1),
+        ))]);
 
-        assert_eq!(monster.stats.health, 60);
+        assert_eq!(monster.stats.health, 62);
         // State should NOT change, as per the new design
         assert_eq!(monster.state, MonsterState::Idle);
-        assert!(monster.target_champion_id.is_none());
+        assert!(monster.target.is_none());
     }
 
     #[test]
     fn test_attach_target_sets_aggro() {
         let monster_def = create_test_monster_def();
-        let mut monster = Monster::new(1, monster_def);
+        let mut monster = Monster::new(1, monster_def, (1, 1));
         let target_id = 25;
 
-        monster.attach_target(target_id);
+        monster.attach_target(MonsterTarget::Champion(target_id));
 
         assert_eq!(monster.state, MonsterState::Aggro);
-        assert_eq!(monster.target_champion_id, Some(target_id));
+        assert_eq!(monster.target, Some(MonsterTarget::Champion(target_id)));
     }
 
     #[test]
     fn test_take_effect_handles_death() {
         let monster_def = create_test_monster_def();
-        let mut monster = Monster::new(1, monster_def);
+        let mut monster = Monster::new(1, monster_def, (1, 1));
         let attacker_id = 42;
 
         // Set the monster to be aggressive towards a target
-        monster.attach_target(attacker_id);
+        monster.attach_target(MonsterTarget::Champion(attacker_id));
         assert_eq!(monster.state, MonsterState::Aggro);
 
         // Apply lethal damage (more than its health)
-        monster.take_effect(vec![GameplayEffect::Damage(150)]);
+        monster.take_effect(vec![GameplayEffect::Damage(Damage::physical(
+            150,
+            DamageSource::Champion(1),
+        ))]);
 
         // Verify the monster is dead
         assert_eq!(monster.stats.health, 0);
         assert_eq!(monster.state, MonsterState::Dead);
         assert!(monster.death_time.is_some(), "death_time should be set");
-        
+
         // Verify the target is cleared upon death
-        assert!(monster.target_champion_id.is_none(), "target should be cleared on death");
+        assert!(
+            monster.target.is_none(),
+            "target should be cleared on death"
+        );
     }
 
     #[test]
     fn test_can_attack_respects_cooldown() {
         let monster_def = create_test_monster_def();
-        let mut monster = Monster::new(1, monster_def);
+        let mut monster = Monster::new(1, monster_def, (1, 1));
 
         // 1. Manually expire the cooldown.
         let cooldown = monster.stats.attack_speed;
         monster.last_attacked = Instant::now() - (cooldown + Duration::from_millis(100));
 
         let attack_action = monster.can_attack();
-        assert!(attack_action.is_some(), "Should be able to attack after cooldown");
+        assert!(
+            attack_action.is_some(),
+            "Should be able to attack after cooldown"
+        );
 
         if let Some(AttackAction::Melee { damage, .. }) = attack_action {
-            assert_eq!(damage, 10); // From create_test_monster_def
+            assert_eq!(damage.amount, 10); // From create_test_monster_def
         } else {
             panic!("Expected a Melee attack action");
         }
@@ -263,7 +388,7 @@ mod tests {
     #[test]
     fn test_start_returning_calculates_path_to_spawn() {
         let monster_def = create_test_monster_def(); // Spawns at (1, 1)
-        let mut monster = Monster::new(1, monster_def);
+        let mut monster = Monster::new(1, monster_def, (1, 1));
         let board = Board::new(20, 20); // A clear board for pathfinding
         let target_id = 25;
 
@@ -272,19 +397,22 @@ mod tests {
         monster.col = 10;
 
         // Make the monster aggressive first
-        monster.attach_target(target_id);
+        monster.attach_target(MonsterTarget::Champion(target_id));
         assert_eq!(monster.state, MonsterState::Aggro);
         assert!(monster.path.is_none());
 
         // Tell the monster to return, providing the board context
-        monster.start_returning(&board);
+        monster.start_returning(&board, &mut Pathfinder::new(10_000));
 
         // Verify state change and target clearing
         assert_eq!(monster.state, MonsterState::Returning);
-        assert!(monster.target_champion_id.is_none(), "Target should be cleared");
+        assert!(monster.target.is_none(), "Target should be cleared");
 
         // Verify a path has been calculated
-        assert!(monster.path.is_some(), "Path should be calculated on return");
+        assert!(
+            monster.path.is_some(),
+            "Path should be calculated on return"
+        );
         let path = monster.path.as_ref().unwrap();
         assert!(!path.is_empty(), "Path should not be empty");
 
@@ -296,13 +424,16 @@ mod tests {
     #[test]
     fn test_reset_monster_restores_state_and_health() {
         let monster_def = create_test_monster_def();
-        let mut monster = Monster::new(1, monster_def);
+        let mut monster = Monster::new(1, monster_def, (1, 1));
         let board = Board::new(20, 20);
 
         // Damage the monster and make it return
-        monster.take_effect(vec![GameplayEffect::Damage(50)]);
-        monster.start_returning(&board);
-        assert_eq!(monster.stats.health, 50);
+        monster.take_effect(vec![GameplayEffect::Damage(Damage::physical(
+            50,
+            DamageSource::Champion(1),
+        ))]);
+        monster.start_returning(&board, &mut Pathfinder::new(10_000));
+        assert_eq!(monster.stats.health, 52);
         assert_eq!(monster.state, MonsterState::Returning);
         assert!(monster.path.is_some());
 
@@ -312,26 +443,9 @@ mod tests {
         // Verify it's back to a pristine Idle state
         assert_eq!(monster.state, MonsterState::Idle);
         assert!(monster.path.is_none(), "Path should be cleared on reset");
-        assert_eq!(monster.stats.health, monster.stats.max_health, "Health should be fully restored");
-    }
-
-    #[test]
-    fn test_can_respawn_respects_timer() {
-        let monster_def = create_test_monster_def();
-        let mut monster = Monster::new(1, monster_def);
-
-        // Kill the monster
-        monster.state = MonsterState::Dead;
-        monster.death_time = Some(Instant::now());
-
-        // Immediately after death, it should not be able to respawn
-        assert!(!monster.can_respawn(), "Should not respawn immediately");
-
-        // Manually set the death time to be far in the past
-        let respawn_duration = monster.respawn_timer;
-        monster.death_time = Some(Instant::now() - respawn_duration - Duration::from_secs(1));
-
-        // Now it should be able to respawn
-        assert!(monster.can_respawn(), "Should be able to respawn after timer expires");
+        assert_eq!(
+            monster.stats.health, monster.stats.max_health,
+            "Health should be fully restored"
+        );
     }
 }
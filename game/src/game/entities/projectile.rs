@@ -2,16 +2,19 @@ use crate::game::{
     algorithms::bresenham::Bresenham,
     animation::{AnimationCommand, AnimationTrait},
     buffs::Buff,
-    cell::{CellAnimation, Team},
+    cell::{CellAnimation, EntityId, Team},
 };
 
-use super::Target;
+use super::{
+    Target,
+    damage::{Damage, DamageSource},
+};
 
 #[derive(Debug)]
 /// Represents the various effects a projectile can apply upon impact.
 pub enum GameplayEffect {
-    /// Applies a specified amount of damage to the target.
-    Damage(u16),
+    /// Applies a hit, mitigated by the armor or magic resist of the target.
+    Damage(Damage),
     /// Applies a specific buff/debuff to the target
     Buff(Box<dyn Buff>),
 }
@@ -35,6 +38,28 @@ impl Clone for GameplayEffect {
     }
 }
 
+/// Unit behind the first hit of the effects, `None` for buffs alone
+pub fn damage_source(effects: &[GameplayEffect]) -> Option<DamageSource> {
+    effects.iter().find_map(|effect| match effect {
+        GameplayEffect::Damage(damage) => Some(damage.source),
+        GameplayEffect::Buff(_) => None,
+    })
+}
+
+/// Splits the effects into the hits and the buffs, buffs are kept by the
+/// registry
+pub fn split_buffs(effects: Vec<GameplayEffect>) -> (Vec<GameplayEffect>, Vec<Box<dyn Buff>>) {
+    let mut hits = Vec::new();
+    let mut buffs = Vec::new();
+    for effect in effects {
+        match effect {
+            GameplayEffect::Damage(_) => hits.push(effect),
+            GameplayEffect::Buff(buff) => buffs.push(buff),
+        }
+    }
+    (hits, buffs)
+}
+
 #[derive(Debug, Clone)]
 pub enum PathingLogic {
     Straight {
@@ -48,9 +73,8 @@ pub enum PathingLogic {
 
 #[derive(Debug)]
 pub struct Projectile {
-    pub id: u64,
     pub team_id: Team,
-    pub owner_id: u64,
+    pub owner_id: EntityId,
     // Path and Movement
     pub current_position: (u16, u16),
     pub pathing: PathingLogic,
@@ -65,8 +89,7 @@ pub struct Projectile {
 
 impl Projectile {
     pub fn from_skillshot(
-        id: u64,
-        owner_id: u64,
+        owner_id: EntityId,
         team_id: Team,
         start_pos: (u16, u16),
         end_pos: (u16, u16),
@@ -80,7 +103,6 @@ impl Projectile {
             current_index: 0,
         };
         Projectile {
-            id,
             owner_id,
             team_id,
             current_position: start_pos,
@@ -93,8 +115,7 @@ impl Projectile {
     }
 
     pub fn from_homing_shot(
-        id: u64,
-        owner_id: u64,
+        owner_id: EntityId,
         team_id: Team,
         start_pos: (u16, u16),
         target_id: Target,
@@ -104,7 +125,6 @@ impl Projectile {
     ) -> Self {
         let pathing = PathingLogic::LockOn { target_id };
         Projectile {
-            id,
             owner_id,
             team_id,
             current_position: start_pos,
@@ -162,15 +182,7 @@ impl AnimationTrait for Projectile {
     }
 
     fn get_owner_id(&self) -> usize {
-        self.owner_id as usize
-    }
-
-    fn attach_target(&mut self, _target_id: crate::game::PlayerId) {
-        // This is not needed for projectiles as their target is determined on creation
-    }
-
-    fn get_animation_type(&self) -> crate::game::cell::CellAnimation {
-        self.visual_cell_type.clone()
+        self.owner_id
     }
 
     fn get_last_drawn_pos(&self) -> Option<(u16, u16)> {
@@ -184,6 +196,7 @@ mod tests {
     use crate::game::animation::AnimationTrait; // For trait methods
     use crate::game::cell::CellAnimation;
     use crate::game::entities::Target;
+    use crate::game::entities::damage::{Damage, DamageSource};
 
     // Test `from_skillshot` constructor
     #[test]
@@ -191,17 +204,18 @@ mod tests {
         let start_pos = (10, 10);
         let end_pos = (15, 10);
         let projectile = Projectile::from_skillshot(
-            1,
             101,
             Team::Blue,
             start_pos,
             end_pos,
             1,
-            vec![GameplayEffect::Damage(50)],
+            vec![GameplayEffect::Damage(Damage::physical(
+                50,
+                DamageSource::Champion(1),
+            ))],
             CellAnimation::Projectile,
         );
 
-        assert_eq!(projectile.id, 1);
         assert_eq!(projectile.owner_id, 101);
         assert_eq!(projectile.team_id, Team::Blue);
         assert_eq!(projectile.current_position, start_pos);
@@ -225,17 +239,18 @@ mod tests {
         let start_pos = (5, 5);
         let target = Target::Champion(202);
         let projectile = Projectile::from_homing_shot(
-            2,
             102,
             Team::Red,
             start_pos,
             target.clone(),
             2,
-            vec![GameplayEffect::Damage(30)],
+            vec![GameplayEffect::Damage(Damage::physical(
+                30,
+                DamageSource::Champion(1),
+            ))],
             CellAnimation::Projectile,
         );
 
-        assert_eq!(projectile.id, 2);
         assert_eq!(projectile.owner_id, 102);
         assert_eq!(projectile.team_id, Team::Red);
         assert_eq!(projectile.current_position, start_pos);
@@ -254,13 +269,15 @@ mod tests {
         let start_pos = (0, 0);
         let end_pos = (2, 0); // Simple horizontal path
         let mut projectile = Projectile::from_skillshot(
-            3,
             103,
             Team::Blue,
             start_pos,
             end_pos,
             1, // speed = 1 tick per cell
-            vec![GameplayEffect::Damage(10)],
+            vec![GameplayEffect::Damage(Damage::physical(
+                10,
+                DamageSource::Champion(1),
+            ))],
             CellAnimation::Projectile,
         );
 
@@ -299,13 +316,15 @@ mod tests {
         let start_pos = (10, 10);
         let target = Target::Champion(202);
         let mut projectile = Projectile::from_homing_shot(
-            4,
             104,
             Team::Red,
             start_pos,
             target,
             1, // speed = 1 tick per cell
-            vec![GameplayEffect::Damage(10)],
+            vec![GameplayEffect::Damage(Damage::physical(
+                10,
+                DamageSource::Champion(1),
+            ))],
             CellAnimation::Projectile,
         );
 
@@ -356,13 +375,15 @@ mod tests {
         let start_pos = (0, 0);
         let end_pos = (1, 0);
         let mut projectile = Projectile::from_skillshot(
-            5,
             105,
             Team::Blue,
             start_pos,
             end_pos,
             2, // speed = 2 ticks per cell
-            vec![GameplayEffect::Damage(10)],
+            vec![GameplayEffect::Damage(Damage::physical(
+                10,
+                DamageSource::Champion(1),
+            ))],
             CellAnimation::Projectile,
         );
 
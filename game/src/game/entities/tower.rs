@@ -1,34 +1,45 @@
 use std::time::{Duration, Instant};
 
-use rand::seq::IndexedRandom;
-
-use crate::errors::GameError;
 use crate::game::BaseTerrain;
 use crate::game::board::Board;
-use crate::game::cell::{Cell, CellAnimation, CellContent, Team, TowerId};
-use crate::game::entities::reduced_damage;
+use crate::game::cell::{Cell, CellAnimation, CellContent, EntityId, PlayerId, Team, TowerId};
+use crate::game::entities::damage::DamageSource;
+use crate::game::registry::Ai;
 
+use super::minion::Lane;
 use super::projectile::GameplayEffect;
-use super::{AttackAction, Fighter, Stats};
+use super::{AttackAction, Fighter, Stats, Target, Unit};
 
 #[derive(Debug)]
 pub struct Tower {
     pub tower_id: TowerId,
     pub team_id: Team,
+    pub lane: Lane,
+    pub tier: TowerTier,
+    // Invulnerable while a tower of a lower tier stands in the lane
+    protected: bool,
     stats: Stats,
     tower_stats: TowerStats,
     destroyed: bool,
     last_attacked: Instant,
+    // Kept until it dies or leaves the range
+    target: Option<Target>,
+    // Enemy champion who hit an allied champion under the tower
+    help_call: Option<PlayerId>,
+    // Consecutive hits on the targeted champion
+    champion_hits: u8,
     pub row: u16,
     pub col: u16,
 }
 
-use crate::config::TowerStats;
+use crate::config::{TowerStats, TowerTier};
 
 impl Tower {
     pub fn new(
         tower_id: TowerId,
         team_id: Team,
+        lane: Lane,
+        tier: TowerTier,
         row: u16,
         col: u16,
         tower_stats: TowerStats,
@@ -36,6 +47,9 @@ impl Tower {
         Tower {
             tower_id,
             team_id,
+            lane,
+            tier,
+            protected: false,
             stats: Stats {
                 attack_damage: tower_stats.attack_damage,
                 attack_speed: Duration::from_secs(tower_stats.attack_speed_secs),
@@ -44,42 +58,152 @@ impl Tower {
                 mana: 0,
                 max_mana: 0,
                 armor: tower_stats.armor,
+                magic_resist: tower_stats.magic_resist,
+                // Only champions get penetration and critical strikes
+                armor_penetration_flat: 0,
+                armor_penetration_percent: 0.,
+                crit_chance: 0.,
+                crit_multiplier: 1.,
+                // Towers do not move
+                movement_speed: 0,
             },
             tower_stats,
             destroyed: false,
             last_attacked: Instant::now(),
+            target: None,
+            help_call: None,
+            champion_hits: 0,
             row,
             col,
         }
     }
 
     pub fn place_tower(&self, board: &mut Board) {
-        board.place_cell(
-            CellContent::Tower(self.tower_id, self.team_id),
-            self.row as usize,
-            self.col as usize,
-        );
-        board.place_cell(
-            CellContent::Tower(self.tower_id, self.team_id),
-            self.row as usize - 1,
-            self.col as usize,
-        );
-        board.place_cell(
-            CellContent::Tower(self.tower_id, self.team_id),
-            self.row as usize,
-            self.col as usize + 1,
-        );
-        board.place_cell(
-            CellContent::Tower(self.tower_id, self.team_id),
-            self.row as usize - 1,
-            self.col as usize + 1,
-        );
+        for (row, col) in self.footprint() {
+            board.place_cell(
+                CellContent::Tower(self.tower_id, self.team_id),
+                row as usize,
+                col as usize,
+            );
+        }
+    }
+
+    /// Cells covered by the tower
+    pub fn footprint(&self) -> [(u16, u16); 4] {
+        Self::footprint_at((self.row, self.col))
+    }
+
+    /// Cells covered by a tower anchored at `position`, the anchor is its
+    /// bottom left cell
+    pub fn footprint_at(position: (u16, u16)) -> [(u16, u16); 4] {
+        let (row, col) = position;
+        [
+            (row, col),
+            (row - 1, col),
+            (row, col + 1),
+            (row - 1, col + 1),
+        ]
+    }
+
+    pub fn health(&self) -> u16 {
+        self.stats.health
     }
 
     pub fn is_destroyed(&self) -> bool {
         self.destroyed
     }
 
+    pub fn is_protected(&self) -> bool {
+        self.protected
+    }
+
+    pub fn set_protected(&mut self, protected: bool) {
+        self.protected = protected;
+    }
+
+    /// Whether the position is inside the attack range of the tower
+    pub fn in_range(&self, position: (u16, u16)) -> bool {
+        self.row.abs_diff(position.0) <= self.tower_stats.attack_range_row / 2
+            && self.col.abs_diff(position.1) <= self.tower_stats.attack_range_col / 2
+    }
+
+    /// An enemy champion hit an allied champion in range, the tower turns on it
+    pub fn call_for_help(&mut self, attacker: PlayerId) {
+        self.help_call = Some(attacker);
+    }
+
+    /// Chooses the target of the tower for this tick. A champion who hit an
+    /// allied champion comes first, then the current target while it stays in
+    /// range, then the closest minion, then the closest champion
+    pub fn acquire_target(&mut self, board: &Board) -> Option<Target> {
+        let help_call = self.help_call.take().filter(|attacker| {
+            self.closest_in_range(
+                board,
+                |content| matches!(content, CellContent::Champion(id, _) if id == attacker),
+            )
+            .is_some()
+        });
+        let kept = self.target.clone().filter(|target| {
+            self.closest_in_range(board, |content| target_of(content).as_ref() == Some(target))
+                .is_some()
+        });
+        let target = match (help_call, kept) {
+            (Some(attacker), _) => Some(Target::Champion(attacker)),
+            (None, Some(target)) => Some(target),
+            (None, None) => self
+                .get_potential_target(board)
+                .and_then(|cell| cell.content.as_ref())
+                .and_then(target_of),
+        };
+        if target != self.target {
+            self.champion_hits = 0;
+        }
+        self.target = target.clone();
+        target
+    }
+
+    /// Damage of the next hit on the target. Each consecutive hit on the same
+    /// champion deals more, up to `max_damage_ramp_hits`
+    pub fn hit_damage(&mut self, damage: u16) -> u16 {
+        if !matches!(self.target, Some(Target::Champion(_))) {
+            return damage;
+        }
+        let ramp = self.champion_hits as u32 * self.tower_stats.damage_ramp_percent as u32;
+        self.champion_hits = (self.champion_hits + 1).min(self.tower_stats.max_damage_ramp_hits);
+        (damage as u32 * (100 + ramp) / 100).min(u16::MAX as u32) as u16
+    }
+
+    // Closest cell of the range whose content matches
+    fn closest_in_range<'a>(
+        &self,
+        board: &'a Board,
+        matches: impl Fn(&CellContent) -> bool,
+    ) -> Option<&'a Cell> {
+        let (row_range, col_range) = (
+            self.tower_stats.attack_range_row,
+            self.tower_stats.attack_range_col,
+        );
+        let target_area = board.center_view(self.row, self.col, row_range, col_range);
+        let center_row = target_area.len() / 2;
+        let center_col = target_area[0].len() / 2;
+
+        target_area
+            .iter()
+            .enumerate()
+            .flat_map(|(row_index, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(move |(col_index, cell)| (row_index, col_index, cell))
+            })
+            .filter(|(_, _, cell)| cell.content.as_ref().is_some_and(&matches))
+            .min_by(|(r1, c1, _), (r2, c2, _)| {
+                let dist1 = r1.abs_diff(center_row) + c1.abs_diff(center_col);
+                let dist2 = r2.abs_diff(center_row) + c2.abs_diff(center_col);
+                dist1.cmp(&dist2)
+            })
+            .map(|(_, _, &cell)| cell)
+    }
+
     pub fn destroy_tower(&self, board: &mut Board) {
         // Clear cell
         board.clear_cell(self.row as usize, self.col as usize);
@@ -87,32 +211,51 @@ impl Tower {
         board.clear_cell(self.row as usize, self.col as usize + 1);
         board.clear_cell(self.row as usize - 1, self.col as usize + 1);
 
-        board.change_base(
-            BaseTerrain::TowerDestroyed,
-            self.row as usize,
-            self.col as usize,
-        );
-        board.change_base(
-            BaseTerrain::TowerDestroyed,
-            self.row as usize,
-            self.col as usize + 1,
-        );
+        let remains = match self.tier {
+            TowerTier::Outer => BaseTerrain::TowerDestroyed,
+            TowerTier::Inner | TowerTier::Inhibitor => BaseTerrain::InnerTowerDestroyed,
+        };
+        board.change_base(remains, self.row as usize, self.col as usize);
+        board.change_base(remains, self.row as usize, self.col as usize + 1);
+    }
+}
+
+impl Unit for Tower {
+    fn id(&self) -> EntityId {
+        self.tower_id
+    }
+
+    fn team(&self) -> Option<Team> {
+        Some(self.team_id)
+    }
+
+    fn position(&self) -> (u16, u16) {
+        (self.row, self.col)
+    }
+
+    fn stats(&self) -> &Stats {
+        &self.stats
+    }
+
+    fn ai(&self) -> Ai {
+        Ai::Turret {
+            target: self.target.clone(),
+        }
     }
 }
 
 impl Fighter for Tower {
     fn take_effect(&mut self, effects: Vec<GameplayEffect>) {
         for effect in effects.into_iter() {
-            match effect {
-                GameplayEffect::Damage(damage) => {
-                    let reduced_damage = reduced_damage(damage, self.stats.armor);
-                    self.stats.health = self.stats.health.saturating_sub(reduced_damage as u16);
-                    if self.stats.health == 0 {
-                        self.destroyed = true;
-                    }
+            // Tower cannot be affected by buff or debuff
+            if let GameplayEffect::Damage(damage) = effect
+                && !self.protected
+            {
+                let damage_taken = self.stats.damage_taken(&damage);
+                self.stats.health = self.stats.health.saturating_sub(damage_taken);
+                if self.stats.health == 0 {
+                    self.destroyed = true;
                 }
-                // Tower cannot be affected by buff or debuff
-                _ => {}
             }
         }
     }
@@ -121,7 +264,7 @@ impl Fighter for Tower {
         if self.last_attacked + self.stats.attack_speed < Instant::now() {
             self.last_attacked = Instant::now();
             Some(AttackAction::Projectile {
-                damage: self.stats.attack_damage,
+                damage: self.stats.attack(DamageSource::Tower(self.tower_id)),
                 speed: 1,
                 visual: CellAnimation::TowerHit,
             })
@@ -131,54 +274,24 @@ impl Fighter for Tower {
     }
 
     fn get_potential_target<'a>(&self, board: &'a Board) -> Option<&'a Cell> {
-        let (row_range, col_range) = (
-            self.tower_stats.attack_range_row,
-            self.tower_stats.attack_range_col,
-        );
-        let target_area = board.center_view(self.row, self.col, row_range, col_range);
-        let center_row = target_area.len() / 2;
-        let center_col = target_area[0].len() / 2;
-
-        target_area
-            .iter()
-            .enumerate()
-            .flat_map(|(row_index, row)| {
-                row.iter()
-                    .enumerate()
-                    .map(move |(col_index, cell)| (row_index, col_index, cell))
-            })
-            .filter_map(|(row, col, cell)| {
-                if let Some(content) = &cell.content {
-                    match content {
-                        CellContent::Champion(_, team_id) | CellContent::Minion(_, team_id) => {
-                            if *team_id != self.team_id {
-                                Some((row, col, cell))
-                            } else {
-                                None
-                            }
-                        }
-                        _ => None,
-                    }
-                } else {
-                    None
-                }
+        // Minions first, so champions can fight under an enemy tower behind their wave
+        self.closest_in_range(board, |content| {
+            matches!(content, CellContent::Minion(_, team_id) if *team_id != self.team_id)
+        })
+        .or_else(|| {
+            self.closest_in_range(board, |content| {
+                matches!(content, CellContent::Champion(_, team_id) if *team_id != self.team_id)
             })
-            .min_by(|(r1, c1, _), (r2, c2, _)| {
-                let dist1 = r1.abs_diff(center_row) + c1.abs_diff(center_col);
-                let dist2 = r2.abs_diff(center_row) + c2.abs_diff(center_col);
-                dist1.cmp(&dist2)
-            })
-            .map(|(_, _, &cell)| cell)
+        })
     }
 }
 
-pub fn generate_tower_id() -> Result<TowerId, GameError> {
-    let mut rng = rand::rng();
-    let nums: Vec<usize> = (1..99999).collect();
-    if let Some(id) = nums.choose(&mut rng) {
-        Ok(*id)
-    } else {
-        Err(GameError::GenerateIdError)
+// Towers only shoot at minions and champions
+fn target_of(content: &CellContent) -> Option<Target> {
+    match content {
+        CellContent::Champion(id, _) => Some(Target::Champion(*id)),
+        CellContent::Minion(id, _) => Some(Target::Minion(*id)),
+        _ => None,
     }
 }
 
@@ -186,6 +299,7 @@ pub fn generate_tower_id() -> Result<TowerId, GameError> {
 mod tests {
     use super::*;
     use crate::config::TowerStats;
+    use crate::game::entities::damage::{Damage, DamageSource};
     use crate::game::{BaseTerrain, Board, cell::CellContent};
 
     // Helper function to create a dummy board
@@ -199,8 +313,11 @@ mod tests {
             attack_speed_secs: 3,
             health: 400,
             armor: 8,
+            magic_resist: 0,
             attack_range_row: 7,
             attack_range_col: 9,
+            damage_ramp_percent: 40,
+            max_damage_ramp_hits: 3,
         }
     }
 
@@ -211,7 +328,15 @@ mod tests {
         let row = 10;
         let col = 20;
         let tower_stats = create_default_tower_stats();
-        let tower = Tower::new(tower_id, team_id, row, col, tower_stats);
+        let tower = Tower::new(
+            tower_id,
+            team_id,
+            Lane::Mid,
+            TowerTier::Outer,
+            row,
+            col,
+            tower_stats,
+        );
 
         assert_eq!(tower.tower_id, tower_id);
         assert_eq!(tower.team_id, team_id);
@@ -231,10 +356,26 @@ mod tests {
     #[test]
     fn test_is_destroyed() {
         let tower_stats = create_default_tower_stats();
-        let tower = Tower::new(1, Team::Red, 10, 20, tower_stats);
+        let tower = Tower::new(
+            1,
+            Team::Red,
+            Lane::Mid,
+            TowerTier::Outer,
+            10,
+            20,
+            tower_stats,
+        );
         assert!(!tower.is_destroyed(), "New tower should not be destroyed");
 
-        let mut destroyed_tower = Tower::new(2, Team::Red, 10, 20, create_default_tower_stats());
+        let mut destroyed_tower = Tower::new(
+            2,
+            Team::Red,
+            Lane::Mid,
+            TowerTier::Outer,
+            10,
+            20,
+            create_default_tower_stats(),
+        );
         destroyed_tower.destroyed = true;
         assert!(
             destroyed_tower.is_destroyed(),
@@ -245,15 +386,22 @@ mod tests {
     #[test]
     fn test_take_damage() {
         let tower_stats = create_default_tower_stats();
-        let mut tower = Tower::new(1, Team::Red, 10, 20, tower_stats);
+        let mut tower = Tower::new(
+            1,
+            Team::Red,
+            Lane::Mid,
+            TowerTier::Outer,
+            10,
+            20,
+            tower_stats,
+        );
         let initial_health = tower.stats.health;
-        let damage = 50;
-        let armor = tower.stats.armor as u16;
+        let damage = Damage::physical(50, DamageSource::Champion(1));
 
         tower.take_effect(vec![GameplayEffect::Damage(damage)]);
 
         // Calculate expected health after damage reduction by armor
-        let reduced_damage = reduced_damage(damage, armor);
+        let reduced_damage = damage.mitigated(tower.stats.armor, tower.stats.magic_resist);
         let expected_health = initial_health.saturating_sub(reduced_damage);
         assert_eq!(
             tower.stats.health, expected_health,
@@ -265,10 +413,21 @@ mod tests {
         );
 
         // Test taking enough damage to be destroyed
-        let mut tower_to_destroy = Tower::new(2, Team::Red, 10, 20, create_default_tower_stats());
+        let mut tower_to_destroy = Tower::new(
+            2,
+            Team::Red,
+            Lane::Mid,
+            TowerTier::Outer,
+            10,
+            20,
+            create_default_tower_stats(),
+        );
         let lethal_damage = 500; // Damage exceeding health + armor
 
-        tower_to_destroy.take_effect(vec![GameplayEffect::Damage(lethal_damage)]);
+        tower_to_destroy.take_effect(vec![GameplayEffect::Damage(Damage::physical(
+            lethal_damage,
+            DamageSource::Champion(1),
+        ))]);
 
         assert_eq!(
             tower_to_destroy.stats.health, 0,
@@ -280,19 +439,58 @@ mod tests {
         );
 
         // Test taking damage when already at 0 health (should not go below 0)
-        let mut tower_already_destroyed =
-            Tower::new(3, Team::Red, 10, 20, create_default_tower_stats());
+        let mut tower_already_destroyed = Tower::new(
+            3,
+            Team::Red,
+            Lane::Mid,
+            TowerTier::Outer,
+            10,
+            20,
+            create_default_tower_stats(),
+        );
         tower_already_destroyed.stats.health = 0;
         tower_already_destroyed.destroyed = true;
         let additional_damage = 10;
 
-        tower_already_destroyed.take_effect(vec![GameplayEffect::Damage(additional_damage)]);
+        tower_already_destroyed.take_effect(vec![GameplayEffect::Damage(Damage::physical(
+            additional_damage,
+            DamageSource::Champion(1),
+        ))]);
         assert_eq!(
             tower_already_destroyed.stats.health, 0,
             "Health should remain at 0 if already destroyed"
         );
     }
 
+    #[test]
+    fn test_protected_tower_ignores_damage() {
+        let mut tower = Tower::new(
+            1,
+            Team::Red,
+            Lane::Mid,
+            TowerTier::Inner,
+            10,
+            20,
+            create_default_tower_stats(),
+        );
+        let initial_health = tower.stats.health;
+
+        tower.set_protected(true);
+        tower.take_effect(vec![GameplayEffect::Damage(Damage::physical(
+            500,
+            DamageSource::Champion(1),
+        ))]);
+        assert_eq!(tower.stats.health, initial_health);
+        assert!(!tower.is_destroyed());
+
+        tower.set_protected(false);
+        tower.take_effect(vec![GameplayEffect::Damage(Damage::physical(
+            50,
+            DamageSource::Champion(1),
+        ))]);
+        assert!(tower.stats.health < initial_health);
+    }
+
     #[test]
     fn test_place_tower() {
         // Tower::place_tower places content in a 2x2 area starting from (row - 1, col)
@@ -303,7 +501,15 @@ mod tests {
         let col = 100; // Center col for placing
 
         let tower_stats = create_default_tower_stats();
-        let tower = Tower::new(tower_id, team_id, row, col, tower_stats);
+        let tower = Tower::new(
+            tower_id,
+            team_id,
+            Lane::Mid,
+            TowerTier::Outer,
+            row,
+            col,
+            tower_stats,
+        );
         let tower_content = CellContent::Tower(tower_id, team_id);
 
         tower.place_tower(&mut board);
@@ -320,7 +526,7 @@ mod tests {
         for (r, c) in &placed_cells {
             let cell = board
                 .get_cell(*r as usize, *c as usize)
-                .expect(&format!("Cell at ({}, {}) should exist", r, c));
+                .unwrap_or_else(|| panic!("Cell at ({}, {}) should exist", r, c));
             assert_eq!(
                 cell.content,
                 Some(tower_content.clone()),
@@ -359,10 +565,9 @@ mod tests {
         for (r, c) in &outside_cells {
             // Check boundaries before getting cell
             if *r < board.rows as u16 && *c < board.cols as u16 {
-                let cell = board.get_cell(*r as usize, *c as usize).expect(&format!(
-                    "Cell at ({}, {}) should exist for outside check",
-                    r, c
-                ));
+                let cell = board.get_cell(*r as usize, *c as usize).unwrap_or_else(|| {
+                    panic!("Cell at ({}, {}) should exist for outside check", r, c)
+                });
                 assert!(
                     cell.content.is_none() || cell.content != Some(tower_content.clone()),
                     "Cell at ({}, {}) should not have tower content",
@@ -383,7 +588,15 @@ mod tests {
         let col = 100; // Center col for placing
 
         let tower_stats = create_default_tower_stats();
-        let tower = Tower::new(tower_id, team_id, row, col, tower_stats);
+        let tower = Tower::new(
+            tower_id,
+            team_id,
+            Lane::Mid,
+            TowerTier::Outer,
+            row,
+            col,
+            tower_stats,
+        );
         let tower_content = CellContent::Tower(tower_id, team_id);
 
         // First, place the tower
@@ -397,10 +610,9 @@ mod tests {
             (row, col + 1),
         ];
         for (r, c) in &placed_cells {
-            let cell = board.get_cell(*r as usize, *c as usize).expect(&format!(
-                "Cell at ({}, {}) should exist before destroying",
-                r, c
-            ));
+            let cell = board
+                .get_cell(*r as usize, *c as usize)
+                .unwrap_or_else(|| panic!("Cell at ({}, {}) should exist before destroying", r, c));
             assert_eq!(
                 cell.content,
                 Some(tower_content.clone()),
@@ -424,10 +636,12 @@ mod tests {
             (row, col + 1),
         ];
         for (r, c) in &cleared_content_cells {
-            let cell = board.get_cell(*r as usize, *c as usize).expect(&format!(
-                "Cell at ({}, {}) should exist after destroying for content check",
-                r, c
-            ));
+            let cell = board.get_cell(*r as usize, *c as usize).unwrap_or_else(|| {
+                panic!(
+                    "Cell at ({}, {}) should exist after destroying for content check",
+                    r, c
+                )
+            });
             assert!(
                 cell.content.is_none(),
                 "Cell at ({}, {}) should have no content after destroying",
@@ -438,10 +652,12 @@ mod tests {
 
         let destroyed_base_cells = [(row, col), (row, col + 1)];
         for (r, c) in &destroyed_base_cells {
-            let cell = board.get_cell(*r as usize, *c as usize).expect(&format!(
-                "Cell at ({}, {}) should exist after destroying for base check",
-                r, c
-            ));
+            let cell = board.get_cell(*r as usize, *c as usize).unwrap_or_else(|| {
+                panic!(
+                    "Cell at ({}, {}) should exist after destroying for base check",
+                    r, c
+                )
+            });
             assert_eq!(
                 cell.base,
                 BaseTerrain::TowerDestroyed,
@@ -454,10 +670,12 @@ mod tests {
         // Verify the other two cells in the 2x2 area still have their original base (Floor)
         let original_base_cells = [(row - 1, col), (row - 1, col + 1)];
         for (r, c) in &original_base_cells {
-            let cell = board.get_cell(*r as usize, *c as usize).expect(&format!(
-                "Cell at ({}, {}) should exist after destroying for original base check",
-                r, c
-            ));
+            let cell = board.get_cell(*r as usize, *c as usize).unwrap_or_else(|| {
+                panic!(
+                    "Cell at ({}, {}) should exist after destroying for original base check",
+                    r, c
+                )
+            });
             assert_eq!(
                 cell.base,
                 BaseTerrain::Floor,
@@ -477,7 +695,15 @@ mod tests {
         let tower_team = Team::Red;
 
         let tower_stats = create_default_tower_stats();
-        let tower = Tower::new(tower_id, tower_team, tower_row, tower_col, tower_stats);
+        let tower = Tower::new(
+            tower_id,
+            tower_team,
+            Lane::Mid,
+            TowerTier::Outer,
+            tower_row,
+            tower_col,
+            tower_stats,
+        );
         // We don't need to place the tower content for scan_range test itself
 
         // Case 1: No other entities on the board
@@ -548,7 +774,15 @@ mod tests {
         let tower_team = Team::Red;
 
         let tower_stats = create_default_tower_stats();
-        let tower = Tower::new(tower_id, tower_team, tower_row, tower_col, tower_stats);
+        let tower = Tower::new(
+            tower_id,
+            tower_team,
+            Lane::Mid,
+            TowerTier::Outer,
+            tower_row,
+            tower_col,
+            tower_stats,
+        );
 
         let enemy_team = Team::Blue; // Different team
 
@@ -610,7 +844,15 @@ mod tests {
         let tower_team = Team::Red;
 
         let tower_stats = create_default_tower_stats();
-        let tower = Tower::new(tower_id, tower_team, tower_row, tower_col, tower_stats);
+        let tower = Tower::new(
+            tower_id,
+            tower_team,
+            Lane::Mid,
+            TowerTier::Outer,
+            tower_row,
+            tower_col,
+            tower_stats,
+        );
 
         let enemy_team = Team::Blue; // Different team
 
@@ -622,7 +864,7 @@ mod tests {
         // Closest enemy (Manhattan distance 2 from 10,10 -> e.g., 9,11)
         let closest_enemy_row = tower_row - 1;
         let closest_enemy_col = tower_col + 1;
-        let closest_enemy_content = CellContent::Champion(1, enemy_team);
+        let closest_enemy_content = CellContent::Minion(2, enemy_team);
         board.place_cell(
             closest_enemy_content.clone(),
             closest_enemy_row as usize,
@@ -672,7 +914,15 @@ mod tests {
         let tower_team = Team::Red;
 
         let tower_stats = create_default_tower_stats();
-        let tower = Tower::new(tower_id, tower_team, tower_row, tower_col, tower_stats);
+        let tower = Tower::new(
+            tower_id,
+            tower_team,
+            Lane::Mid,
+            TowerTier::Outer,
+            tower_row,
+            tower_col,
+            tower_stats,
+        );
 
         let enemy_team = Team::Blue; // Different team
 
@@ -709,4 +959,126 @@ mod tests {
             "Tower scan_range should return None when enemies are outside the 7x9 range"
         );
     }
+
+    fn create_tower_at_center() -> Tower {
+        Tower::new(
+            1,
+            Team::Red,
+            Lane::Mid,
+            TowerTier::Outer,
+            10,
+            10,
+            create_default_tower_stats(),
+        )
+    }
+
+    #[test]
+    fn test_tower_prefers_minions_over_closer_champions() {
+        let mut board = create_dummy_board(20, 20);
+        let mut tower = create_tower_at_center();
+        board.place_cell(CellContent::Champion(1, Team::Blue), 9, 11);
+        board.place_cell(CellContent::Minion(7, Team::Blue), 12, 13);
+
+        assert_eq!(tower.acquire_target(&board), Some(Target::Minion(7)));
+
+        // Without minions the champion is targeted
+        board.clear_cell(12, 13);
+        assert_eq!(tower.acquire_target(&board), Some(Target::Champion(1)));
+    }
+
+    #[test]
+    fn test_tower_keeps_target_until_it_leaves_range() {
+        let mut board = create_dummy_board(20, 20);
+        let mut tower = create_tower_at_center();
+        board.place_cell(CellContent::Champion(1, Team::Blue), 9, 11);
+        assert_eq!(tower.acquire_target(&board), Some(Target::Champion(1)));
+
+        // A minion walks in, the tower stays on the champion
+        board.place_cell(CellContent::Minion(7, Team::Blue), 10, 9);
+        assert_eq!(tower.acquire_target(&board), Some(Target::Champion(1)));
+
+        // The champion leaves the range, the tower takes the minion
+        board.move_cell(9, 11, 9, 16);
+        assert_eq!(tower.acquire_target(&board), Some(Target::Minion(7)));
+    }
+
+    #[test]
+    fn test_tower_switches_to_champion_hitting_an_ally() {
+        let mut board = create_dummy_board(20, 20);
+        let mut tower = create_tower_at_center();
+        board.place_cell(CellContent::Minion(7, Team::Blue), 10, 9);
+        board.place_cell(CellContent::Champion(2, Team::Blue), 12, 12);
+        assert_eq!(tower.acquire_target(&board), Some(Target::Minion(7)));
+
+        tower.call_for_help(2);
+        assert_eq!(tower.acquire_target(&board), Some(Target::Champion(2)));
+        // The champion stays the target after the call
+        assert_eq!(tower.acquire_target(&board), Some(Target::Champion(2)));
+
+        // A call for a champion out of range is ignored
+        tower.call_for_help(3);
+        board.place_cell(CellContent::Champion(3, Team::Blue), 1, 1);
+        assert_eq!(tower.acquire_target(&board), Some(Target::Champion(2)));
+    }
+
+    #[test]
+    fn test_tower_damage_ramps_on_the_same_champion() {
+        let mut board = create_dummy_board(20, 20);
+        let mut tower = create_tower_at_center();
+        board.place_cell(CellContent::Champion(1, Team::Blue), 9, 11);
+        tower.acquire_target(&board);
+
+        // +40% per consecutive hit, up to 3 hits
+        let damages: Vec<u16> = (0..5).map(|_| tower.hit_damage(40)).collect();
+        assert_eq!(damages, vec![40, 56, 72, 88, 88]);
+
+        // A new target starts over, minions never get the ramp
+        board.clear_cell(9, 11);
+        board.place_cell(CellContent::Minion(7, Team::Blue), 10, 9);
+        tower.acquire_target(&board);
+        assert_eq!(tower.hit_damage(40), 40);
+        assert_eq!(tower.hit_damage(40), 40);
+
+        board.clear_cell(10, 9);
+        board.place_cell(CellContent::Champion(1, Team::Blue), 9, 11);
+        tower.acquire_target(&board);
+        assert_eq!(tower.hit_damage(40), 40);
+    }
+
+    #[test]
+    fn test_destroyed_inner_tower_leaves_its_own_terrain() {
+        let mut board = create_dummy_board(20, 20);
+        let outer = Tower::new(
+            1,
+            Team::Red,
+            Lane::Mid,
+            TowerTier::Outer,
+            5,
+            5,
+            create_default_tower_stats(),
+        );
+        let inner = Tower::new(
+            2,
+            Team::Red,
+            Lane::Mid,
+            TowerTier::Inner,
+            10,
+            10,
+            create_default_tower_stats(),
+        );
+        outer.place_tower(&mut board);
+        inner.place_tower(&mut board);
+
+        outer.destroy_tower(&mut board);
+        inner.destroy_tower(&mut board);
+
+        assert_eq!(
+            board.get_cell(5, 5).unwrap().base,
+            BaseTerrain::TowerDestroyed
+        );
+        assert_eq!(
+            board.get_cell(10, 10).unwrap().base,
+            BaseTerrain::InnerTowerDestroyed
+        );
+    }
 }
@@ -0,0 +1,322 @@
+use log::{info, trace, warn};
+use tokio::sync::{mpsc, oneshot};
+
+use super::{ClientMessage, GameManager, PlayerId};
+use crate::errors::GameError;
+use crate::packet::start_packet::StartPacket;
+
+/// Updates waiting in the channel of a player before the next ones are
+/// dropped
+pub const OUTBOUND_CHANNEL_SIZE: usize = 32;
+
+/// Inputs waiting for the tick loop before the connections have to wait
+pub const INPUT_CHANNEL_SIZE: usize = 1024;
+
+/// What a connection hands to the tick loop, the only owner of the
+/// `GameManager`
+#[derive(Debug)]
+pub enum PlayerInput {
+    /// A new connection picked its champion. The tick loop answers with the
+    /// id of the player, or why it cannot join
+    Join {
+        champion: u8,
+        spell1: u8,
+        spell2: u8,
+        outbound: mpsc::Sender<ClientMessage>,
+        /// Resolves once the writer task sent everything queued on `outbound`
+        flushed: oneshot::Receiver<()>,
+        reply: oneshot::Sender<Result<PlayerId, GameError>>,
+    },
+    Action {
+        player_id: PlayerId,
+        action: u8,
+    },
+    MoveTo {
+        player_id: PlayerId,
+        destination: (u16, u16),
+        attack_move: bool,
+    },
+    Leave {
+        player_id: PlayerId,
+    },
+}
+
+impl GameManager {
+    /// Applies an input of a connection, between two ticks
+    pub fn handle_input(&mut self, input: PlayerInput) {
+        match input {
+            PlayerInput::Join {
+                champion,
+                spell1,
+                spell2,
+                outbound,
+                flushed,
+                reply,
+            } => {
+                let player_id = self.add_player(champion, spell1, spell2);
+                match &player_id {
+                    Ok(id) => {
+                        self.client_channel.insert(*id, outbound);
+                        self.writers_flushed.insert(*id, flushed);
+                        info!(
+                            "Player {} joined as champion {} with spells {} and {}",
+                            id, champion, spell1, spell2
+                        );
+                    }
+                    Err(e) => warn!("Join rejected: {}", e),
+                }
+                let joined = player_id.is_ok();
+                if let Err(Ok(id)) = reply.send(player_id) {
+                    // The connection closed while waiting for its id
+                    self.remove_player(&id);
+                    return;
+                }
+                if joined && self.game_started {
+                    let players: Vec<PlayerId> = self.client_channel.keys().copied().collect();
+                    for id in players {
+                        self.send_to_player(id, StartPacket::new(0).serialize());
+                    }
+                }
+            }
+            PlayerInput::Action { player_id, action } => {
+                self.store_player_action(player_id, action);
+            }
+            PlayerInput::MoveTo {
+                player_id,
+                destination,
+                attack_move,
+            } => self.store_move_order(player_id, destination, attack_move),
+            PlayerInput::Leave { player_id } => self.remove_player(&player_id),
+        }
+    }
+
+    /// Hands over the channel of each player once the game is over, with the
+    /// signal of its writer task. The channels are closed once dropped
+    pub fn close_connections(
+        &mut self,
+    ) -> Vec<(PlayerId, mpsc::Sender<ClientMessage>, oneshot::Receiver<()>)> {
+        self.client_channel
+            .drain()
+            .filter_map(|(player_id, outbound)| {
+                let flushed = self.writers_flushed.remove(&player_id)?;
+                Some((player_id, outbound, flushed))
+            })
+            .collect()
+    }
+
+    /// Queues a message on the channel of the player, without waiting. A
+    /// player too slow to read its channel misses the message, which is not
+    /// counted in the bytes sent
+    pub fn send_to_player(&mut self, player_id: PlayerId, message: ClientMessage) {
+        trace!("Sending {} bytes to player {}", message.len(), player_id);
+        let Some(sender) = self.client_channel.get(&player_id) else {
+            warn!(
+                "Attempted to send message to disconnected or non-existent player {}",
+                player_id
+            );
+            return;
+        };
+        let bytes = message.len();
+        match sender.try_send(message) {
+            Ok(()) => {
+                *self
+                    .tick_metrics
+                    .bytes_per_player
+                    .entry(player_id)
+                    .or_default() += bytes;
+            }
+            Err(mpsc::error::TrySendError::Full(_)) => {
+                warn!(
+                    "Channel of player {} is full, dropping a message",
+                    player_id
+                );
+            }
+            Err(mpsc::error::TrySendError::Closed(_)) => {
+                warn!(
+                    "Channel of player {} is closed, no more messages sent",
+                    player_id
+                );
+                self.client_channel.remove(&player_id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GameConfig;
+    use crate::game::map::MapRegistry;
+
+    fn create_manager() -> GameManager {
+        let config = GameConfig::load("stats.toml", "spells.toml", "rules.toml").unwrap();
+        let map = MapRegistry::load("maps").unwrap().load_map(None).unwrap();
+        GameManager::new(config, map).unwrap()
+    }
+
+    fn join(
+        manager: &mut GameManager,
+    ) -> (Result<PlayerId, GameError>, mpsc::Receiver<ClientMessage>) {
+        let (outbound, outbound_rx) = mpsc::channel(OUTBOUND_CHANNEL_SIZE);
+        let (reply, mut reply_rx) = oneshot::channel();
+        manager.handle_input(PlayerInput::Join {
+            champion: 0,
+            spell1: 0,
+            spell2: 1,
+            outbound,
+            flushed: oneshot::channel().1,
+            reply,
+        });
+        (reply_rx.try_recv().unwrap(), outbound_rx)
+    }
+
+    #[test]
+    fn test_join_replies_and_starts_the_game() {
+        let mut manager = create_manager();
+        let (player_id, mut outbound_rx) = join(&mut manager);
+
+        let player_id = player_id.unwrap();
+        assert!(manager.game_started);
+        assert!(manager.client_channel.contains_key(&player_id));
+        assert_eq!(
+            outbound_rx.try_recv().unwrap(),
+            StartPacket::new(0).serialize()
+        );
+
+        // The game is full
+        let (rejected, _) = join(&mut manager);
+        assert_eq!(rejected, Err(GameError::GameFull));
+    }
+
+    #[test]
+    fn test_join_with_an_unknown_champion_is_rejected() {
+        let mut manager = create_manager();
+        let (outbound, _outbound_rx) = mpsc::channel(OUTBOUND_CHANNEL_SIZE);
+        let (reply, mut reply_rx) = oneshot::channel();
+        manager.handle_input(PlayerInput::Join {
+            champion: 42,
+            spell1: 0,
+            spell2: 1,
+            outbound,
+            flushed: oneshot::channel().1,
+            reply,
+        });
+
+        assert_eq!(
+            reply_rx.try_recv().unwrap(),
+            Err(GameError::UnknownChampion(42))
+        );
+        assert!(manager.champions.is_empty());
+        assert!(manager.client_channel.is_empty());
+    }
+
+    #[test]
+    fn test_join_gives_the_champion_its_own_signature_spell() {
+        let mut manager = create_manager();
+        let (outbound, _outbound_rx) = mpsc::channel(OUTBOUND_CHANNEL_SIZE);
+        let (reply, mut reply_rx) = oneshot::channel();
+        let kit = manager.config.champion_kit(1).unwrap().clone();
+        manager.handle_input(PlayerInput::Join {
+            champion: kit.id,
+            spell1: 0,
+            // The signature spell of the marksman cannot be picked
+            spell2: kit.signature_spell,
+            outbound,
+            flushed: oneshot::channel().1,
+            reply,
+        });
+        let player_id = reply_rx.try_recv().unwrap().unwrap();
+
+        let spells = &manager.champions[&player_id].spells;
+        assert_eq!(spells[&0].id(), 0);
+        assert!(!spells.contains_key(&1));
+        assert_eq!(spells[&2].id(), kit.signature_spell);
+        assert!(manager.config.spells[&kit.signature_spell].signature);
+    }
+
+    #[test]
+    fn test_inputs_are_stored_for_the_next_tick() {
+        let mut manager = create_manager();
+        let (player_id, _outbound_rx) = join(&mut manager);
+        let player_id = player_id.unwrap();
+
+        manager.handle_input(PlayerInput::Action {
+            player_id,
+            action: 16,
+        });
+        assert!(manager.scoreboard_requests.contains(&player_id));
+        manager.handle_input(PlayerInput::MoveTo {
+            player_id,
+            destination: (10, 12),
+            attack_move: true,
+        });
+        assert!(manager.player_action.contains_key(&player_id));
+
+        manager.handle_input(PlayerInput::Leave { player_id });
+        assert!(!manager.game_started);
+        assert!(!manager.player_action.contains_key(&player_id));
+        assert!(!manager.client_channel.contains_key(&player_id));
+    }
+
+    #[test]
+    fn test_send_to_player_never_waits() {
+        let mut manager = create_manager();
+        let (player_id, mut outbound_rx) = join(&mut manager);
+        let player_id = player_id.unwrap();
+
+        // The start packet is already queued, the channel fills up
+        for _ in 0..OUTBOUND_CHANNEL_SIZE {
+            manager.send_to_player(player_id, ClientMessage::from(&[1, 9][..]));
+        }
+        let mut received = 0;
+        while outbound_rx.try_recv().is_ok() {
+            received += 1;
+        }
+        assert_eq!(received, OUTBOUND_CHANNEL_SIZE);
+        // The dropped message is not counted
+        let start_bytes = StartPacket::new(0).serialize().len();
+        assert_eq!(
+            manager.take_tick_metrics().bytes_per_player[&player_id],
+            start_bytes + (OUTBOUND_CHANNEL_SIZE - 1) * 2
+        );
+        assert!(manager.tick_metrics().bytes_per_player.is_empty());
+
+        // A closed channel is forgotten
+        drop(outbound_rx);
+        manager.send_to_player(player_id, ClientMessage::from(&[1, 9][..]));
+        assert!(!manager.client_channel.contains_key(&player_id));
+    }
+    #[test]
+    fn test_close_connections_hands_over_the_channels() {
+        let mut manager = create_manager();
+        let (outbound, mut outbound_rx) = mpsc::channel(OUTBOUND_CHANNEL_SIZE);
+        let (flushed_tx, flushed) = oneshot::channel();
+        let (reply, mut reply_rx) = oneshot::channel();
+        manager.handle_input(PlayerInput::Join {
+            champion: 0,
+            spell1: 0,
+            spell2: 1,
+            outbound,
+            flushed,
+            reply,
+        });
+        let player_id = reply_rx.try_recv().unwrap().unwrap();
+
+        let mut connections = manager.close_connections();
+        assert!(manager.client_channel.is_empty());
+        assert_eq!(connections.len(), 1);
+        let (closed_id, outbound, mut flushed) = connections.remove(0);
+        assert_eq!(closed_id, player_id);
+
+        // The writer gets what was queued, then the end of the channel
+        drop(outbound);
+        assert_eq!(
+            outbound_rx.try_recv().unwrap(),
+            StartPacket::new(0).serialize()
+        );
+        assert!(outbound_rx.try_recv().is_err());
+        assert!(outbound_rx.is_closed());
+        flushed_tx.send(()).unwrap();
+        assert_eq!(flushed.try_recv(), Ok(()));
+    }
+}
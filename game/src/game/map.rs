@@ -0,0 +1,478 @@
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use strum::IntoEnumIterator;
+
+use super::Board;
+use super::cell::Team;
+use super::entities::minion::Lane;
+use crate::config::TowerTier;
+use crate::errors::MapError;
+
+/// Side of the square covered by a base, from its position
+pub const BASE_SIZE: u16 = 3;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MapData {
+    pub metadata: MapMetadata,
+    pub rows: usize,
+    pub cols: usize,
+    pub layout: Vec<Vec<String>>,
+    pub objects: MapObjects,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct MapMetadata {
+    pub id: u8,
+    pub name: String,
+    /// Number of players per team the map is designed for
+    pub team_sizes: Vec<u8>,
+}
+
+/// Named objects placed on the map, every coordinate is (row, col)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MapObjects {
+    pub bases: Vec<BaseLayout>,
+    pub towers: Vec<TowerLayout>,
+    pub lanes: Vec<LaneLayout>,
+    pub spawns: Vec<SpawnZone>,
+    pub camps: Vec<CampLayout>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BaseLayout {
+    pub team: Team,
+    pub position: (u16, u16),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TowerLayout {
+    pub team: Team,
+    pub lane: Lane,
+    pub tier: TowerTier,
+    pub position: (u16, u16),
+}
+
+/// Path followed by a team minions in a lane, minions spawn on `spawn`
+/// then walk through each waypoint in order
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LaneLayout {
+    pub team: Team,
+    pub lane: Lane,
+    pub spawn: (u16, u16),
+    pub waypoints: Vec<(u16, u16)>,
+}
+
+/// Rectangle where the champions of a team spawn and respawn
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SpawnZone {
+    pub team: Team,
+    pub position: (u16, u16),
+    pub height: u16,
+    pub width: u16,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CampLayout {
+    pub monster: String,
+    pub position: (u16, u16),
+}
+
+/// Only the metadata and the size of a map file, used to index the registry
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+pub struct MapSummary {
+    pub metadata: MapMetadata,
+    pub rows: usize,
+    pub cols: usize,
+}
+
+fn read_map_file(file_path: &Path) -> Result<String, MapError> {
+    let mut contents = String::new();
+    File::open(file_path)
+        .and_then(|mut file| file.read_to_string(&mut contents))
+        .map_err(|e| MapError::Read(file_path.display().to_string(), e.to_string()))?;
+    Ok(contents)
+}
+
+impl MapData {
+    /// Loads and validates a map file
+    pub fn from_json(file_path: &Path) -> Result<Self, MapError> {
+        let contents = read_map_file(file_path)?;
+        let map_data: MapData = serde_json::from_str(&contents)
+            .map_err(|e| MapError::Parse(file_path.display().to_string(), e.to_string()))?;
+        map_data.validate()?;
+        Ok(map_data)
+    }
+
+    pub fn validate(&self) -> Result<(), MapError> {
+        let layout_cols = self.layout.first().map_or(0, |row| row.len());
+        if self.layout.len() != self.rows || self.layout.iter().any(|row| row.len() != self.cols) {
+            return Err(MapError::InvalidSize(
+                self.layout.len(),
+                layout_cols,
+                self.rows,
+                self.cols,
+            ));
+        }
+
+        let objects = &self.objects;
+        let positions = objects
+            .bases
+            .iter()
+            .map(|base| ("Base", base.position))
+            .chain(objects.towers.iter().map(|tower| ("Tower", tower.position)))
+            .chain(objects.lanes.iter().flat_map(|lane| {
+                std::iter::once(("Lane spawn", lane.spawn))
+                    .chain(lane.waypoints.iter().map(|w| ("Lane waypoint", *w)))
+            }))
+            .chain(objects.spawns.iter().flat_map(|zone| {
+                // Both corners of the zone must be on the map
+                [
+                    ("Spawn zone", zone.position),
+                    (
+                        "Spawn zone",
+                        (
+                            zone.position.0 + zone.height.saturating_sub(1),
+                            zone.position.1 + zone.width.saturating_sub(1),
+                        ),
+                    ),
+                ]
+            }))
+            .chain(objects.camps.iter().map(|camp| ("Camp", camp.position)));
+        for (name, (row, col)) in positions {
+            if row as usize >= self.rows || col as usize >= self.cols {
+                return Err(MapError::OutOfBounds(name.to_string(), row, col));
+            }
+        }
+
+        // A base covers the square below and on the right of its position
+        for base in objects.bases.iter() {
+            let (row, col) = base.position;
+            if (row + BASE_SIZE) as usize > self.rows || (col + BASE_SIZE) as usize > self.cols {
+                return Err(MapError::OutOfBounds("Base".to_string(), row, col));
+            }
+        }
+
+        // A tower also covers the cell above it and the cells on their right
+        for tower in objects.towers.iter() {
+            let (row, col) = tower.position;
+            if row == 0 || col as usize + 1 >= self.cols {
+                return Err(MapError::OutOfBounds("Tower".to_string(), row, col));
+            }
+        }
+
+        for team in Team::iter() {
+            if objects.base(team).is_none() {
+                return Err(MapError::MissingObject("base".to_string(), team));
+            }
+            if objects.spawn_zone(team).is_none() {
+                return Err(MapError::MissingObject("spawn zone".to_string(), team));
+            }
+        }
+        Ok(())
+    }
+
+    /// Serializes the map with one layout row per line
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        let mut value = serde_json::to_value(self)?;
+        if let Some(fields) = value.as_object_mut() {
+            fields.remove("layout");
+        }
+        let header = serde_json::to_string_pretty(&value)?;
+        let rows = self
+            .layout
+            .iter()
+            .map(serde_json::to_string)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(format!(
+            "{},\n    \"layout\": [\n        {}\n    ]\n}}\n",
+            header.trim_end().trim_end_matches('}').trim_end(),
+            rows.join(",\n        ")
+        ))
+    }
+
+    pub fn supports_team_size(&self, team_size: usize) -> bool {
+        self.metadata
+            .team_sizes
+            .iter()
+            .any(|size| *size as usize == team_size)
+    }
+}
+
+/// Every map file of a directory, indexed by map id
+pub struct MapRegistry {
+    maps: Vec<(MapSummary, PathBuf)>,
+}
+
+impl MapRegistry {
+    /// Indexes every map file of `directory`, a file that cannot be read or
+    /// parsed is logged and skipped so it does not hide the other maps
+    pub fn load(directory: &str) -> Result<Self, MapError> {
+        let entries = fs::read_dir(directory)
+            .map_err(|e| MapError::Read(directory.to_string(), e.to_string()))?;
+        let mut maps: Vec<(MapSummary, PathBuf)> = Vec::new();
+        for entry in entries {
+            let path = entry
+                .map_err(|e| MapError::Read(directory.to_string(), e.to_string()))?
+                .path();
+            if path.extension().is_none_or(|extension| extension != "json") {
+                continue;
+            }
+            let summary = read_map_file(&path).and_then(|contents| {
+                serde_json::from_str::<MapSummary>(&contents)
+                    .map_err(|e| MapError::Parse(path.display().to_string(), e.to_string()))
+            });
+            let summary = match summary {
+                Ok(summary) => summary,
+                Err(e) => {
+                    warn!("Skipping map file: {}", e);
+                    continue;
+                }
+            };
+            if maps
+                .iter()
+                .any(|(other, _)| other.metadata.id == summary.metadata.id)
+            {
+                return Err(MapError::DuplicateId(summary.metadata.id));
+            }
+            maps.push((summary, path));
+        }
+        if maps.is_empty() {
+            return Err(MapError::EmptyRegistry);
+        }
+        maps.sort_by_key(|(summary, _)| summary.metadata.id);
+        Ok(MapRegistry { maps })
+    }
+
+    pub fn maps(&self) -> impl Iterator<Item = &MapSummary> {
+        self.maps.iter().map(|(summary, _)| summary)
+    }
+
+    /// Loads the map `map_id`, or the map with the lowest id when none is given
+    pub fn load_map(&self, map_id: Option<u8>) -> Result<MapData, MapError> {
+        let (summary, path) = match map_id {
+            Some(id) => self
+                .maps
+                .iter()
+                .find(|(summary, _)| summary.metadata.id == id)
+                .ok_or(MapError::NotFound(id))?,
+            None => self.maps.first().ok_or(MapError::EmptyRegistry)?,
+        };
+        let map = MapData::from_json(path)?;
+        info!(
+            "Loaded map {} ({})",
+            summary.metadata.name, summary.metadata.id
+        );
+        Ok(map)
+    }
+}
+
+impl MapObjects {
+    pub fn base(&self, team: Team) -> Option<&BaseLayout> {
+        self.bases.iter().find(|base| base.team == team)
+    }
+
+    pub fn spawn_zone(&self, team: Team) -> Option<&SpawnZone> {
+        self.spawns.iter().find(|zone| zone.team == team)
+    }
+}
+
+impl SpawnZone {
+    pub fn contains(&self, row: u16, col: u16) -> bool {
+        (self.position.0..self.position.0 + self.height).contains(&row)
+            && (self.position.1..self.position.1 + self.width).contains(&col)
+    }
+
+    /// Returns the first cell of the zone a champion can be placed on
+    pub fn free_cell(&self, board: &Board) -> Option<(u16, u16)> {
+        let (row, col) = self.position;
+        (row..row + self.height)
+            .flat_map(|r| (col..col + self.width).map(move |c| (r, c)))
+            .find(|(r, c)| {
+                board
+                    .get_cell(*r as usize, *c as usize)
+                    .is_some_and(|cell| cell.is_passable())
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::CellContent;
+
+    const MAP_JSON: &str = r#"{
+        "metadata": { "id": 7, "name": "Test", "team_sizes": [1, 2] },
+        "rows": 3,
+        "cols": 6,
+        "layout": [
+            ["floor", "floor", "wall", "floor", "floor", "floor"],
+            ["floor", "bush", "floor", "floor", "floor", "floor"],
+            ["wall", "floor", "floor", "floor", "floor", "floor"]
+        ],
+        "objects": {
+            "bases": [
+                { "team": "blue", "position": [0, 0] },
+                { "team": "red", "position": [0, 3] }
+            ],
+            "towers": [
+                { "team": "blue", "lane": "mid", "tier": "inner", "position": [1, 1] }
+            ],
+            "lanes": [
+                { "team": "blue", "lane": "mid", "spawn": [2, 1], "waypoints": [[1, 2], [0, 3]] }
+            ],
+            "spawns": [
+                { "team": "blue", "position": [1, 0], "height": 2, "width": 2 },
+                { "team": "red", "position": [0, 3], "height": 1, "width": 1 }
+            ],
+            "camps": [
+                { "monster": "wolf", "position": [1, 3] }
+            ]
+        }
+    }"#;
+
+    #[test]
+    fn test_parse_map_objects() {
+        let map: MapData = serde_json::from_str(MAP_JSON).unwrap();
+
+        assert_eq!(map.rows, 3);
+        assert_eq!(map.cols, 6);
+        assert_eq!(map.objects.base(Team::Red).unwrap().position, (0, 3));
+        assert_eq!(map.objects.towers[0].lane, Lane::Mid);
+        assert_eq!(map.objects.towers[0].tier, TowerTier::Inner);
+        assert_eq!(map.objects.lanes.len(), 1);
+
+        let lane = &map.objects.lanes[0];
+        assert_eq!(lane.team, Team::Blue);
+        assert_eq!(lane.spawn, (2, 1));
+        assert_eq!(lane.waypoints, vec![(1, 2), (0, 3)]);
+        assert_eq!(map.objects.camps[0].monster, "wolf");
+    }
+
+    #[test]
+    fn test_spawn_zone_free_cell() {
+        let map: MapData = serde_json::from_str(MAP_JSON).unwrap();
+        let mut board = Board::from_map(&map);
+        let zone = map.objects.spawn_zone(Team::Blue).unwrap();
+
+        assert_eq!(zone.free_cell(&board), Some((1, 0)));
+        assert!(zone.contains(2, 1));
+        assert!(!zone.contains(0, 0));
+
+        // Occupied and wall cells are skipped
+        board.place_cell(CellContent::Champion(1, Team::Blue), 1, 0);
+        assert_eq!(zone.free_cell(&board), Some((1, 1)));
+        board.place_cell(CellContent::Champion(2, Team::Blue), 1, 1);
+        assert_eq!(zone.free_cell(&board), Some((2, 1)));
+        board.place_cell(CellContent::Champion(3, Team::Blue), 2, 1);
+        assert_eq!(zone.free_cell(&board), None);
+    }
+
+    #[test]
+    fn test_to_json_round_trip() {
+        let map: MapData = serde_json::from_str(MAP_JSON).unwrap();
+        let json = map.to_json().unwrap();
+        let parsed: MapData = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed.metadata, map.metadata);
+        assert_eq!(parsed.layout, map.layout);
+        assert_eq!(
+            parsed.objects.lanes[0].waypoints,
+            map.objects.lanes[0].waypoints
+        );
+        assert!(json.contains("[\"floor\",\"bush\",\"floor\",\"floor\",\"floor\",\"floor\"]"));
+    }
+
+    #[test]
+    fn test_validate_map() {
+        let map: MapData = serde_json::from_str(MAP_JSON).unwrap();
+        assert_eq!(map.validate(), Ok(()));
+        assert!(map.supports_team_size(2));
+        assert!(!map.supports_team_size(5));
+
+        let mut invalid = map.clone();
+        invalid.layout.pop();
+        assert_eq!(invalid.validate(), Err(MapError::InvalidSize(2, 6, 3, 6)));
+
+        let mut invalid = map.clone();
+        invalid.objects.towers[0].position = (3, 0);
+        assert_eq!(
+            invalid.validate(),
+            Err(MapError::OutOfBounds("Tower".to_string(), 3, 0))
+        );
+
+        let mut invalid = map.clone();
+        invalid.objects.spawns[0].width = 7;
+        assert_eq!(
+            invalid.validate(),
+            Err(MapError::OutOfBounds("Spawn zone".to_string(), 2, 6))
+        );
+
+        let mut invalid = map;
+        invalid.objects.bases.pop();
+        assert_eq!(
+            invalid.validate(),
+            Err(MapError::MissingObject("base".to_string(), Team::Red))
+        );
+    }
+
+    #[test]
+    fn test_validate_base_area() {
+        let map: MapData = serde_json::from_str(MAP_JSON).unwrap();
+
+        // The position is on the map, the rows below it are not
+        let mut invalid = map.clone();
+        invalid.objects.bases[0].position = (2, 0);
+        assert_eq!(
+            invalid.validate(),
+            Err(MapError::OutOfBounds("Base".to_string(), 2, 0))
+        );
+
+        let mut invalid = map;
+        invalid.objects.bases[1].position = (0, 4);
+        assert_eq!(
+            invalid.validate(),
+            Err(MapError::OutOfBounds("Base".to_string(), 0, 4))
+        );
+    }
+
+    #[test]
+    fn test_registry_loads_map_by_id() {
+        let directory = std::env::temp_dir().join(format!("map_registry_{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(directory.join("test.json"), MAP_JSON).unwrap();
+        fs::write(directory.join("notes.txt"), "not a map").unwrap();
+        // A malformed file is skipped, the other maps are still indexed
+        fs::write(directory.join("broken.json"), "{ \"metadata\": ").unwrap();
+
+        let registry = MapRegistry::load(directory.to_str().unwrap()).unwrap();
+        let summaries: Vec<&MapSummary> = registry.maps().collect();
+        assert_eq!(summaries.len(), 1);
+        assert_eq!((summaries[0].rows, summaries[0].cols), (3, 6));
+        assert_eq!(registry.load_map(None).unwrap().metadata.id, 7);
+        assert_eq!(registry.load_map(Some(7)).unwrap().metadata.name, "Test");
+        assert_eq!(
+            registry.load_map(Some(3)).unwrap_err(),
+            MapError::NotFound(3)
+        );
+
+        // Two files cannot share an id
+        fs::write(directory.join("copy.json"), MAP_JSON).unwrap();
+        assert_eq!(
+            MapRegistry::load(directory.to_str().unwrap()).err(),
+            Some(MapError::DuplicateId(7))
+        );
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_shipped_maps_are_valid() {
+        let registry = MapRegistry::load("maps").unwrap();
+        let ids: Vec<u8> = registry.maps().map(|summary| summary.metadata.id).collect();
+        for id in ids {
+            assert!(registry.load_map(Some(id)).is_ok(), "Map {} is invalid", id);
+        }
+    }
+}
@@ -0,0 +1,613 @@
+use std::collections::HashSet;
+use strum::IntoEnumIterator;
+use thiserror::Error;
+
+use super::algorithms::flow_field::FlowField;
+use super::cell::{BaseTerrain, CellContent, Team};
+use super::entities::minion::Lane;
+use super::entities::tower::Tower;
+use super::map::{BASE_SIZE, MapData};
+use super::{Board, TowerId};
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum MapIssue {
+    #[error("Layout has {0} rows but the map declares {1}")]
+    RowCount(usize, usize),
+    #[error("Row {0} has {1} tiles but the map declares {2}")]
+    ColCount(usize, usize, usize),
+    #[error("Unknown tile \"{2}\" at ({0}, {1})")]
+    UnknownTile(usize, usize, String),
+    #[error("Invalid objects: {0}")]
+    InvalidObjects(String),
+    #[error("{0:?} {1:?} waypoint {2:?} cannot be reached")]
+    UnreachableWaypoint(Team, Lane, (u16, u16)),
+    #[error("{0:?} {1:?} tower at {2:?} cannot be reached by the enemy team")]
+    UnreachableTower(Team, Lane, (u16, u16)),
+    #[error("Camp {0} at {1:?} cannot be reached")]
+    UnreachableCamp(String, (u16, u16)),
+    #[error("{0:?} spawn zone has no walkable cell")]
+    BlockedSpawnZone(Team),
+    #[error("{0:?} base at {1:?} does not fit on walkable tiles")]
+    BaseOutsideTerrain(Team, (u16, u16)),
+    #[error("{0:?} base at {1:?} has no opening in its enclosure")]
+    ClosedBase(Team, (u16, u16)),
+    #[error("{0:?} base at {1:?} cannot be reached by the enemy team")]
+    UnreachableBase(Team, (u16, u16)),
+    #[error("Layout is not symmetric between teams")]
+    AsymmetricLayout,
+    #[error("{0} at {1:?} has no mirrored counterpart for the other team")]
+    AsymmetricObject(String, (u16, u16)),
+}
+
+/// How the red half of a map mirrors the blue half
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Symmetry {
+    /// (row, col) mirrors to (col, row)
+    Diagonal,
+    /// (row, col) mirrors to (rows - 1 - row, cols - 1 - col)
+    Point,
+}
+
+impl Symmetry {
+    /// Returns None when the mirrored cell is off the map
+    pub fn mirror(&self, position: (u16, u16), rows: usize, cols: usize) -> Option<(u16, u16)> {
+        let (row, col) = match self {
+            Symmetry::Diagonal => (position.1, position.0),
+            Symmetry::Point => (
+                (rows as u16).checked_sub(1)?.checked_sub(position.0)?,
+                (cols as u16).checked_sub(1)?.checked_sub(position.1)?,
+            ),
+        };
+        (usize::from(row) < rows && usize::from(col) < cols).then_some((row, col))
+    }
+
+    /// Around the center, the top lane corridor mirrors the bottom one
+    pub fn mirror_lane(&self, lane: Lane) -> Lane {
+        match (self, lane) {
+            (Symmetry::Point, Lane::Top) => Lane::Bottom,
+            (Symmetry::Point, Lane::Bottom) => Lane::Top,
+            _ => lane,
+        }
+    }
+}
+
+/// Returns every issue found on the map, an empty list means the map is valid
+pub fn check_map(map: &MapData) -> Vec<MapIssue> {
+    let mut issues = check_dimensions(map);
+    if !issues.is_empty() {
+        // Every other check relies on a well formed layout
+        return issues;
+    }
+    issues.extend(check_tiles(map));
+    if let Err(e) = map.validate() {
+        issues.push(MapIssue::InvalidObjects(e.to_string()));
+        return issues;
+    }
+    issues.extend(check_symmetry(map));
+
+    let board = build_board(map);
+    issues.extend(check_bases(map, &board));
+    issues.extend(check_reachability(map, &board));
+    issues
+}
+
+fn check_dimensions(map: &MapData) -> Vec<MapIssue> {
+    let mut issues = Vec::new();
+    if map.layout.len() != map.rows {
+        issues.push(MapIssue::RowCount(map.layout.len(), map.rows));
+    }
+    for (i, row) in map.layout.iter().enumerate() {
+        if row.len() != map.cols {
+            issues.push(MapIssue::ColCount(i, row.len(), map.cols));
+        }
+    }
+    issues
+}
+
+fn check_tiles(map: &MapData) -> Vec<MapIssue> {
+    let mut issues = Vec::new();
+    for (i, row) in map.layout.iter().enumerate() {
+        for (j, tile) in row.iter().enumerate() {
+            if BaseTerrain::from_tile(tile).is_none() {
+                issues.push(MapIssue::UnknownTile(i, j, tile.clone()));
+            }
+        }
+    }
+    issues
+}
+
+/// Every symmetry followed by the layout, a layout can follow several
+pub fn layout_symmetries(map: &MapData) -> Vec<Symmetry> {
+    [Symmetry::Diagonal, Symmetry::Point]
+        .into_iter()
+        .filter(|symmetry| *symmetry == Symmetry::Point || map.rows == map.cols)
+        .filter(|symmetry| {
+            map.layout.iter().enumerate().all(|(i, row)| {
+                row.iter().enumerate().all(|(j, tile)| {
+                    symmetry
+                        .mirror((i as u16, j as u16), map.rows, map.cols)
+                        .is_some_and(|(mirror_row, mirror_col)| {
+                            map.layout[mirror_row as usize][mirror_col as usize] == *tile
+                        })
+                })
+            })
+        })
+        .collect()
+}
+
+/// Objects may follow any symmetry of the layout, the issues of the closest
+/// one are reported
+fn check_symmetry(map: &MapData) -> Vec<MapIssue> {
+    let symmetries = layout_symmetries(map);
+    if symmetries.is_empty() {
+        return vec![MapIssue::AsymmetricLayout];
+    }
+    symmetries
+        .into_iter()
+        .map(|symmetry| check_object_symmetry(map, symmetry))
+        .min_by_key(|issues| issues.len())
+        .unwrap_or_default()
+}
+
+fn check_object_symmetry(map: &MapData, symmetry: Symmetry) -> Vec<MapIssue> {
+    let mirror = |position| symmetry.mirror(position, map.rows, map.cols);
+    let objects = &map.objects;
+    let mut issues = Vec::new();
+
+    for base in objects.bases.iter() {
+        // A base covers a square, its mirrored anchor is another corner of the square
+        let far_corner = (
+            base.position.0 + BASE_SIZE - 1,
+            base.position.1 + BASE_SIZE - 1,
+        );
+        let mirrored = match symmetry {
+            Symmetry::Diagonal => mirror(base.position),
+            Symmetry::Point => mirror(far_corner),
+        };
+        if !objects
+            .bases
+            .iter()
+            .any(|other| other.team != base.team && Some(other.position) == mirrored)
+        {
+            issues.push(MapIssue::AsymmetricObject(
+                format!("{:?} base", base.team),
+                base.position,
+            ));
+        }
+    }
+    for tower in objects.towers.iter() {
+        if !objects.towers.iter().any(|other| {
+            other.team != tower.team
+                && other.lane == symmetry.mirror_lane(tower.lane)
+                && other.tier == tower.tier
+                && Some(other.position) == mirror(tower.position)
+        }) {
+            issues.push(MapIssue::AsymmetricObject(
+                format!("{:?} {:?} tower", tower.team, tower.lane),
+                tower.position,
+            ));
+        }
+    }
+    for lane in objects.lanes.iter() {
+        if !objects.lanes.iter().any(|other| {
+            other.team != lane.team
+                && other.lane == symmetry.mirror_lane(lane.lane)
+                && Some(other.spawn) == mirror(lane.spawn)
+                && other.waypoints.len() == lane.waypoints.len()
+                && other
+                    .waypoints
+                    .iter()
+                    .zip(lane.waypoints.iter())
+                    .all(|(a, b)| Some(*a) == mirror(*b))
+        }) {
+            issues.push(MapIssue::AsymmetricObject(
+                format!("{:?} {:?} lane", lane.team, lane.lane),
+                lane.spawn,
+            ));
+        }
+    }
+    for zone in objects.spawns.iter() {
+        // None when part of the zone mirrors off the map
+        let mirrored_cells: Option<HashSet<(u16, u16)>> =
+            zone_cells(zone.position, zone.height, zone.width)
+                .map(mirror)
+                .collect();
+        if !mirrored_cells.is_some_and(|mirrored_cells| {
+            objects.spawns.iter().any(|other| {
+                other.team != zone.team
+                    && zone_cells(other.position, other.height, other.width).collect::<HashSet<_>>()
+                        == mirrored_cells
+            })
+        }) {
+            issues.push(MapIssue::AsymmetricObject(
+                format!("{:?} spawn zone", zone.team),
+                zone.position,
+            ));
+        }
+    }
+    // Camps have no team, the camp set must mirror itself
+    for camp in objects.camps.iter() {
+        if !objects
+            .camps
+            .iter()
+            .any(|other| Some(other.position) == mirror(camp.position))
+        {
+            issues.push(MapIssue::AsymmetricObject(
+                format!("Camp {}", camp.monster),
+                camp.position,
+            ));
+        }
+    }
+    issues
+}
+
+fn zone_cells(position: (u16, u16), height: u16, width: u16) -> impl Iterator<Item = (u16, u16)> {
+    (position.0..position.0 + height)
+        .flat_map(move |row| (position.1..position.1 + width).map(move |col| (row, col)))
+}
+
+/// Builds the board as the server does, towers and bases block the way
+fn build_board(map: &MapData) -> Board {
+    let mut board = Board::from_map(map);
+    for (id, tower) in map.objects.towers.iter().enumerate() {
+        for (row, col) in Tower::footprint_at(tower.position) {
+            board.place_cell(
+                CellContent::Tower(id as TowerId, tower.team),
+                row as usize,
+                col as usize,
+            );
+        }
+    }
+    for base in map.objects.bases.iter() {
+        for (r, c) in zone_cells(base.position, BASE_SIZE, BASE_SIZE) {
+            board.place_cell(CellContent::Base(base.team), r as usize, c as usize);
+        }
+    }
+    board
+}
+
+/// Passable cells of the ring around the base, the only cells a unit can
+/// stand on to enter it
+fn base_openings(board: &Board, position: (u16, u16)) -> Vec<(u16, u16)> {
+    let (row, col) = (position.0 as i32, position.1 as i32);
+    let size = BASE_SIZE as i32;
+    (row - 1..=row + size)
+        .flat_map(|r| (col - 1..=col + size).map(move |c| (r, c)))
+        .filter(|(r, c)| *r < row || *r >= row + size || *c < col || *c >= col + size)
+        .filter_map(|(r, c)| {
+            let cell = board.get_cell(usize::try_from(r).ok()?, usize::try_from(c).ok()?)?;
+            cell.is_passable().then_some((r as u16, c as u16))
+        })
+        .collect()
+}
+
+fn check_bases(map: &MapData, board: &Board) -> Vec<MapIssue> {
+    let mut issues = Vec::new();
+    for base in map.objects.bases.iter() {
+        let fits = zone_cells(base.position, BASE_SIZE, BASE_SIZE).all(|(row, col)| {
+            board
+                .get_cell(row as usize, col as usize)
+                .is_some_and(|cell| cell.base != BaseTerrain::Wall)
+        });
+        if !fits {
+            issues.push(MapIssue::BaseOutsideTerrain(base.team, base.position));
+            continue;
+        }
+        let openings = base_openings(board, base.position);
+        if openings.is_empty() {
+            issues.push(MapIssue::ClosedBase(base.team, base.position));
+            continue;
+        }
+        // The enemy champions must be able to walk into one of the openings
+        let reachable = enemy_spawn(map, board, base.team).is_some_and(|start| {
+            openings
+                .iter()
+                .any(|opening| start == *opening || walk(board, start, *opening).is_some())
+        });
+        if !reachable {
+            issues.push(MapIssue::UnreachableBase(base.team, base.position));
+        }
+    }
+    issues
+}
+
+fn enemy_spawn(map: &MapData, board: &Board, team: Team) -> Option<(u16, u16)> {
+    let enemy = Team::iter().find(|other| *other != team)?;
+    map.objects.spawn_zone(enemy)?.free_cell(board)
+}
+
+fn check_reachability(map: &MapData, board: &Board) -> Vec<MapIssue> {
+    let mut issues = Vec::new();
+    for team in Team::iter() {
+        if map
+            .objects
+            .spawn_zone(team)
+            .is_some_and(|zone| zone.free_cell(board).is_none())
+        {
+            issues.push(MapIssue::BlockedSpawnZone(team));
+        }
+    }
+
+    // Minions walk from their spawn through each waypoint
+    for lane in map.objects.lanes.iter() {
+        let mut position = lane.spawn;
+        for waypoint in lane.waypoints.iter() {
+            match walk(board, position, *waypoint) {
+                Some(reached) => position = reached,
+                None => {
+                    issues.push(MapIssue::UnreachableWaypoint(
+                        lane.team, lane.lane, *waypoint,
+                    ));
+                    break;
+                }
+            }
+        }
+    }
+
+    for tower in map.objects.towers.iter() {
+        let reachable = enemy_spawn(map, board, tower.team)
+            .is_some_and(|start| walk(board, start, tower.position).is_some());
+        if !reachable {
+            issues.push(MapIssue::UnreachableTower(
+                tower.team,
+                tower.lane,
+                tower.position,
+            ));
+        }
+    }
+
+    let blue_spawn = map
+        .objects
+        .spawn_zone(Team::Blue)
+        .and_then(|zone| zone.free_cell(board));
+    for camp in map.objects.camps.iter() {
+        let reachable = blue_spawn.is_some_and(|start| walk(board, start, camp.position).is_some());
+        if !reachable {
+            issues.push(MapIssue::UnreachableCamp(
+                camp.monster.clone(),
+                camp.position,
+            ));
+        }
+    }
+    issues
+}
+
+/// Walks from start until the unit stands next to `goal`, along the flow
+/// field units follow in game. Returns the position next to the goal, or None
+/// if the goal cannot be reached
+pub fn walk(board: &Board, start: (u16, u16), goal: (u16, u16)) -> Option<(u16, u16)> {
+    let path = FlowField::build(board, goal).path_from(start)?;
+    path.back().copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::algorithms::pathfinding::is_adjacent_to_goal;
+    use crate::game::map::MapRegistry;
+
+    // 10x10 map, mirrored along the diagonal, walls on (4, 5) and (5, 4)
+    const MAP_JSON: &str = r#"{
+        "metadata": { "id": 1, "name": "Test", "team_sizes": [1] },
+        "rows": 10,
+        "cols": 10,
+        "layout": [
+            ["floor", "floor", "floor", "floor", "floor", "floor", "floor", "floor", "floor", "floor"],
+            ["floor", "floor", "floor", "floor", "floor", "floor", "floor", "floor", "floor", "floor"],
+            ["floor", "floor", "floor", "floor", "floor", "floor", "floor", "floor", "floor", "floor"],
+            ["floor", "floor", "floor", "floor", "floor", "floor", "floor", "floor", "floor", "floor"],
+            ["floor", "floor", "floor", "floor", "floor", "wall", "floor", "floor", "floor", "floor"],
+            ["floor", "floor", "floor", "floor", "wall", "floor", "floor", "floor", "floor", "floor"],
+            ["floor", "floor", "floor", "floor", "floor", "floor", "floor", "floor", "floor", "floor"],
+            ["floor", "floor", "floor", "floor", "floor", "floor", "floor", "floor", "floor", "floor"],
+            ["floor", "floor", "floor", "floor", "floor", "floor", "floor", "floor", "floor", "floor"],
+            ["floor", "floor", "floor", "floor", "floor", "floor", "floor", "floor", "floor", "floor"]
+        ],
+        "objects": {
+            "bases": [
+                { "team": "blue", "position": [7, 0] },
+                { "team": "red", "position": [0, 7] }
+            ],
+            "towers": [
+                { "team": "blue", "lane": "mid", "tier": "outer", "position": [7, 5] },
+                { "team": "red", "lane": "mid", "tier": "outer", "position": [5, 7] }
+            ],
+            "lanes": [
+                { "team": "blue", "lane": "mid", "spawn": [6, 1], "waypoints": [[3, 3], [1, 6]] },
+                { "team": "red", "lane": "mid", "spawn": [1, 6], "waypoints": [[3, 3], [6, 1]] }
+            ],
+            "spawns": [
+                { "team": "blue", "position": [9, 3], "height": 1, "width": 2 },
+                { "team": "red", "position": [3, 9], "height": 2, "width": 1 }
+            ],
+            "camps": [
+                { "monster": "wolf", "position": [2, 2] }
+            ]
+        }
+    }"#;
+
+    fn load_map() -> MapData {
+        serde_json::from_str(MAP_JSON).unwrap()
+    }
+
+    #[test]
+    fn test_valid_map_has_no_issue() {
+        let map = load_map();
+        assert_eq!(
+            layout_symmetries(&map),
+            vec![Symmetry::Diagonal, Symmetry::Point]
+        );
+        assert_eq!(check_map(&map), vec![]);
+    }
+
+    #[test]
+    fn test_check_dimensions_and_tiles() {
+        let mut map = load_map();
+        map.layout[1].pop();
+        assert_eq!(check_map(&map), vec![MapIssue::ColCount(1, 9, 10)]);
+
+        let mut map = load_map();
+        map.layout[4][1] = "lava".to_string();
+        let issues = check_map(&map);
+        assert!(issues.contains(&MapIssue::UnknownTile(4, 1, "lava".to_string())));
+        assert!(issues.contains(&MapIssue::AsymmetricLayout));
+    }
+
+    #[test]
+    fn test_check_symmetry_of_objects() {
+        let mut map = load_map();
+        map.objects.towers[1].position = (6, 7);
+        let issues = check_map(&map);
+        assert!(issues.contains(&MapIssue::AsymmetricObject(
+            "Blue Mid tower".to_string(),
+            (7, 5)
+        )));
+        assert!(issues.contains(&MapIssue::AsymmetricObject(
+            "Red Mid tower".to_string(),
+            (6, 7)
+        )));
+    }
+
+    #[test]
+    fn test_point_symmetry() {
+        let map = load_map();
+        assert_eq!(
+            Symmetry::Point.mirror((0, 1), map.rows, map.cols),
+            Some((9, 8))
+        );
+        assert_eq!(Symmetry::Point.mirror((10, 1), map.rows, map.cols), None);
+        assert_eq!(Symmetry::Point.mirror_lane(Lane::Top), Lane::Bottom);
+        assert_eq!(Symmetry::Diagonal.mirror_lane(Lane::Top), Lane::Top);
+
+        let mut point_map = load_map();
+        point_map.layout[4][5] = "floor".to_string();
+        point_map.layout[5][4] = "floor".to_string();
+        point_map.layout[0][1] = "bush".to_string();
+        point_map.layout[9][8] = "bush".to_string();
+        assert_eq!(layout_symmetries(&point_map), vec![Symmetry::Point]);
+    }
+
+    #[test]
+    fn test_objects_may_follow_any_symmetry_of_the_layout() {
+        // The layout follows both symmetries, the objects the diagonal one
+        let mut map = load_map();
+        assert_eq!(check_symmetry(&map), vec![]);
+
+        // Red objects mirrored around the center instead of along the diagonal
+        map.objects.towers[1].position = (2, 4);
+        map.objects.lanes[1].spawn = (3, 8);
+        map.objects.lanes[1].waypoints = vec![(6, 6), (8, 3)];
+        map.objects.spawns[1].position = (0, 5);
+        map.objects.spawns[1].height = 1;
+        map.objects.spawns[1].width = 2;
+        map.objects.camps.push(map.objects.camps[0].clone());
+        map.objects.camps[1].position = (7, 7);
+        assert!(!check_object_symmetry(&map, Symmetry::Diagonal).is_empty());
+        assert_eq!(check_symmetry(&map), vec![]);
+    }
+
+    #[test]
+    fn test_base_on_the_map_edge_is_reported() {
+        let mut map = load_map();
+        map.layout[4][5] = "floor".to_string();
+        map.layout[5][4] = "floor".to_string();
+        map.layout[0][1] = "bush".to_string();
+        map.layout[9][8] = "bush".to_string();
+        assert_eq!(layout_symmetries(&map), vec![Symmetry::Point]);
+
+        // The far corner of the base is off the map, it has no mirror
+        map.objects.bases[0].position = (9, 9);
+        let issues = check_symmetry(&map);
+        assert!(issues.contains(&MapIssue::AsymmetricObject("Blue base".to_string(), (9, 9))));
+    }
+
+    #[test]
+    fn test_check_reachability() {
+        let mut map = load_map();
+        // Wall off the camp
+        for (row, col) in [
+            (1, 1),
+            (1, 2),
+            (1, 3),
+            (2, 1),
+            (2, 3),
+            (3, 1),
+            (3, 2),
+            (3, 3),
+        ] {
+            map.layout[row][col] = "wall".to_string();
+        }
+        map.objects.lanes[0].waypoints = vec![(2, 2)];
+        map.objects.lanes[1].waypoints = vec![(2, 2)];
+
+        let issues = check_map(&map);
+        assert!(issues.contains(&MapIssue::UnreachableWaypoint(
+            Team::Blue,
+            Lane::Mid,
+            (2, 2)
+        )));
+        assert!(issues.contains(&MapIssue::UnreachableCamp("wolf".to_string(), (2, 2))));
+    }
+
+    #[test]
+    fn test_check_bases() {
+        let mut map = load_map();
+        map.layout[8][1] = "wall".to_string();
+        map.layout[1][8] = "wall".to_string();
+        let issues = check_map(&map);
+        assert_eq!(
+            issues,
+            vec![
+                MapIssue::BaseOutsideTerrain(Team::Blue, (7, 0)),
+                MapIssue::BaseOutsideTerrain(Team::Red, (0, 7)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_base_is_entered_through_its_openings() {
+        let map = load_map();
+        let mut board = build_board(&map);
+        let ring = [(6, 0), (6, 1), (6, 2), (6, 3), (7, 3), (8, 3), (9, 3)];
+        assert_eq!(base_openings(&board, (7, 0)), ring);
+        assert_eq!(check_bases(&map, &board), vec![]);
+
+        // Walls all around the blue base
+        for (row, col) in ring {
+            board.change_base(BaseTerrain::Wall, row as usize, col as usize);
+        }
+        assert_eq!(
+            check_bases(&map, &board),
+            vec![MapIssue::ClosedBase(Team::Blue, (7, 0))]
+        );
+
+        // A single opening, walled off from the rest of the map
+        board.change_base(BaseTerrain::Floor, 6, 3);
+        for (row, col) in [(5, 2), (5, 3), (5, 4), (6, 4), (7, 4)] {
+            board.change_base(BaseTerrain::Wall, row as usize, col as usize);
+        }
+        assert_eq!(base_openings(&board, (7, 0)), vec![(6, 3)]);
+        assert_eq!(
+            check_bases(&map, &board),
+            vec![MapIssue::UnreachableBase(Team::Blue, (7, 0))]
+        );
+    }
+
+    #[test]
+    fn test_walk_goes_around_walls() {
+        let map = load_map();
+        let board = build_board(&map);
+        let reached = walk(&board, (6, 1), (1, 6)).unwrap();
+        assert!(is_adjacent_to_goal(reached, (1, 6)));
+    }
+
+    #[test]
+    fn test_shipped_maps_pass_checks() {
+        let registry = MapRegistry::load("maps").unwrap();
+        let ids: Vec<u8> = registry.maps().map(|summary| summary.metadata.id).collect();
+        for id in ids {
+            let map = registry.load_map(Some(id)).unwrap();
+            assert_eq!(check_map(&map), vec![], "Map {} has issues", id);
+        }
+    }
+}
@@ -0,0 +1,659 @@
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use std::collections::{HashSet, VecDeque};
+
+use super::algorithms::bresenham::Bresenham;
+use super::cell::{BaseTerrain, Team};
+use super::entities::minion::Lane;
+use super::map::{
+    BaseLayout, CampLayout, LaneLayout, MapData, MapMetadata, MapObjects, SpawnZone, TowerLayout,
+};
+use super::map_check::{Symmetry, check_map};
+use crate::config::TowerTier;
+use crate::errors::MapError;
+
+const MIN_SIZE: u16 = 40;
+const MAX_SIZE: u16 = 250;
+/// Towers of a lane, as a fraction of the lane length from their base
+const TOWER_FRACTIONS: [(TowerTier, f32); 3] = [
+    (TowerTier::Outer, 0.4),
+    (TowerTier::Inner, 0.25),
+    (TowerTier::Inhibitor, 0.12),
+];
+
+#[derive(Debug, Clone)]
+pub struct GeneratorSettings {
+    pub seed: u64,
+    /// Rows and columns of the square map
+    pub size: u16,
+    /// 1 is a single mid lane, 2 are top and bottom, 3 are all lanes
+    pub lanes: u8,
+    /// Share of the jungle covered by walls, from 0 to 1
+    pub jungle_density: f32,
+    /// Chance for a walkable cell to be a bush, from 0 to 1
+    pub bush_frequency: f32,
+    /// Monsters spawned by the camps, in turn
+    pub monsters: Vec<String>,
+    pub metadata: MapMetadata,
+}
+
+/// Generates a map mirrored around its center: the cell (row, col) of the blue
+/// half is the cell (size - 1 - row, size - 1 - col) of the red half
+pub fn generate_map(settings: &GeneratorSettings) -> Result<MapData, MapError> {
+    if !(MIN_SIZE..=MAX_SIZE).contains(&settings.size) {
+        return Err(MapError::InvalidGeneratorSettings(format!(
+            "size must be between {} and {}",
+            MIN_SIZE, MAX_SIZE
+        )));
+    }
+    if !(1..=3).contains(&settings.lanes) {
+        return Err(MapError::InvalidGeneratorSettings(
+            "lanes must be between 1 and 3".to_string(),
+        ));
+    }
+    if !(0.0..=1.0).contains(&settings.jungle_density)
+        || !(0.0..=1.0).contains(&settings.bush_frequency)
+    {
+        return Err(MapError::InvalidGeneratorSettings(
+            "jungle density and bush frequency must be between 0 and 1".to_string(),
+        ));
+    }
+
+    let mut generator = Generator::new(settings);
+    generator.add_border();
+    generator.add_jungle(settings.jungle_density);
+    generator.remove_unreachable_pockets();
+    let objects = generator.place_objects(&settings.monsters)?;
+    generator.add_bushes(settings.bush_frequency, &objects);
+
+    let size = settings.size as usize;
+    let map = MapData {
+        metadata: settings.metadata.clone(),
+        rows: size,
+        cols: size,
+        layout: generator.layout(),
+        objects,
+    };
+    // The generator promises a valid map, a map failing the checks is a bug
+    let issues = check_map(&map);
+    if let Some(issue) = issues.first() {
+        return Err(MapError::InvalidGeneratedMap(
+            issues.len(),
+            issue.to_string(),
+        ));
+    }
+    Ok(map)
+}
+
+struct Generator {
+    size: u16,
+    rng: StdRng,
+    grid: Vec<Vec<BaseTerrain>>,
+    /// Cells walls cannot cover: lane corridors and base areas
+    protected: Vec<Vec<bool>>,
+    /// Half width of a lane corridor
+    lane_radius: u16,
+    /// Center of the blue base
+    base_center: (u16, u16),
+    /// Cells followed by each lane, from the blue base to the red base
+    lane_paths: Vec<(Lane, Vec<(u16, u16)>)>,
+}
+
+impl Generator {
+    fn new(settings: &GeneratorSettings) -> Self {
+        let size = settings.size;
+        let lane_radius = (size / 50).max(2);
+        let base_area = (size / 8).max(8);
+        let base_center = (size - 1 - base_area / 2, base_area / 2);
+        let mut generator = Generator {
+            size,
+            rng: StdRng::seed_from_u64(settings.seed),
+            grid: vec![vec![BaseTerrain::Floor; size as usize]; size as usize],
+            protected: vec![vec![false; size as usize]; size as usize],
+            lane_radius,
+            base_center,
+            lane_paths: Vec::new(),
+        };
+
+        let lanes: &[Lane] = match settings.lanes {
+            1 => &[Lane::Mid],
+            2 => &[Lane::Top, Lane::Bottom],
+            _ => &[Lane::Top, Lane::Mid, Lane::Bottom],
+        };
+        let margin = lane_radius + 2;
+        for lane in lanes {
+            let corner = match lane {
+                Lane::Top => Some((margin, margin)),
+                Lane::Mid => None,
+                Lane::Bottom => Some((size - 1 - margin, size - 1 - margin)),
+            };
+            let red_base_center = generator.mirror(base_center);
+            let path = match corner {
+                Some(corner) => polyline(&[base_center, corner, red_base_center]),
+                None => polyline(&[base_center, red_base_center]),
+            };
+            for position in path.iter() {
+                generator.protect_disk(*position, lane_radius + 1);
+            }
+            generator.lane_paths.push((*lane, path));
+        }
+        for row in size - base_area..size {
+            for col in 0..base_area {
+                generator.protect((row, col));
+            }
+        }
+        generator
+    }
+
+    fn mirror(&self, position: (u16, u16)) -> (u16, u16) {
+        (self.size - 1 - position.0, self.size - 1 - position.1)
+    }
+
+    fn is_blue_half(&self, position: (u16, u16)) -> bool {
+        position.0 + position.1 > self.size - 1
+    }
+
+    fn protect(&mut self, position: (u16, u16)) {
+        let mirrored = self.mirror(position);
+        self.protected[position.0 as usize][position.1 as usize] = true;
+        self.protected[mirrored.0 as usize][mirrored.1 as usize] = true;
+    }
+
+    fn protect_disk(&mut self, center: (u16, u16), radius: u16) {
+        for position in disk(center, radius, self.size) {
+            self.protect(position);
+        }
+    }
+
+    /// Sets a cell of the blue half and its mirror
+    fn set(&mut self, position: (u16, u16), terrain: BaseTerrain) {
+        let mirrored = self.mirror(position);
+        self.grid[position.0 as usize][position.1 as usize] = terrain;
+        self.grid[mirrored.0 as usize][mirrored.1 as usize] = terrain;
+    }
+
+    fn terrain(&self, position: (u16, u16)) -> BaseTerrain {
+        self.grid[position.0 as usize][position.1 as usize]
+    }
+
+    fn add_border(&mut self) {
+        let last = self.size - 1;
+        for i in 0..self.size {
+            self.set((last, i), BaseTerrain::Wall);
+            self.set((i, 0), BaseTerrain::Wall);
+        }
+    }
+
+    /// Covers the jungle between lanes with round walls, only on the blue half
+    /// then mirrored, until `density` of the jungle is covered
+    fn add_jungle(&mut self, density: f32) {
+        let jungle: Vec<(u16, u16)> = (0..self.size)
+            .flat_map(|row| (0..self.size).map(move |col| (row, col)))
+            .filter(|position| {
+                self.is_blue_half(*position)
+                    && !self.protected[position.0 as usize][position.1 as usize]
+                    && self.terrain(*position) == BaseTerrain::Floor
+            })
+            .collect();
+        let target = (jungle.len() as f32 * density) as usize;
+        let mut covered = 0;
+        let mut attempts = 0;
+        while covered < target && attempts < jungle.len() {
+            attempts += 1;
+            let center = jungle[self.rng.random_range(0..jungle.len())];
+            let radius = self.rng.random_range(1..=self.lane_radius * 2);
+            for position in disk(center, radius, self.size) {
+                if covered < target
+                    && self.is_blue_half(position)
+                    && !self.protected[position.0 as usize][position.1 as usize]
+                    && self.terrain(position) == BaseTerrain::Floor
+                {
+                    self.set(position, BaseTerrain::Wall);
+                    covered += 1;
+                }
+            }
+        }
+    }
+
+    /// Fills every walkable pocket that cannot be reached from the blue base.
+    /// Lanes link both bases, so the reachable area mirrors itself
+    fn remove_unreachable_pockets(&mut self) {
+        let reachable = flood_fill(&self.grid, self.base_center);
+        for row in 0..self.size {
+            for col in 0..self.size {
+                if !reachable.contains(&(row, col)) {
+                    self.grid[row as usize][col as usize] = BaseTerrain::Wall;
+                }
+            }
+        }
+    }
+
+    fn place_objects(&mut self, monsters: &[String]) -> Result<MapObjects, MapError> {
+        let size = self.size;
+        let base_anchor = (self.base_center.0 - 1, self.base_center.1 - 1);
+        let spawn_zone = SpawnZone {
+            team: Team::Blue,
+            position: (size - 4, 1),
+            height: 3,
+            width: 3,
+        };
+        // The red base covers the mirrored square, its anchor is the mirror of the far corner
+        let bases = vec![
+            BaseLayout {
+                team: Team::Blue,
+                position: base_anchor,
+            },
+            BaseLayout {
+                team: Team::Red,
+                position: self.mirror((base_anchor.0 + 2, base_anchor.1 + 2)),
+            },
+        ];
+        let red_zone_corner = self.mirror((spawn_zone.position.0 + 2, spawn_zone.position.1 + 2));
+        let spawns = vec![
+            spawn_zone.clone(),
+            SpawnZone {
+                team: Team::Red,
+                position: red_zone_corner,
+                ..spawn_zone
+            },
+        ];
+
+        // Cells taken by bases, spawn zones and towers, with their mirror
+        let mut taken: HashSet<(u16, u16)> = HashSet::new();
+        for position in disk(self.base_center, 2, size).chain(disk((size - 3, 2), 2, size)) {
+            taken.insert(position);
+            taken.insert(self.mirror(position));
+        }
+
+        // Minions of a lane spawn on the first cell of the path away from their base
+        let lane_spawns: Vec<(Lane, (u16, u16), usize)> = self
+            .lane_paths
+            .iter()
+            .map(|(lane, path)| {
+                let index = path
+                    .iter()
+                    .position(|position| chebyshev(*position, self.base_center) >= 3)
+                    .unwrap_or(0);
+                (*lane, path[index], index)
+            })
+            .collect();
+
+        let mut lanes = Vec::new();
+        let mut towers = Vec::new();
+        let step = (size / 8) as usize;
+        for (lane, path, (_, spawn, spawn_index)) in self
+            .lane_paths
+            .iter()
+            .zip(lane_spawns.iter())
+            .map(|((lane, path), spawn)| (*lane, path, spawn))
+        {
+            // The last waypoint is where the minions of the other team spawn
+            let mirrored_lane = Symmetry::Point.mirror_lane(lane);
+            let (_, end, _) = lane_spawns
+                .iter()
+                .find(|(other, _, _)| *other == mirrored_lane)
+                .expect("Mirrored lanes are generated together");
+            let end = self.mirror(*end);
+            let end_index = path.len() - 1 - spawn_index;
+            let mut waypoints: Vec<(u16, u16)> = path[*spawn_index..end_index]
+                .iter()
+                .skip(step)
+                .step_by(step)
+                .copied()
+                .collect();
+            waypoints.push(end);
+            lanes.push(LaneLayout {
+                team: Team::Blue,
+                lane,
+                spawn: *spawn,
+                waypoints: waypoints.clone(),
+            });
+            lanes.push(LaneLayout {
+                team: Team::Red,
+                lane: mirrored_lane,
+                spawn: self.mirror(*spawn),
+                waypoints: waypoints.iter().map(|w| self.mirror(*w)).collect(),
+            });
+
+            for (tier, fraction) in TOWER_FRACTIONS {
+                let target = path[(path.len() as f32 * fraction) as usize];
+                let anchor = self.tower_anchor(target, &taken).ok_or_else(|| {
+                    MapError::InvalidGeneratorSettings(format!("no room for a {:?} tower", lane))
+                })?;
+                for position in tower_cells(anchor).chain(tower_cells(self.mirror(anchor))) {
+                    taken.insert(position);
+                    taken.insert(self.mirror(position));
+                }
+                towers.push(TowerLayout {
+                    team: Team::Blue,
+                    lane,
+                    tier,
+                    position: anchor,
+                });
+                towers.push(TowerLayout {
+                    team: Team::Red,
+                    lane: mirrored_lane,
+                    tier,
+                    position: self.mirror(anchor),
+                });
+            }
+        }
+
+        let camps = self.place_camps(monsters, &taken);
+        Ok(MapObjects {
+            bases,
+            towers,
+            lanes,
+            spawns,
+            camps,
+        })
+    }
+
+    /// Finds the closest cell to `target` where a tower and its mirror fit on
+    /// floor, with room to walk around them
+    fn tower_anchor(&self, target: (u16, u16), taken: &HashSet<(u16, u16)>) -> Option<(u16, u16)> {
+        let mut candidates: Vec<(u16, u16)> = disk(target, self.lane_radius, self.size)
+            .filter(|position| *position != target)
+            .collect();
+        candidates.sort_by_key(|position| chebyshev(*position, target));
+        candidates.into_iter().find(|anchor| {
+            anchor.0 >= 2
+                && anchor.1 + 2 < self.size
+                && [*anchor, self.mirror(*anchor)].iter().all(|tower| {
+                    tower.0 >= 2
+                        && tower.1 + 2 < self.size
+                        && tower_cells(*tower).all(|cell| !taken.contains(&cell))
+                        // The tower and the ring around it are floor
+                        && (tower.0 - 2..=tower.0 + 1).all(|row| {
+                            (tower.1 - 1..=tower.1 + 2).all(|col| {
+                                self.grid
+                                    .get(row as usize)
+                                    .and_then(|cells| cells.get(col as usize))
+                                    == Some(&BaseTerrain::Floor)
+                            })
+                        })
+                })
+        })
+    }
+
+    /// Camps go in the jungle, away from the lanes, one per 50 cells of map size
+    fn place_camps(&mut self, monsters: &[String], taken: &HashSet<(u16, u16)>) -> Vec<CampLayout> {
+        let mut camps = Vec::new();
+        if monsters.is_empty() {
+            return camps;
+        }
+        let mut candidates: Vec<(u16, u16)> = (1..self.size - 1)
+            .flat_map(|row| (1..self.size - 1).map(move |col| (row, col)))
+            .filter(|position| {
+                self.is_blue_half(*position)
+                    && !self.protected[position.0 as usize][position.1 as usize]
+                    && !taken.contains(position)
+                    && disk(*position, 1, self.size)
+                        .all(|cell| self.terrain(cell) == BaseTerrain::Floor)
+            })
+            .collect();
+        candidates.shuffle(&mut self.rng);
+
+        let count = (self.size / 50).max(1) as usize;
+        let mut placed: Vec<(u16, u16)> = Vec::new();
+        for position in candidates {
+            if placed.len() == count {
+                break;
+            }
+            // Camps are spread over the jungle
+            if placed
+                .iter()
+                .any(|other| chebyshev(*other, position) < self.size / 8)
+            {
+                continue;
+            }
+            let monster = &monsters[placed.len() % monsters.len()];
+            camps.push(CampLayout {
+                monster: monster.clone(),
+                position,
+            });
+            camps.push(CampLayout {
+                monster: monster.clone(),
+                position: self.mirror(position),
+            });
+            placed.push(position);
+        }
+        camps
+    }
+
+    fn add_bushes(&mut self, frequency: f32, objects: &MapObjects) {
+        // Objects stand on plain floor
+        let mut reserved: HashSet<(u16, u16)> = HashSet::new();
+        for base in objects.bases.iter() {
+            reserved.extend(disk(
+                (base.position.0 + 1, base.position.1 + 1),
+                2,
+                self.size,
+            ));
+        }
+        for zone in objects.spawns.iter() {
+            reserved.extend(disk(
+                (zone.position.0 + 1, zone.position.1 + 1),
+                1,
+                self.size,
+            ));
+        }
+        for tower in objects.towers.iter() {
+            reserved.extend(tower_cells(tower.position));
+        }
+        for lane in objects.lanes.iter() {
+            reserved.insert(lane.spawn);
+            reserved.extend(lane.waypoints.iter().copied());
+        }
+        for camp in objects.camps.iter() {
+            reserved.insert(camp.position);
+        }
+
+        for row in 0..self.size {
+            for col in 0..self.size {
+                let position = (row, col);
+                if self.is_blue_half(position)
+                    && self.terrain(position) == BaseTerrain::Floor
+                    && !reserved.contains(&position)
+                    && !reserved.contains(&self.mirror(position))
+                    && self.rng.random::<f32>() < frequency
+                {
+                    self.set(position, BaseTerrain::Bush);
+                }
+            }
+        }
+    }
+
+    fn layout(&self) -> Vec<Vec<String>> {
+        self.grid
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|terrain| match terrain {
+                        BaseTerrain::Wall => "wall",
+                        BaseTerrain::Bush => "bush",
+                        _ => "floor",
+                    })
+                    .map(String::from)
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+fn chebyshev(a: (u16, u16), b: (u16, u16)) -> u16 {
+    a.0.abs_diff(b.0).max(a.1.abs_diff(b.1))
+}
+
+/// Cells joining each point to the next one
+fn polyline(points: &[(u16, u16)]) -> Vec<(u16, u16)> {
+    let mut path: Vec<(u16, u16)> = vec![points[0]];
+    for segment in points.windows(2) {
+        path.extend(Bresenham::new(segment[0], segment[1]).skip(1));
+    }
+    path
+}
+
+/// Cells of the square of side 2 * radius + 1 around center, on the map
+fn disk(center: (u16, u16), radius: u16, size: u16) -> impl Iterator<Item = (u16, u16)> {
+    let rows = center.0.saturating_sub(radius)..=(center.0 + radius).min(size - 1);
+    let cols = center.1.saturating_sub(radius)..=(center.1 + radius).min(size - 1);
+    rows.flat_map(move |row| cols.clone().map(move |col| (row, col)))
+}
+
+/// A tower covers its cell, the cell above and the cells on their right
+fn tower_cells(anchor: (u16, u16)) -> impl Iterator<Item = (u16, u16)> {
+    [
+        anchor,
+        (anchor.0 - 1, anchor.1),
+        (anchor.0, anchor.1 + 1),
+        (anchor.0 - 1, anchor.1 + 1),
+    ]
+    .into_iter()
+}
+
+fn flood_fill(grid: &[Vec<BaseTerrain>], start: (u16, u16)) -> HashSet<(u16, u16)> {
+    let mut reached: HashSet<(u16, u16)> = HashSet::new();
+    let mut queue: VecDeque<(u16, u16)> = VecDeque::from([start]);
+    reached.insert(start);
+    while let Some((row, col)) = queue.pop_front() {
+        for (d_row, d_col) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
+            let next = ((row as i32 + d_row) as usize, (col as i32 + d_col) as usize);
+            if grid
+                .get(next.0)
+                .and_then(|cells| cells.get(next.1))
+                .is_some_and(|terrain| *terrain != BaseTerrain::Wall)
+                && reached.insert((next.0 as u16, next.1 as u16))
+            {
+                queue.push_back((next.0 as u16, next.1 as u16));
+            }
+        }
+    }
+    reached
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::map_check::layout_symmetries;
+
+    fn create_settings(seed: u64, size: u16, lanes: u8) -> GeneratorSettings {
+        GeneratorSettings {
+            seed,
+            size,
+            lanes,
+            jungle_density: 0.4,
+            bush_frequency: 0.05,
+            monsters: vec!["wolf".to_string(), "golem".to_string()],
+            metadata: MapMetadata {
+                id: 9,
+                name: "Generated".to_string(),
+                team_sizes: vec![1],
+            },
+        }
+    }
+
+    #[test]
+    fn test_generated_map_passes_checks() {
+        for (seed, size, lanes) in [(1, 200, 3), (2, 120, 2), (3, 60, 1), (4, 40, 3)] {
+            let map = generate_map(&create_settings(seed, size, lanes)).unwrap();
+            assert_eq!(map.validate(), Ok(()));
+            assert!(layout_symmetries(&map).contains(&Symmetry::Point));
+            assert_eq!(check_map(&map), vec![], "Seed {} size {}", seed, size);
+            assert_eq!(map.objects.lanes.len(), lanes as usize * 2);
+            assert_eq!(map.objects.towers.len(), lanes as usize * 6);
+        }
+    }
+
+    #[test]
+    fn test_extreme_densities_give_valid_maps() {
+        for jungle_density in [0.0, 1.0] {
+            for bush_frequency in [0.0, 1.0] {
+                for (seed, size, lanes) in [(3, 80, 3), (8, 40, 2), (9, 60, 1)] {
+                    let mut settings = create_settings(seed, size, lanes);
+                    settings.jungle_density = jungle_density;
+                    settings.bush_frequency = bush_frequency;
+                    let map = generate_map(&settings).unwrap_or_else(|e| {
+                        panic!(
+                            "Seed {} size {} jungle {} bushes {}: {}",
+                            seed, size, jungle_density, bush_frequency, e
+                        )
+                    });
+                    assert_eq!(check_map(&map), vec![]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_generation_is_deterministic() {
+        let settings = create_settings(42, 80, 3);
+        let first = generate_map(&settings).unwrap();
+        let second = generate_map(&settings).unwrap();
+        assert_eq!(first.layout, second.layout);
+
+        let other = generate_map(&create_settings(43, 80, 3)).unwrap();
+        assert_ne!(first.layout, other.layout);
+    }
+
+    #[test]
+    fn test_jungle_density_and_bushes() {
+        let count = |map: &MapData, tile: &str| {
+            map.layout
+                .iter()
+                .flatten()
+                .filter(|cell| cell.as_str() == tile)
+                .count()
+        };
+        let mut settings = create_settings(5, 100, 3);
+        settings.jungle_density = 0.0;
+        settings.bush_frequency = 0.0;
+        let open = generate_map(&settings).unwrap();
+        assert_eq!(count(&open, "bush"), 0);
+
+        settings.jungle_density = 0.8;
+        settings.bush_frequency = 0.2;
+        let dense = generate_map(&settings).unwrap();
+        assert!(count(&dense, "wall") > count(&open, "wall"));
+        assert!(count(&dense, "bush") > 0);
+    }
+
+    #[test]
+    fn test_lanes_connect_both_bases() {
+        let map = generate_map(&create_settings(7, 100, 3)).unwrap();
+        let board_grid: Vec<Vec<BaseTerrain>> = map
+            .layout
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|tile| BaseTerrain::from_tile(tile).unwrap())
+                    .collect()
+            })
+            .collect();
+        let blue_lane = &map.objects.lanes[0];
+        let reachable = flood_fill(&board_grid, blue_lane.spawn);
+        for lane in map.objects.lanes.iter() {
+            assert!(reachable.contains(&lane.spawn));
+            assert!(lane.waypoints.iter().all(|w| reachable.contains(w)));
+        }
+        assert!(!map.objects.camps.is_empty());
+        for camp in map.objects.camps.iter() {
+            assert!(reachable.contains(&camp.position));
+        }
+    }
+
+    #[test]
+    fn test_invalid_settings() {
+        let mut settings = create_settings(1, 20, 3);
+        assert!(matches!(
+            generate_map(&settings),
+            Err(MapError::InvalidGeneratorSettings(_))
+        ));
+        settings.size = 100;
+        settings.lanes = 4;
+        assert!(generate_map(&settings).is_err());
+        settings.lanes = 3;
+        settings.jungle_density = 1.5;
+        assert!(generate_map(&settings).is_err());
+    }
+}
@@ -1,183 +1,521 @@
-use rand::prelude::*;
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     time::{Duration, Instant},
 };
-use strum::IntoEnumIterator;
-
-use crate::errors::GameError;
 
 use super::{
     Board, CellContent, MinionId,
+    algorithms::pathfinding::{Pathfinder, get_valid_neighbors},
     animation::AnimationTrait,
     cell::Team,
     entities::{
         Target,
-        minion::{Lane, Minion},
+        minion::{Lane, Minion, Traffic},
         projectile::GameplayEffect,
     },
+    map::LaneLayout,
+    projectile_manager::ProjectileManager,
+    registry::{EntityKind, EntityRegistry},
 };
-use crate::config::MinionStats;
+use crate::config::{MinionKind, MinionRoster, MinionRules, Rules};
 
 #[derive(Debug)]
 pub struct MinionManager {
-    minions_per_wave: u8,
     pub minions_this_wave: u8,
+    pub wave_number: u32,
     pub minions: HashMap<MinionId, Minion>,
     pub wave_creation_time: Instant,
-    minion_stats: MinionStats,
+    minion_stats: MinionRoster,
+    minion_rules: MinionRules,
+    wave_interval: Duration,
+    lanes: Vec<LaneLayout>,
+    // Lanes where a team spawns super minions, the enemy towers of the lane are destroyed
+    super_minion_lanes: HashSet<(Team, Lane)>,
 }
 
 impl MinionManager {
-    pub fn new(minion_stats: MinionStats) -> Self {
+    pub fn new(minion_stats: MinionRoster, rules: &Rules, lanes: Vec<LaneLayout>) -> Self {
         Self {
-            minions_per_wave: 6,
             minions_this_wave: 0,
+            wave_number: 1,
             minions: HashMap::new(),
             wave_creation_time: Instant::now(),
             minion_stats,
+            minion_rules: rules.minion_rules.clone(),
+            wave_interval: Duration::from_secs(rules.timing_rules.minion_wave_interval_secs),
+            lanes,
+            super_minion_lanes: HashSet::new(),
         }
     }
 
-    pub fn make_wave(&mut self, board: &mut Board) {
+    pub fn wave_interval(&self) -> Duration {
+        self.wave_interval
+    }
+
+    pub fn enable_super_minions(&mut self, team: Team, lane: Lane) {
+        self.super_minion_lanes.insert((team, lane));
+    }
+
+    // Super minions lead the wave in unlocked lanes, then the wave composition follows
+    fn lane_composition(&self, team: Team, lane: Lane) -> Vec<MinionKind> {
+        let super_count = if self.super_minion_lanes.contains(&(team, lane)) {
+            self.minion_rules.super_minions_per_wave
+        } else {
+            0
+        };
+        let mut composition = vec![MinionKind::Super; super_count as usize];
+        composition.extend(self.minion_rules.wave_composition(self.wave_number));
+        composition
+    }
+
+    pub fn make_wave(
+        &mut self,
+        board: &mut Board,
+        registry: &mut EntityRegistry,
+        game_time: Duration,
+    ) {
         let now = Instant::now();
         if now >= self.wave_creation_time {
-            for team in Team::iter() {
-                match team {
-                    Team::Blue => {
-                        for lane in Lane::iter() {
-                            let minion_id = generate_minion_id().unwrap();
-                            match lane {
-                                Lane::Top => {
-                                    let minion = Minion::new(
-                                        minion_id,
-                                        team,
-                                        lane,
-                                        self.minion_stats.clone(),
-                                    );
-                                    board.place_cell(
-                                        CellContent::Minion(minion_id, team),
-                                        minion.row as usize,
-                                        minion.col as usize,
-                                    );
-                                    self.minions.insert(minion_id, minion);
-                                }
-                                Lane::Mid => {
-                                    let minion = Minion::new(
-                                        minion_id,
-                                        team,
-                                        lane,
-                                        self.minion_stats.clone(),
-                                    );
-                                    board.place_cell(
-                                        CellContent::Minion(minion_id, team),
-                                        minion.row as usize,
-                                        minion.col as usize,
-                                    );
-                                    self.minions.insert(minion_id, minion);
-                                }
-                                Lane::Bottom => {
-                                    let minion = Minion::new(
-                                        minion_id,
-                                        team,
-                                        lane,
-                                        self.minion_stats.clone(),
-                                    );
-                                    board.place_cell(
-                                        CellContent::Minion(minion_id, team),
-                                        minion.row as usize,
-                                        minion.col as usize,
-                                    );
-                                    self.minions.insert(minion_id, minion);
-                                }
-                            }
-                        }
-                    }
-                    Team::Red => {
-                        for lane in Lane::iter() {
-                            let minion_id = generate_minion_id().unwrap();
-                            match lane {
-                                Lane::Top => {
-                                    let minion = Minion::new(
-                                        minion_id,
-                                        team,
-                                        lane,
-                                        self.minion_stats.clone(),
-                                    );
-                                    board.place_cell(
-                                        CellContent::Minion(minion_id, team),
-                                        minion.row as usize,
-                                        minion.col as usize,
-                                    );
-                                    self.minions.insert(minion_id, minion);
-                                }
-                                Lane::Mid => {
-                                    let minion = Minion::new(
-                                        minion_id,
-                                        team,
-                                        lane,
-                                        self.minion_stats.clone(),
-                                    );
-                                    board.place_cell(
-                                        CellContent::Minion(minion_id, team),
-                                        minion.row as usize,
-                                        minion.col as usize,
-                                    );
-                                    self.minions.insert(minion_id, minion);
-                                }
-                                Lane::Bottom => {
-                                    let minion = Minion::new(
-                                        minion_id,
-                                        team,
-                                        lane,
-                                        self.minion_stats.clone(),
-                                    );
-                                    board.place_cell(
-                                        CellContent::Minion(minion_id, team),
-                                        minion.row as usize,
-                                        minion.col as usize,
-                                    );
-                                    self.minions.insert(minion_id, minion);
-                                }
-                            }
-                        }
-                    }
+            // Each call spawns the next minion of the wave in every lane
+            let step = self.minions_this_wave as usize;
+            let mut wave_done = true;
+            for lane in self.lanes.iter() {
+                let composition = self.lane_composition(lane.team, lane.lane);
+                if step + 1 < composition.len() {
+                    wave_done = false;
                 }
+                let Some(kind) = composition.get(step) else {
+                    continue;
+                };
+                let minion_stats = self
+                    .minion_stats
+                    .stats(*kind)
+                    .scaled(game_time, &self.minion_rules);
+                // A unit still stands on the spawn, the minion takes a free cell next to it
+                let Some((row, col)) = free_spawn_cell(board, lane.spawn) else {
+                    continue;
+                };
+                let minion_id = registry.spawn(EntityKind::Minion, Some(lane.team));
+                let mut minion = Minion::new(minion_id, lane, minion_stats);
+                (minion.row, minion.col) = (row, col);
+                board.place_cell(
+                    CellContent::Minion(minion_id, lane.team),
+                    minion.row as usize,
+                    minion.col as usize,
+                );
+                self.minions.insert(minion_id, minion);
             }
             // Stopping wave creation
             self.minions_this_wave += 1;
-            self.wave_creation_time = Instant::now() + Duration::from_millis(80);
-            if self.minions_this_wave >= self.minions_per_wave {
-                self.wave_creation_time = Instant::now() + Duration::from_secs(30);
+            self.wave_creation_time =
+                Instant::now() + Duration::from_millis(self.minion_rules.spawn_delay_ms);
+            if wave_done {
+                self.wave_creation_time = Instant::now() + self.wave_interval;
                 self.minions_this_wave = 0;
+                self.wave_number += 1;
             }
         }
     }
 
-    pub fn manage_minions_mouvements(&mut self, mut board: &mut Board) {
-        self.minions.iter_mut().for_each(|(_, minion)| {
-            let _ = minion.movement_phase(&mut board);
-        });
+    pub fn manage_minions_mouvements(&mut self, board: &mut Board, pathfinder: &mut Pathfinder) {
+        // Minions closest to their waypoint move first, so a queue moves up in a single tick
+        let mut order: Vec<(u32, MinionId)> = self
+            .minions
+            .iter()
+            .map(|(id, minion)| {
+                let distance = pathfinder
+                    .field(minion.lane_goal())
+                    .and_then(|field| field.distance((minion.row, minion.col)))
+                    .unwrap_or(u32::MAX);
+                (distance, *id)
+            })
+            .collect();
+        order.sort_unstable();
+
+        let mut traffic: HashMap<MinionId, Traffic> = self
+            .minions
+            .iter()
+            .map(|(id, minion)| (*id, minion.traffic))
+            .collect();
+        for (_, id) in order {
+            let Some(minion) = self.minions.get_mut(&id) else {
+                continue;
+            };
+            if let Ok(Some(ally_id)) = minion.movement_phase(board, pathfinder, &traffic)
+                && let [Some(minion), Some(ally)] = self.minions.get_disjoint_mut([&id, &ally_id])
+            {
+                minion.swap_with(ally, board);
+                traffic.insert(ally_id, ally.traffic);
+            }
+            if let Some(minion) = self.minions.get(&id) {
+                traffic.insert(id, minion.traffic);
+            }
+        }
     }
 
     pub fn manage_minions_attack(
         &mut self,
-        mut board: &mut Board,
+        board: &mut Board,
+        projectile_manager: &mut ProjectileManager,
         new_animations: &mut Vec<Box<dyn AnimationTrait>>,
-        pending_effects: &mut Vec<(Option<super::PlayerId>, Target, Vec<GameplayEffect>)>,
+        pending_effects: &mut Vec<(Target, Vec<GameplayEffect>)>,
     ) {
         self.minions.iter_mut().for_each(|(_, minion)| {
-            minion.attack_phase(&mut board, new_animations, pending_effects);
+            minion.attack_phase(board, projectile_manager, new_animations, pending_effects);
         });
     }
 }
 
-fn generate_minion_id() -> Result<MinionId, GameError> {
-    let mut rng = rand::rng();
-    let nums: Vec<usize> = (1..99999).collect();
-    if let Some(id) = nums.choose(&mut rng) {
-        Ok(*id)
-    } else {
-        Err(GameError::GenerateIdError)
+fn free_spawn_cell(board: &Board, spawn: (u16, u16)) -> Option<(u16, u16)> {
+    let is_free = |(row, col): (u16, u16)| {
+        board
+            .get_cell(row as usize, col as usize)
+            .is_some_and(|cell| cell.is_passable())
+    };
+    if is_free(spawn) {
+        return Some(spawn);
+    }
+    get_valid_neighbors(board, spawn.0, spawn.1)
+        .into_iter()
+        .next()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{AttackType, MinionStats, TimingRules, WaveGroup};
+    use crate::game::BaseTerrain;
+
+    fn create_minion_stats(health: u16) -> MinionStats {
+        MinionStats {
+            attack_damage: 10,
+            attack_speed_ms: 2500,
+            health,
+            armor: 0,
+            magic_resist: 0,
+            aggro_range_row: 10,
+            aggro_range_col: 10,
+            attack_range_row: 1,
+            attack_range_col: 1,
+            attack: AttackType::Melee,
+            movement_speed: 1,
+        }
+    }
+
+    fn create_roster() -> MinionRoster {
+        MinionRoster {
+            melee: create_minion_stats(40),
+            caster: create_minion_stats(25),
+            siege: create_minion_stats(90),
+            super_minion: create_minion_stats(150),
+        }
+    }
+
+    fn create_rules() -> Rules {
+        Rules {
+            timing_rules: TimingRules {
+                game_tick_ms: 40,
+                minion_wave_interval_secs: 30,
+            },
+            minion_rules: MinionRules {
+                spawn_delay_ms: 80,
+                super_minions_per_wave: 1,
+                scaling_interval_secs: 60,
+                health_scaling: 0.5,
+                attack_damage_scaling: 0.1,
+                waves: vec![
+                    WaveGroup {
+                        kind: MinionKind::Melee,
+                        count: 3,
+                        every: 1,
+                    },
+                    WaveGroup {
+                        kind: MinionKind::Siege,
+                        count: 1,
+                        every: 3,
+                    },
+                    WaveGroup {
+                        kind: MinionKind::Caster,
+                        count: 3,
+                        every: 1,
+                    },
+                ],
+            },
+            ..Default::default()
+        }
+    }
+
+    // One lane per team and lane, spawns mirror each other
+    fn create_lanes() -> Vec<LaneLayout> {
+        vec![
+            ((184, 10), Lane::Top),
+            ((184, 17), Lane::Mid),
+            ((191, 17), Lane::Bottom),
+        ]
+        .into_iter()
+        .flat_map(|((row, col), lane)| {
+            [
+                LaneLayout {
+                    team: Team::Blue,
+                    lane,
+                    spawn: (row, col),
+                    waypoints: vec![(col, row)],
+                },
+                LaneLayout {
+                    team: Team::Red,
+                    lane,
+                    spawn: (col, row),
+                    waypoints: vec![(row, col)],
+                },
+            ]
+        })
+        .collect()
+    }
+
+    // Spawns every minion of the current wave
+    fn spawn_full_wave(
+        manager: &mut MinionManager,
+        board: &mut Board,
+        registry: &mut EntityRegistry,
+        game_time: Duration,
+    ) {
+        let wave_number = manager.wave_number;
+        while manager.wave_number == wave_number {
+            manager.wave_creation_time = Instant::now() - Duration::from_millis(1);
+            manager.make_wave(board, registry, game_time);
+        }
+    }
+
+    fn count_minions_with_health(manager: &MinionManager, health: u16) -> usize {
+        manager
+            .minions
+            .values()
+            .filter(|minion| minion.stats.max_health == health)
+            .count()
+    }
+
+    // A corridor along row 2 of the given width, walls everywhere else. The
+    // board is as tall as the aggro range of the minions
+    fn create_corridor(cols: usize, width: usize) -> Board {
+        let mut board = Board::new(12, cols);
+        for row in 0..board.rows {
+            for col in 0..cols {
+                if !(2..2 + width).contains(&row) {
+                    board.change_base(BaseTerrain::Wall, row, col);
+                }
+            }
+        }
+        board
+    }
+
+    fn create_corridor_lane(
+        team: Team,
+        lane: Lane,
+        spawn: (u16, u16),
+        goal: (u16, u16),
+    ) -> LaneLayout {
+        LaneLayout {
+            team,
+            lane,
+            spawn,
+            waypoints: vec![goal],
+        }
+    }
+
+    // Spawns a full wave in every lane while the minions walk, then lets them walk for `ticks`
+    fn run_lanes(
+        manager: &mut MinionManager,
+        board: &mut Board,
+        lanes: &[LaneLayout],
+        ticks: usize,
+    ) {
+        let mut pathfinder = Pathfinder::new(100_000);
+        let mut registry = EntityRegistry::new();
+        for lane in lanes {
+            pathfinder.request_field(board, lane.waypoints[0]);
+        }
+        for _ in 0..ticks {
+            if manager.wave_number == 1 {
+                manager.wave_creation_time = Instant::now() - Duration::from_millis(1);
+                manager.make_wave(board, &mut registry, Duration::ZERO);
+            }
+            pathfinder.start_tick();
+            manager.manage_minions_mouvements(board, &mut pathfinder);
+            pathfinder.build_fields(board);
+        }
+    }
+
+    // Columns of the minions of a team on row 2, sorted
+    fn minion_columns(manager: &MinionManager, team: Team) -> Vec<u16> {
+        let mut columns: Vec<u16> = manager
+            .minions
+            .values()
+            .filter(|minion| minion.team_id == team)
+            .map(|minion| minion.col)
+            .collect();
+        columns.sort_unstable();
+        columns
+    }
+
+    #[test]
+    fn test_wave_queues_at_the_front_of_a_narrow_lane() {
+        let mut board = create_corridor(60, 1);
+        let lanes = vec![create_corridor_lane(Team::Blue, Lane::Mid, (2, 1), (2, 58))];
+        let mut manager = MinionManager::new(create_roster(), &create_rules(), lanes.clone());
+
+        run_lanes(&mut manager, &mut board, &lanes, 120);
+
+        // The whole wave stands in line behind the front minion
+        assert_eq!(
+            minion_columns(&manager, Team::Blue),
+            vec![52, 53, 54, 55, 56, 57]
+        );
+        assert!(
+            manager
+                .minions
+                .values()
+                .all(|minion| minion.traffic == Traffic::Settled)
+        );
+    }
+
+    #[test]
+    fn test_waves_cross_head_on_in_a_narrow_lane() {
+        let mut board = create_corridor(80, 1);
+        // Two lanes of the same team walk the corridor in opposite directions
+        let lanes = vec![
+            create_corridor_lane(Team::Blue, Lane::Top, (2, 2), (2, 78)),
+            create_corridor_lane(Team::Blue, Lane::Bottom, (2, 77), (2, 1)),
+        ];
+        let mut manager = MinionManager::new(create_roster(), &create_rules(), lanes.clone());
+
+        run_lanes(&mut manager, &mut board, &lanes, 300);
+
+        let mut top: Vec<u16> = Vec::new();
+        let mut bottom: Vec<u16> = Vec::new();
+        for minion in manager.minions.values() {
+            if minion.lane_goal() == (2, 78) {
+                top.push(minion.col);
+            } else {
+                bottom.push(minion.col);
+            }
+        }
+        top.sort_unstable();
+        bottom.sort_unstable();
+        assert_eq!(top, vec![72, 73, 74, 75, 76, 77]);
+        assert_eq!(bottom, vec![2, 3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn test_wave_goes_around_a_unit_standing_in_the_lane() {
+        let mut board = create_corridor(40, 2);
+        // A monster out of its camp stands in the middle of the lane
+        board.place_cell(CellContent::Monster(1), 2, 20);
+        let lanes = vec![create_corridor_lane(Team::Red, Lane::Mid, (2, 1), (2, 38))];
+        let mut manager = MinionManager::new(create_roster(), &create_rules(), lanes.clone());
+
+        run_lanes(&mut manager, &mut board, &lanes, 120);
+
+        // Every minion went past the monster and waits next to the waypoint
+        let columns = minion_columns(&manager, Team::Red);
+        assert_eq!(columns.len(), 6);
+        assert!(columns.iter().all(|col| *col > 30), "{:?}", columns);
+        assert!(
+            manager
+                .minions
+                .values()
+                .all(|minion| minion.traffic == Traffic::Settled)
+        );
+    }
+
+    #[test]
+    fn test_wave_composition() {
+        let rules = create_rules();
+        assert_eq!(
+            rules.minion_rules.wave_composition(1),
+            vec![
+                MinionKind::Melee,
+                MinionKind::Melee,
+                MinionKind::Melee,
+                MinionKind::Caster,
+                MinionKind::Caster,
+                MinionKind::Caster
+            ]
+        );
+        assert_eq!(
+            rules.minion_rules.wave_composition(3),
+            vec![
+                MinionKind::Melee,
+                MinionKind::Melee,
+                MinionKind::Melee,
+                MinionKind::Siege,
+                MinionKind::Caster,
+                MinionKind::Caster,
+                MinionKind::Caster
+            ]
+        );
+    }
+
+    #[test]
+    fn test_make_wave_spawns_siege_every_third_wave() {
+        let mut board = Board::new(200, 200);
+        let mut registry = EntityRegistry::new();
+        let mut manager = MinionManager::new(create_roster(), &create_rules(), create_lanes());
+
+        spawn_full_wave(&mut manager, &mut board, &mut registry, Duration::ZERO);
+        // 6 minions in each of the 3 lanes for both teams
+        assert_eq!(manager.minions.len(), 36);
+        assert_eq!(manager.wave_number, 2);
+        assert!(manager.wave_creation_time > Instant::now() + Duration::from_secs(29));
+
+        manager.minions.clear();
+        board = Board::new(200, 200);
+        spawn_full_wave(&mut manager, &mut board, &mut registry, Duration::ZERO);
+        assert_eq!(count_minions_with_health(&manager, 90), 0);
+
+        manager.minions.clear();
+        board = Board::new(200, 200);
+        spawn_full_wave(&mut manager, &mut board, &mut registry, Duration::ZERO);
+        assert_eq!(manager.minions.len(), 42);
+        assert_eq!(count_minions_with_health(&manager, 90), 6);
+    }
+
+    #[test]
+    fn test_make_wave_spawns_super_minions_in_cleared_lane() {
+        let mut board = Board::new(200, 200);
+        let mut registry = EntityRegistry::new();
+        let mut manager = MinionManager::new(create_roster(), &create_rules(), create_lanes());
+        manager.enable_super_minions(Team::Blue, Lane::Mid);
+
+        spawn_full_wave(&mut manager, &mut board, &mut registry, Duration::ZERO);
+
+        assert_eq!(manager.minions.len(), 37);
+        let super_minions: Vec<&Minion> = manager
+            .minions
+            .values()
+            .filter(|minion| minion.stats.max_health == 150)
+            .collect();
+        assert_eq!(super_minions.len(), 1);
+        assert_eq!(super_minions[0].team_id, Team::Blue);
+    }
+
+    #[test]
+    fn test_minion_stats_scale_with_game_time() {
+        let mut board = Board::new(200, 200);
+        let mut registry = EntityRegistry::new();
+        let mut manager = MinionManager::new(create_roster(), &create_rules(), create_lanes());
+
+        // Two scaling intervals elapsed: +100% health, +20% damage
+        spawn_full_wave(
+            &mut manager,
+            &mut board,
+            &mut registry,
+            Duration::from_secs(150),
+        );
+
+        assert_eq!(count_minions_with_health(&manager, 80), 18);
+
+        let scaled =
+            create_minion_stats(40).scaled(Duration::from_secs(150), &create_rules().minion_rules);
+        assert_eq!(scaled.attack_damage, 12);
     }
 }
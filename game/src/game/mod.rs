@@ -4,45 +4,66 @@ pub mod board;
 pub mod buffs;
 pub mod cell;
 pub mod entities;
+pub mod input;
+pub mod map;
+pub mod map_check;
+pub mod map_generator;
 pub mod minion_manager;
 pub mod monster_manager;
 pub mod projectile_manager;
+pub mod registry;
+pub mod scoreboard;
 pub mod spell;
-
-use crate::config::GameConfig;
-use crate::packet::board_packet::BoardPacket;
-use animation::{AnimationCommand, AnimationTrait};
+pub mod systems;
+
+use crate::config::{GameConfig, TowerTier};
+use crate::errors::{GameError, MapError};
+use crate::metrics::TickMetrics;
+use crate::packet::scoreboard_packet::ScoreEntry;
+use crate::tick_clock::TickTiming;
+use algorithms::pathfinding::Pathfinder;
+use animation::AnimationTrait;
 pub use board::Board;
-use buffs::Buff;
+use buffs::kill_buff::KillBuff;
 use bytes::BytesMut;
 use cell::Team;
-pub use cell::{BaseTerrain, Cell, CellContent, MinionId, PlayerId, TowerId};
+pub use cell::{BaseTerrain, Cell, CellContent, EntityId, MinionId, MonsterId, PlayerId, TowerId};
 pub use entities::champion::{Action, Champion};
 use entities::{
-    AttackAction, Fighter, Target,
-    base::Base,
-    projectile::GameplayEffect,
-    tower::{Tower, generate_tower_id},
+    AttackAction, Fighter, base::Base, damage::DamageSource, minion::Lane, monster::MonsterTarget,
+    projectile::GameplayEffect, tower::Tower,
 };
+use log::{debug, info, warn};
+use map::{BASE_SIZE, MapData, MapObjects};
 use minion_manager::MinionManager;
-use monster_manager::MonsterManager;
+use monster_manager::{MonsterKill, MonsterManager};
 use projectile_manager::ProjectileManager;
+use registry::{EntityKind, EntityRegistry};
+use scoreboard::Scoreboard;
 use spell::Spell;
-use tokio::sync::mpsc;
+use systems::System;
+use tokio::sync::{mpsc, oneshot};
 
 use std::{
-    collections::HashMap,
-    mem::take,
+    collections::{HashMap, HashSet},
     time::{Duration, Instant},
-    usize, vec,
+    vec,
 };
 
 pub type ClientMessage = BytesMut;
 
+/// Ticks between two scoreboard packets, a second at the 40ms tick
+const SCOREBOARD_INTERVAL_TICKS: u64 = 25;
+
+/// Ticks between two tick rate packets, a second at the 40ms tick
+const TICK_RATE_INTERVAL_TICKS: u64 = 25;
+
 pub struct GameManager {
     players_count: usize,
     max_players: usize,
     pub game_started: bool,
+    /// Set once a base is destroyed, the tick loop then stops the server
+    game_over: bool,
     player_action: HashMap<PlayerId, Action>,
     champions: HashMap<PlayerId, Champion>,
     towers: HashMap<TowerId, Tower>,
@@ -53,76 +74,114 @@ pub struct GameManager {
     projectile_manager: ProjectileManager,
     animations: Vec<Box<dyn AnimationTrait>>,
     pub client_channel: HashMap<PlayerId, mpsc::Sender<ClientMessage>>,
+    writers_flushed: HashMap<PlayerId, oneshot::Receiver<()>>,
     board: Board,
     pub tick: u64,
     dead_minion_positions: Vec<(u16, u16, Team)>,
     config: GameConfig,
+    map_objects: MapObjects,
     game_start_time: Option<Instant>,
     initial_monsters_spawned: bool,
+    pathfinder: Pathfinder,
+    registry: EntityRegistry,
+    scoreboard: Scoreboard,
+    scoreboard_requests: HashSet<PlayerId>,
+    tick_metrics: TickMetrics,
+    tick_period: Duration,
+    tick_timing: TickTiming,
 }
 
 impl GameManager {
-    pub fn new(config: GameConfig) -> Self {
-        println!("Initializing GameManager...");
-        let file_path = "game/assets/map.json";
-        let mut board = match Board::from_json(file_path) {
-            Ok(board) => board,
-            Err(e) => {
-                eprintln!("Failed to initialize the board from {}: {}", file_path, e);
-                std::process::exit(1);
-            }
-        };
+    pub fn new(config: GameConfig, map: MapData) -> Result<Self, MapError> {
+        info!("Initializing GameManager...");
+        let max_players = 1;
+        // Every player is in the blue team for now
+        if !map.supports_team_size(max_players) {
+            return Err(MapError::UnsupportedTeamSize(max_players));
+        }
+        let mut board = Board::from_map(&map);
+        let map_objects = map.objects;
+
+        let mut registry = EntityRegistry::new();
         let mut towers: HashMap<TowerId, Tower> = HashMap::new();
         // Tower placement
-        {
-            let placement = vec![
-                (196, 150),
-                (39, 7),
-                (115, 82),
-                (191, 79),
-                (120, 8),
-                (148, 67),
-            ];
-            // Bottom t1
-            placement.into_iter().for_each(|place| {
-                let id = generate_tower_id().unwrap();
-                let tower_blue = Tower::new(id, Team::Blue, place.0, place.1, config.tower.clone());
-                tower_blue.place_tower(&mut board);
-                let id = generate_tower_id().unwrap();
-                let tower_red = Tower::new(id, Team::Red, place.1, place.0, config.tower.clone());
-                tower_red.place_tower(&mut board);
-                towers.insert(tower_blue.tower_id, tower_blue);
-                towers.insert(tower_red.tower_id, tower_red);
-            });
+        for layout in map_objects.towers.iter() {
+            let id = registry.spawn(EntityKind::Tower, Some(layout.team));
+            let tower = Tower::new(
+                id,
+                layout.team,
+                layout.lane,
+                layout.tier,
+                layout.position.0,
+                layout.position.1,
+                config.tower.stats(layout.tier).clone(),
+            );
+            tower.place_tower(&mut board);
+            towers.insert(tower.tower_id, tower);
         }
 
-        let red_base = Base::new(Team::Red, (190, 10), config.base.clone());
-        let blue_base = Base::new(Team::Blue, (10, 190), config.base.clone());
+        let (blue_base, red_base) =
+            match (map_objects.base(Team::Blue), map_objects.base(Team::Red)) {
+                (Some(blue), Some(red)) => (
+                    Base::new(
+                        Team::Blue,
+                        (blue.position.0 as i32, blue.position.1 as i32),
+                        config.base.clone(),
+                    ),
+                    Base::new(
+                        Team::Red,
+                        (red.position.0 as i32, red.position.1 as i32),
+                        config.base.clone(),
+                    ),
+                ),
+                (None, _) => return Err(MapError::MissingObject("base".to_string(), Team::Blue)),
+                (_, None) => return Err(MapError::MissingObject("base".to_string(), Team::Red)),
+            };
 
-        for i in 0..3 {
-            for j in 0..3 {
-                board.place_cell(
-                    CellContent::Base(Team::Blue),
-                    (red_base.position.0 + i) as usize,
-                    (red_base.position.1 + j) as usize,
-                );
-                board.place_cell(
-                    CellContent::Base(Team::Red),
-                    (blue_base.position.0 + i) as usize,
-                    (blue_base.position.1 + j) as usize,
-                );
+        for base in [&blue_base, &red_base] {
+            for i in 0..BASE_SIZE as i32 {
+                for j in 0..BASE_SIZE as i32 {
+                    board.place_cell(
+                        CellContent::Base(base.team),
+                        (base.position.0 + i) as usize,
+                        (base.position.1 + j) as usize,
+                    );
+                }
             }
         }
 
-        let minion_manager = MinionManager::new(config.minion.clone());
-        let monster_manager = MonsterManager::new(config.neutral_monsters.clone());
+        let minion_manager = MinionManager::new(
+            config.minions.clone(),
+            &config.rules,
+            map_objects.lanes.clone(),
+        );
+        let monster_manager = MonsterManager::new(
+            config.neutral_monsters.clone(),
+            config.neutral_camps.clone(),
+            map_objects.camps.clone(),
+        );
         let projectile_manager = ProjectileManager::new();
 
-        GameManager {
+        // Minions walk to the waypoints of their lane and monsters back to their camp
+        let mut pathfinder = Pathfinder::new(config.rules.pathfinding_rules.nodes_per_tick);
+        for lane in map_objects.lanes.iter() {
+            for waypoint in lane.waypoints.iter() {
+                pathfinder.request_field(&board, *waypoint);
+            }
+        }
+        for camp in map_objects.camps.iter() {
+            pathfinder.request_field(&board, camp.position);
+        }
+
+        let assist_window_secs = config.rules.champion_rules.assist_window_secs;
+        let tick_period = config.rules.timing_rules.tick_period();
+        let mut manager = GameManager {
             players_count: 0,
-            max_players: 1,
+            max_players,
             game_started: false,
+            game_over: false,
             config,
+            map_objects,
             player_action: HashMap::new(),
             champions: HashMap::new(),
             towers,
@@ -133,64 +192,102 @@ impl GameManager {
             projectile_manager,
             animations: Vec::new(),
             client_channel: HashMap::new(),
+            writers_flushed: HashMap::new(),
             board,
             tick: 20,
             dead_minion_positions: Vec::new(),
             game_start_time: None,
             initial_monsters_spawned: false,
-        }
+            pathfinder,
+            registry,
+            scoreboard: Scoreboard::new(Duration::from_secs(assist_window_secs)),
+            scoreboard_requests: HashSet::new(),
+            tick_metrics: TickMetrics::default(),
+            tick_period,
+            tick_timing: TickTiming {
+                rate: 1.0 / tick_period.as_secs_f64(),
+                ..TickTiming::default()
+            },
+        };
+        manager.update_protections();
+        Ok(manager)
     }
 
     pub fn print_game_state(&self) {
-        println!(
+        debug!(
             "Player connected: {}/{}",
             self.players_count, self.max_players
         );
-        if self.player_action.is_empty() {
-            println!("No action received");
-        } else {
-            for (player_id, action) in &self.player_action {
-                println!("Player: {} / Action: {:?}", player_id, action);
-            }
+        for (player_id, action) in &self.player_action {
+            debug!("Player: {} / Action: {:?}", player_id, action);
         }
-        println!("Board size: {}.{}", self.board.rows, self.board.cols);
+    }
+
+    pub fn is_game_over(&self) -> bool {
+        self.game_over
+    }
+
+    /// Time between two ticks, from the timing rules
+    pub fn tick_period(&self) -> Duration {
+        self.tick_period
+    }
+
+    /// Timing of the coming tick given by the tick clock, reported to the
+    /// players and in the metrics
+    pub fn set_tick_timing(&mut self, timing: TickTiming) {
+        self.tick_timing = timing;
     }
 
     pub fn clear_action(&mut self) {
         self.player_action.clear();
     }
 
-    pub fn add_player(&mut self, spell1_id: u8, spell2_id: u8) -> Option<PlayerId> {
-        if self.players_count < self.max_players {
+    pub fn add_player(
+        &mut self,
+        champion_id: u8,
+        spell1_id: u8,
+        spell2_id: u8,
+    ) -> Result<PlayerId, GameError> {
+        let kit = self
+            .config
+            .champion_kit(champion_id)
+            .ok_or(GameError::UnknownChampion(champion_id))?;
+        // Champions spawn on the first free cell of their team spawn zone
+        let spawn = self
+            .map_objects
+            .spawn_zone(Team::Blue)
+            .and_then(|zone| zone.free_cell(&self.board));
+        if self.players_count < self.max_players
+            && let Some((row, col)) = spawn
+        {
             self.players_count += 1;
-            let player_id = self.players_count;
+            let player_id = self.registry.spawn(EntityKind::Champion, Some(Team::Blue));
             // Assign Champion to player, and place it on the board
             {
-                // We get the choosen spell
+                // We get the choosen spell, keyed by the action slot casting it.
+                // Signature spells cannot be picked, the slot stays empty
                 let mut selected_spell: HashMap<u8, Box<dyn Spell>> = HashMap::new();
-                if let Some(spell_stats) = self.config.spells.get(&spell1_id) {
-                    selected_spell.insert(
-                        spell1_id,
-                        spell::create_spell_from_id(spell1_id, spell_stats.clone()),
-                    );
+                for (slot, spell_id) in [(0, spell1_id), (1, spell2_id)] {
+                    if let Some(spell_stats) = self.config.spells.get(&spell_id)
+                        && !spell_stats.signature
+                    {
+                        selected_spell.insert(slot, spell::create_spell(spell_stats.clone()));
+                    }
                 }
-                if let Some(spell_stats) = self.config.spells.get(&spell2_id) {
-                    selected_spell.insert(
-                        spell2_id,
-                        spell::create_spell_from_id(spell2_id, spell_stats.clone()),
-                    );
+                // The signature spell of the champion is always in the last slot
+                if let Some(spell_stats) = self.config.spells.get(&kit.signature_spell) {
+                    selected_spell.insert(2, spell::create_spell(spell_stats.clone()));
                 }
-                let row = 199;
-                let col = 0;
                 let champion = Champion::new(
                     player_id,
                     Team::Blue,
                     row,
                     col,
-                    self.config.champion.clone(),
+                    kit.stats.clone(),
                     selected_spell,
                 );
                 self.champions.insert(player_id, champion);
+                self.scoreboard.add_player(player_id);
                 self.board.place_cell(
                     cell::CellContent::Champion(player_id, Team::Blue),
                     row as usize,
@@ -202,20 +299,22 @@ impl GameManager {
             if self.players_count == self.max_players {
                 self.game_started = true;
                 self.game_start_time = Some(Instant::now());
-                self.minion_manager.wave_creation_time = Instant::now() + Duration::from_secs(30);
+                self.minion_manager.wave_creation_time =
+                    Instant::now() + self.minion_manager.wave_interval();
             }
-            Some(player_id)
+            Ok(player_id)
         } else {
-            None
+            Err(GameError::GameFull)
         }
     }
 
     pub fn remove_player(&mut self, player_id: &PlayerId) {
         if self.players_count > 0 {
             self.players_count -= 1;
-            self.player_action.remove(&player_id);
-            self.client_channel.remove(&player_id);
-            println!(
+            self.player_action.remove(player_id);
+            self.client_channel.remove(player_id);
+            self.writers_flushed.remove(player_id);
+            info!(
                 "Player {} disconnected. Total player now: {}/{}",
                 player_id, self.players_count, self.max_players
             );
@@ -223,7 +322,7 @@ impl GameManager {
                 self.game_started = false;
             }
         } else {
-            println!("Warning: Tried to remove player, but player count already at 0.");
+            warn!("Tried to remove player, but player count already at 0.");
         }
     }
 
@@ -233,468 +332,212 @@ impl GameManager {
             2 => Action::MoveDown,
             3 => Action::MoveLeft,
             4 => Action::MoveRight,
+            12 => Action::MoveUpLeft,
+            13 => Action::MoveUpRight,
+            14 => Action::MoveDownLeft,
+            15 => Action::MoveDownRight,
             5 => Action::Action1,
             6 => Action::Action2,
+            7 => Action::AttackMode,
+            8 => Action::LevelUpSpell1,
+            9 => Action::LevelUpSpell2,
+            10 => Action::Action3,
+            11 => Action::LevelUpSpell3,
+            16 => {
+                // Not an action of the champion, answered with the next board update
+                self.scoreboard_requests.insert(player_id);
+                return;
+            }
             _other => Action::InvalidAction,
         };
         self.player_action.insert(player_id, action);
     }
 
-    pub async fn send_to_player(&self, player_id: PlayerId, message: ClientMessage) {
-        println!("Send_to_player message lenght: {}", message.len());
-        if let Some(sender) = self.client_channel.get(&player_id) {
-            let sender_clone = sender.clone();
-            // We use spawn to send without blocking the game manager lock
-            tokio::spawn(async move {
-                if let Err(e) = sender_clone.send(message).await {
-                    eprintln!("Error sending message to player {}: {}", player_id, e);
-                }
-            });
-        } else {
-            eprintln!(
-                "Attempted to send message to disconnected or non-existent player {}",
-                player_id
-            );
-        }
+    pub fn store_move_order(
+        &mut self,
+        player_id: PlayerId,
+        destination: (u16, u16),
+        attack_move: bool,
+    ) {
+        self.player_action.insert(
+            player_id,
+            Action::MoveTo {
+                destination,
+                attack_move,
+            },
+        );
     }
 
     pub fn game_tick(&mut self) -> HashMap<PlayerId, ClientMessage> {
-        if let Some(start_time) = self.game_start_time {
-            if !self.initial_monsters_spawned && start_time.elapsed() >= Duration::from_secs(5) {
-                self.monster_manager.spawn_initial_monsters(&mut self.board);
-                self.initial_monsters_spawned = true;
-            }
-        }
+        self.run_systems(&System::ALL)
+    }
 
-        self.tick = self.tick.saturating_add(1);
-        println!("---- Game Tick -----");
-        self.print_game_state();
-
-        let mut updates = HashMap::new();
-        let mut new_animations: Vec<Box<dyn AnimationTrait>> = Vec::new();
-        let mut animation_commands_executable: Vec<AnimationCommand> = Vec::new();
-        let mut pending_effects: Vec<(Option<PlayerId>, Target, Vec<GameplayEffect>)> = Vec::new();
-        let mut xp_rewards: Vec<(PlayerId, u8)> = Vec::new();
-
-        // --- Game Logic ---
-        // Buff checks on all entities
-        // Champions
-        for (_, champ) in self.champions.iter_mut() {
-            let current_buff = take(&mut champ.active_buffs);
-            let mut kept_buff: HashMap<String, Box<dyn Buff>> = HashMap::new();
-            for (id, mut buff) in current_buff.into_iter() {
-                if buff.on_tick(champ) {
-                    buff.on_remove(champ);
-                } else {
-                    kept_buff.insert(id, buff);
-                }
-            }
-            champ.active_buffs = kept_buff;
-        }
+    fn tower_turn(&mut self) {
+        let mut projectiles_to_create = Vec::new();
 
-        // Minions
-        for (_, minion) in self.minion_manager.minions.iter_mut() {
-            let current_buff = take(&mut minion.active_buffs);
-            let mut kept_buff: HashMap<String, Box<dyn Buff>> = HashMap::new();
-            for (id, mut buff) in current_buff.into_iter() {
-                if buff.on_tick(minion) {
-                    buff.on_remove(minion);
-                } else {
-                    kept_buff.insert(id, buff);
-                }
+        for (_, tower) in self.towers.iter_mut() {
+            if let Some(target) = tower.acquire_target(&self.board)
+                && let Some(attack_action) = tower.can_attack()
+                && let AttackAction::Projectile {
+                    damage,
+                    speed,
+                    visual,
+                } = attack_action
+            {
+                let damage = damage.with_amount(tower.hit_damage(damage.amount));
+                projectiles_to_create.push((tower.tower_id, target, damage, speed, visual));
             }
-            minion.active_buffs = kept_buff;
         }
-
-        // --- Turn ---
-        // Player turn
-        for (player_id, champ) in &mut self.champions {
-            // 0. Check death and replace
-            // BUG: Champ dead can still move but is replace each tick
-            if champ.is_dead() {
-                champ.put_at_max_health();
-                champ.place_at_base(&mut self.board);
-                continue;
-            }
-            // 1. Iterate through player action
-            if let Some(action) = self.player_action.get(&player_id) {
-                if let Err(e) =
-                    champ.take_action(action, &mut self.board, &mut self.projectile_manager)
-                {
-                    println!("Error on player action: {}", e);
-                }
-            }
-
-            // 2. auto_attack
-            if let Some(enemy) = champ.get_potential_target(&self.board) {
-                match &enemy.content {
-                    Some(content) => {
-                        println!("Got content: {:?}", content);
-                        match content {
-                            CellContent::Tower(id, _) => {
-                                if let Some(attack) = champ.can_attack() {
-                                    match attack {
-                                        AttackAction::Melee { damage, animation } => {
-                                            new_animations.push(animation);
-                                            pending_effects.push((
-                                                Some(*player_id),
-                                                Target::Tower(*id),
-                                                vec![GameplayEffect::Damage(damage)],
-                                            ))
-                                        }
-                                        _ => {}
-                                    }
-                                }
-                            }
-                            CellContent::Monster(id) => {
-                                if let Some(attack) = champ.can_attack() {
-                                    match attack {
-                                        AttackAction::Melee { damage, animation } => {
-                                            new_animations.push(animation);
-                                            pending_effects.push((
-                                                Some(*player_id),
-                                                Target::Monster(*id),
-                                                vec![GameplayEffect::Damage(damage)],
-                                            ))
-                                        }
-                                        _ => {}
-                                    }
-                                }
-                            }
-                            CellContent::Minion(id, _) => {
-                                if let Some(attack) = champ.can_attack() {
-                                    match attack {
-                                        AttackAction::Melee { damage, animation } => {
-                                            new_animations.push(animation);
-                                            pending_effects.push((
-                                                Some(*player_id),
-                                                Target::Minion(*id),
-                                                vec![GameplayEffect::Damage(damage)],
-                                            ))
-                                        }
-                                        _ => {}
-                                    }
-                                }
-                            }
-                            CellContent::Champion(id, _) => {
-                                if let Some(attack) = champ.can_attack() {
-                                    match attack {
-                                        AttackAction::Melee { damage, animation } => {
-                                            new_animations.push(animation);
-                                            pending_effects.push((
-                                                Some(*player_id),
-                                                Target::Champion(*id),
-                                                vec![GameplayEffect::Damage(damage)],
-                                            ))
-                                        }
-                                        _ => {}
-                                    }
-                                }
-                            }
-                            CellContent::Base(team) => {
-                                if let Some(attack) = champ.can_attack() {
-                                    match attack {
-                                        AttackAction::Melee { damage, animation } => {
-                                            new_animations.push(animation);
-                                            pending_effects.push((
-                                                Some(*player_id),
-                                                Target::Base(*team),
-                                                vec![GameplayEffect::Damage(damage)],
-                                            ))
-                                        }
-                                        _ => {}
-                                    }
-                                }
-                            }
-                            _ => break,
-                        }
-                    }
-                    None => break,
-                }
+        // We create the projectiles
+        for (tower_id, target, damage, speed, visual) in projectiles_to_create {
+            if let Some(tower) = self.towers.get(&tower_id) {
+                self.projectile_manager.create_homing_projectile(
+                    tower.tower_id,
+                    tower.team_id,
+                    target,
+                    (tower.row, tower.col),
+                    speed,
+                    vec![GameplayEffect::Damage(damage)],
+                    visual,
+                );
             }
         }
+    }
 
-        // Minion mouvement turn
-        self.minion_manager
-            .manage_minions_mouvements(&mut self.board);
-        self.minion_manager.make_wave(&mut self.board);
-        println!(
-            "Minions: {} | Minions per wave {} | Tick: {}",
-            self.minion_manager.minions.len(),
-            self.minion_manager.minions_this_wave,
-            self.tick,
-        );
-
-        // Adding minion damages dealt
-        self.minion_manager.manage_minions_attack(
-            &mut self.board,
-            &mut new_animations,
-            &mut pending_effects,
-        );
-
-        // Monster turn
-        let (monster_effects, monster_animations) = self.monster_manager.update(&mut self.board, &self.champions);
-        pending_effects.extend(monster_effects.into_iter().map(|(target, effects)| (None, target, effects)));
-        new_animations.extend(monster_animations);
-
-        // Tower turn
-        // 1. Scan range
-        // 2. attack closest enemy
-        self.tower_turn();
-
-        let (projectile_effects, projectile_commands) =
-            self.projectile_manager.update_and_check_collisions(
-                &self.board,
-                &self.champions,
-                &self.minion_manager.minions,
-                &self.towers,
-                &self.monster_manager.active_monsters,
-            );
-        pending_effects.extend(projectile_effects.into_iter().map(|(owner, target, effects)| (Some(owner), target, effects)));
-        animation_commands_executable.extend(projectile_commands);
-
-        // 3. Apply dealt damages
-        pending_effects
-            .into_iter()
-            .for_each(|(attacker_id, target, effect)| match target {
-                Target::Tower(id) => {
-                    if let Some(tower) = self.towers.get_mut(&id) {
-                        tower.take_effect(effect);
-                        if tower.is_destroyed() {
-                            tower.destroy_tower(&mut self.board);
-                            self.towers.remove(&id);
-                        }
-                    }
-                }
-                Target::Minion(id) => {
-                    if let Some(minion) = self.minion_manager.minions.get_mut(&id) {
-                        minion.take_effect(effect);
-                        self.handle_minion_death(&id);
-                    }
-                }
-                Target::Champion(id) => {
-                    if let Some(champ) = self.champions.get_mut(&id) {
-                        champ.take_effect(effect);
-                    }
-                }
-                Target::Base(team) => match team {
-                    Team::Red => self.red_base.take_effect(effect),
-                    Team::Blue => self.blue_base.take_effect(effect),
-                },
-                Target::Monster(id) => {
-                    if let Some(..) = self.monster_manager.active_monsters.get_mut(&id) {
-                        if let Some(attacker) = attacker_id {
-                            if let Some(reward) = self.monster_manager.apply_effects_to_monster(&id, effect, attacker) {
-                                xp_rewards.push(reward);
-                            }
-
-                        }
-                    }
-                }
-            });
-
-        // Distribute XP from dead monster
-        for (player_id, xp_reward) in xp_rewards.into_iter() {
-            if let Some(champion) = self.champions.get_mut(&player_id) {
-                champion.add_xp(xp_reward as u32);
-            }
+    /// Towers defend an allied champion hit by an enemy champion, when both
+    /// stand in their range
+    fn alert_towers(&mut self, attacker_id: PlayerId, victim_id: PlayerId) {
+        let (Some(attacker), Some(victim)) = (
+            self.champions.get(&attacker_id),
+            self.champions.get(&victim_id),
+        ) else {
+            return;
+        };
+        if attacker.team_id == victim.team_id {
+            return;
         }
-        // Distribute XP from dead minions
-        for (minion_row, minion_col, minion_team) in self.dead_minion_positions.drain(..) {
-            let mut champions_in_range = Vec::new();
-            for (_, champion) in self.champions.iter_mut() {
-                // Check if champion is in 5x5 range and is on the opposing team
-                if champion.team_id != minion_team
-                    && (champion.row as i32 - minion_row as i32).abs() <= 2
-                    && (champion.col as i32 - minion_col as i32).abs() <= 2
-                {
-                    champions_in_range.push(champion);
-                }
-            }
-
-            if !champions_in_range.is_empty() {
-                let xp_per_champion = 5 / champions_in_range.len() as u32;
-                for champion in champions_in_range {
-                    champion.add_xp(xp_per_champion);
-                }
+        for tower in self.towers.values_mut() {
+            if tower.team_id == victim.team_id
+                && tower.in_range((victim.row, victim.col))
+                && tower.in_range((attacker.row, attacker.col))
+            {
+                tower.call_for_help(attacker_id);
             }
         }
+    }
 
-        // Render animation
-        let mut kept_animations: Vec<Box<dyn AnimationTrait>> = Vec::new();
-
-        // 1. clear past frame animation
-        for anim in &self.animations {
-            if let Some((row, col)) = anim.get_last_drawn_pos() {
-                animation_commands_executable.push(AnimationCommand::Clear { row, col })
-            }
+    /// A tower is invulnerable while a tower of a lower tier stands in its
+    /// lane, a base until one lane of its team has no tower left
+    fn update_protections(&mut self) {
+        let standing: Vec<(Team, Lane, TowerTier)> = self
+            .towers
+            .values()
+            .map(|tower| (tower.team_id, tower.lane, tower.tier))
+            .collect();
+        for tower in self.towers.values_mut() {
+            tower.set_protected(standing.iter().any(|(team, lane, tier)| {
+                *team == tower.team_id && *lane == tower.lane && *tier < tower.tier
+            }));
         }
-        // 2. Process next frame animations
-        for mut anim in self.animations.drain(..) {
-            let owner_pos = if let Some(champ) = self.champions.get(&anim.get_owner_id()) {
-                Some((champ.row, champ.col))
-            } else if let Some(tower) = self.towers.get(&anim.get_owner_id()) {
-                Some((tower.row, tower.col))
-            } else if let Some(minion) = self.minion_manager.minions.get(&anim.get_owner_id()) {
-                Some((minion.row, minion.col))
-            } else {
-                None // Owner might have been removed
-            };
-
-            if let Some((owner_row, owner_col)) = owner_pos {
-                let command = anim.next_frame(owner_row, owner_col);
-                match command {
-                    AnimationCommand::Done => {}
-                    AnimationCommand::Draw { .. } => {
-                        animation_commands_executable.push(command);
-                        kept_animations.push(anim);
-                    }
-                    AnimationCommand::Clear { .. } => {
-                        // This command should be handle before
-                    }
-                }
-            } else {
-                // Owner is gone, animation should finish and clear in its last frame
-            }
+        for base in [&mut self.blue_base, &mut self.red_base] {
+            let mut lanes = self
+                .map_objects
+                .lanes
+                .iter()
+                .filter(|lane| lane.team == base.team)
+                .peekable();
+            let no_lane = lanes.peek().is_none();
+            let lane_cleared = lanes.any(|lane| {
+                !standing
+                    .iter()
+                    .any(|(team, other, _)| *team == base.team && *other == lane.lane)
+            });
+            base.protected = !(no_lane || lane_cleared);
         }
-        kept_animations.extend(new_animations);
-        self.animations = kept_animations;
+    }
 
-        // 3. Execute animation command
-        for command in animation_commands_executable {
-            match command {
-                AnimationCommand::Draw {
-                    row,
-                    col,
-                    animation_type,
-                } => {
-                    // Add bounds check
-                    if row < self.board.rows as u16 && col < self.board.cols as u16 {
-                        self.board
-                            .place_animation(animation_type, row as usize, col as usize);
-                    } else {
-                        eprintln!("Animation draw position ({}, {}) out of bounds!", row, col);
-                    }
-                }
-                AnimationCommand::Clear { row, col } => {
-                    if row < self.board.rows as u16 && col < self.board.cols as u16 {
-                        self.board.clean_animation(row as usize, col as usize);
-                    } else {
-                        eprintln!("Animation clear position ({}, {}) out of bounds!", row, col);
-                    }
-                }
-                AnimationCommand::Done => {
-                    // This command should be handled in the loop above, not executed on the board
-                }
-            }
+    /// Monsters fight back champions and minions, towers and other monsters
+    /// only deal damage
+    fn monster_attacker(&self, source: Option<DamageSource>) -> Option<MonsterTarget> {
+        match source? {
+            DamageSource::Champion(id) => Some(MonsterTarget::Champion(id)),
+            DamageSource::Minion(id) => Some(MonsterTarget::Minion(id)),
+            DamageSource::Tower(_) | DamageSource::Monster(_) => None,
         }
+    }
 
-        // Check for win condition
-        if self.red_base.stats.health <= 0 {
-            println!("Sending EndGamePacket: Red base destroyed, Blue team wins!");
-            let packet = crate::packet::end_game_packet::EndGamePacket::new(Team::Red);
-            println!("EndGamePacket: {:?}", packet);
-            let serialized_packet = packet.serialize();
-            for (player_id, _) in &self.client_channel {
-                self.send_to_player(*player_id, BytesMut::from(&serialized_packet[..]));
-            }
-            std::process::exit(0);
-        } else if self.blue_base.stats.health <= 0 {
-            println!("Sending EndGamePacket: Blue base destroyed, Red team wins!");
-            let packet = crate::packet::end_game_packet::EndGamePacket::new(Team::Blue);
-            println!("EndGamePacket: {:?}", packet);
-            let serialized_packet = packet.serialize();
-            for (player_id, _) in &self.client_channel {
-                self.send_to_player(*player_id, BytesMut::from(&serialized_packet[..]));
+    /// The killer gets the XP, the buff goes to the killer or its whole team.
+    /// A killer that died meanwhile keeps the XP but not the buff
+    fn reward_monster_kill(&mut self, kill: MonsterKill) {
+        let Some(killer) = self.champions.get_mut(&kill.killer) else {
+            return;
+        };
+        self.scoreboard.record_monster_kill(kill.killer);
+        killer.add_xp(kill.xp as u32);
+        let team = killer.team_id;
+        if let Some(buff) = kill.buff {
+            for (player_id, champion) in self.champions.iter_mut() {
+                if !champion.is_dead()
+                    && (*player_id == kill.killer || (buff.team_wide && champion.team_id == team))
+                {
+                    self.registry.add_buff(
+                        *player_id,
+                        Box::new(KillBuff::new(buff.clone())),
+                        champion,
+                    );
+                }
             }
-            std::process::exit(0);
         }
+    }
 
-        // --- Send per player there board view ---
-        for (player_id, champion) in &self.champions {
-            // 1. Get player-specific board view
-            let board_rle_vec =
-                self.board
-                    .run_length_encode(champion.row, champion.col, &self.minion_manager);
-            // 2. Create the board packet
-            let health = champion.get_health();
-            let xp_needed = champion.xp_for_next_level().unwrap_or(0); // Get XP needed, 0 if max level
-            let board_packet = BoardPacket::new(
-                health.0,
-                health.1,
-                champion.stats.mana,
-                champion.stats.max_mana,
-                champion.level,
-                champion.xp,
-                xp_needed,
-                board_rle_vec,
-            );
-            let serialized_packet = board_packet.serialize();
-            // 3. Store the serialized packet to be sent later
-            updates.insert(*player_id, serialized_packet);
+    /// Champion behind an attack, `None` for minions, towers and monsters
+    fn champion_attacker(&self, source: Option<DamageSource>) -> Option<PlayerId> {
+        match source? {
+            DamageSource::Champion(id) => Some(id),
+            _ => None,
         }
-        println!("--------------------");
-        updates
     }
 
-    fn tower_turn(&mut self) {
-        let mut projectiles_to_create = Vec::new();
+    fn score_entries(&self) -> Vec<ScoreEntry> {
+        self.scoreboard
+            .scores()
+            .into_iter()
+            .filter_map(|(player_id, score)| {
+                self.champions.get(&player_id).map(|champion| ScoreEntry {
+                    player_id: player_id as u16,
+                    team: champion.team_id,
+                    score,
+                })
+            })
+            .collect()
+    }
 
-        for (_, tower) in self.towers.iter_mut() {
-            if let Some(enemy_cell) = tower.get_potential_target(&self.board) {
-                if let Some(enemy_content) = &enemy_cell.content {
-                    let target = match enemy_content {
-                        CellContent::Champion(id, _) => Some(Target::Champion(*id)),
-                        CellContent::Minion(id, _) => Some(Target::Minion(*id)),
-                        _ => None, // Towers can't target other entities
-                    };
-
-                    if let Some(target) = target {
-                        if let Some(attack_action) = tower.can_attack() {
-                            if let AttackAction::Projectile {
-                                damage,
-                                speed,
-                                visual,
-                            } = attack_action
-                            {
-                                projectiles_to_create.push((
-                                    tower.tower_id,
-                                    target,
-                                    damage,
-                                    speed,
-                                    visual,
-                                ));
-                            }
-                        }
-                    }
-                }
-            }
-        }
-        // We create the projectiles
-        for (tower_id, target, damage, speed, visual) in projectiles_to_create {
-            if let Some(tower) = self.towers.get(&tower_id) {
-                self.projectile_manager.create_homing_projectile(
-                    tower.tower_id as u64,
-                    tower.team_id,
-                    target,
-                    (tower.row, tower.col),
-                    speed,
-                    vec![GameplayEffect::Damage(damage)],
-                    visual,
-                );
-            }
+    fn handle_tower_destroyed(&mut self, tower: &Tower) {
+        self.update_protections();
+        let lane_cleared = !self
+            .towers
+            .values()
+            .any(|other| other.team_id == tower.team_id && other.lane == tower.lane);
+        if lane_cleared {
+            let enemy_team = match tower.team_id {
+                Team::Blue => Team::Red,
+                Team::Red => Team::Blue,
+            };
+            self.minion_manager
+                .enable_super_minions(enemy_team, tower.lane);
         }
     }
 
     fn handle_minion_death(&mut self, id: &MinionId) {
-        if let Some(minion) = self.minion_manager.minions.get(id) {
-            if minion.is_dead() {
-                self.dead_minion_positions
-                    .push((minion.row, minion.col, minion.team_id));
-                self.board
-                    .clear_cell(minion.row as usize, minion.col as usize);
-                self.minion_manager.minions.remove(&id);
-            }
+        if let Some(minion) = self.minion_manager.minions.get(id)
+            && minion.is_dead()
+        {
+            self.dead_minion_positions
+                .push((minion.row, minion.col, minion.team_id));
+            self.board
+                .clear_cell(minion.row as usize, minion.col as usize);
+            self.minion_manager.minions.remove(id);
+            self.registry.despawn(*id);
         }
     }
 }
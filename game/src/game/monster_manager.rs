@@ -1,151 +1,326 @@
-use crate::config::MonsterStats;
+use crate::config::{CampStats, KillBuffStats, MonsterStats};
 use crate::game::entities::monster::Monster;
+use log::trace;
 use std::collections::HashMap;
+use std::time::{Duration, Instant};
 
-use super::algorithms::pathfinding::{find_path_on_board, is_adjacent_to_goal};
+use super::algorithms::pathfinding::{Pathfinder, is_adjacent_to_goal};
 use super::animation::AnimationTrait;
-use super::cell::MonsterId;
-use super::entities::monster::MonsterState;
+use super::cell::{MinionId, MonsterId};
+use super::entities::damage::{Damage, DamageSource};
+use super::entities::minion::Minion;
+use super::entities::monster::{MonsterState, MonsterTarget};
 use super::entities::projectile::GameplayEffect;
 use super::entities::{AttackAction, Fighter, Target};
+use super::map::CampLayout;
+use super::registry::{EntityKind, EntityRegistry};
 use super::{Board, CellContent, Champion, PlayerId};
 
+/// A camp of the map, its monsters share their aggro and respawn together
+#[derive(Debug)]
+pub struct Camp {
+    pub id: String,
+    /// Monster and spawn cell of each member
+    members: Vec<(String, (u16, u16))>,
+    /// Living monsters of the camp
+    pub monsters: Vec<MonsterId>,
+    respawn_timer: Duration,
+    first_spawn: Duration,
+    /// Set once every monster died, or before the first spawn
+    next_spawn: Option<Instant>,
+}
+
+impl Camp {
+    /// A camp of the map is either a `[[neutral_camps]]` entry or a single monster
+    fn resolve(
+        layout: &CampLayout,
+        camp_definitions: &[CampStats],
+        monster_definitions: &HashMap<String, MonsterStats>,
+    ) -> Option<Camp> {
+        let (members, respawn_timer, first_spawn) = if let Some(definition) = camp_definitions
+            .iter()
+            .find(|definition| definition.id == layout.monster)
+        {
+            let members = definition
+                .members
+                .iter()
+                .filter_map(|member| {
+                    let row = layout.position.0.checked_add_signed(member.offset.0)?;
+                    let col = layout.position.1.checked_add_signed(member.offset.1)?;
+                    Some((member.monster.clone(), (row, col)))
+                })
+                .collect();
+            (
+                members,
+                definition.respawn_timer_secs,
+                definition.first_spawn_secs,
+            )
+        } else {
+            let monster = monster_definitions.get(&layout.monster)?;
+            (
+                vec![(monster.id.clone(), layout.position)],
+                monster.respawn_timer_secs as u64,
+                0,
+            )
+        };
+        Some(Camp {
+            id: layout.monster.clone(),
+            members,
+            monsters: Vec::new(),
+            respawn_timer: Duration::from_secs(respawn_timer),
+            first_spawn: Duration::from_secs(first_spawn),
+            next_spawn: None,
+        })
+    }
+}
+
+/// Rewards of a monster kill, for the champion who landed the last hit
+#[derive(Debug, PartialEq)]
+pub struct MonsterKill {
+    pub killer: PlayerId,
+    pub xp: u8,
+    pub buff: Option<KillBuffStats>,
+}
+
 pub struct MonsterManager {
     pub monster_definitions: HashMap<String, MonsterStats>,
 
-    pub active_monsters: HashMap<usize, Monster>,
-    next_instance_id: MonsterId,
+    pub active_monsters: HashMap<MonsterId, Monster>,
+    camps: Vec<Camp>,
 }
 
 impl MonsterManager {
-    pub fn new(monsters: Vec<MonsterStats>) -> MonsterManager {
-        let monster_definitions = monsters
+    pub fn new(
+        monsters: Vec<MonsterStats>,
+        camp_definitions: Vec<CampStats>,
+        camps: Vec<CampLayout>,
+    ) -> MonsterManager {
+        let monster_definitions: HashMap<String, MonsterStats> = monsters
             .into_iter()
             .map(|monster| (monster.id.clone(), monster))
             .collect();
+        // Camps referencing an unknown camp or monster are skipped
+        let camps = camps
+            .iter()
+            .filter_map(|camp| Camp::resolve(camp, &camp_definitions, &monster_definitions))
+            .collect();
         MonsterManager {
             monster_definitions,
             active_monsters: HashMap::new(),
-            next_instance_id: 1,
+            camps,
         }
     }
 
-    pub fn spawn_monster(&mut self, name_id: &str, board: &mut Board) {
-        if let Some(monster_def) = self.monster_definitions.get(name_id) {
-            let monster = Monster::new(self.next_instance_id, monster_def.clone());
-            board.place_cell(
-                CellContent::Monster(monster.id),
-                monster.row as usize,
-                monster.col as usize,
-            );
-            self.active_monsters.insert(self.next_instance_id, monster);
-            self.next_instance_id += 1;
-        } else {
-            ()
+    pub fn spawn_monster(
+        &mut self,
+        name_id: &str,
+        spawn: (u16, u16),
+        board: &mut Board,
+        registry: &mut EntityRegistry,
+    ) -> Option<MonsterId> {
+        let monster_def = self.monster_definitions.get(name_id)?;
+        let id = registry.spawn(EntityKind::Monster, None);
+        let monster = Monster::new(id, monster_def.clone(), spawn);
+        board.place_cell(
+            CellContent::Monster(monster.id),
+            monster.row as usize,
+            monster.col as usize,
+        );
+        self.active_monsters.insert(id, monster);
+        Some(id)
+    }
+
+    /// Starts the camp timers, camps without a first spawn delay spawn right away
+    pub fn spawn_initial_monsters(&mut self, board: &mut Board, registry: &mut EntityRegistry) {
+        let now = Instant::now();
+        for camp in self.camps.iter_mut() {
+            camp.next_spawn = Some(now + camp.first_spawn);
         }
+        self.spawn_ready_camps(board, registry);
     }
 
-    pub fn spawn_initial_monsters(&mut self, board: &mut Board) {
-        let definitions_to_spawn: Vec<_> = self.monster_definitions.keys().cloned().collect();
-        for def_id in definitions_to_spawn {
-            self.spawn_monster(&def_id, board);
+    fn spawn_ready_camps(&mut self, board: &mut Board, registry: &mut EntityRegistry) {
+        let now = Instant::now();
+        for index in 0..self.camps.len() {
+            if self.camps[index]
+                .next_spawn
+                .is_none_or(|next_spawn| next_spawn > now)
+            {
+                continue;
+            }
+            self.camps[index].next_spawn = None;
+            let members = self.camps[index].members.clone();
+            for (monster, spawn) in members.iter() {
+                if let Some(id) = self.spawn_monster(monster, *spawn, board, registry) {
+                    self.camps[index].monsters.push(id);
+                }
+            }
         }
     }
 
+    fn camp_of(&self, monster_id: MonsterId) -> Option<&Camp> {
+        self.camps
+            .iter()
+            .find(|camp| camp.monsters.contains(&monster_id))
+    }
+
+    /// Applies a hit, the attacker becomes a target of the monster and of the
+    /// camp-mates around it. Only a champion landing the killing blow is rewarded.
     pub fn apply_effects_to_monster(
         &mut self,
         monster_id: &MonsterId,
         effects: Vec<GameplayEffect>,
-        player_id: PlayerId,
-    ) -> Option<(PlayerId, u8)> {
-        if let Some(monster) = self.active_monsters.get_mut(monster_id) {
-            monster.take_effect(effects);
-            monster.attach_target(player_id);
-            if monster.stats.health == 0 {
-                let monster_def = self.monster_definitions.get(&monster.monster_id).unwrap();
-                return Some((player_id, monster_def.xp_reward));
+        attacker: Option<MonsterTarget>,
+    ) -> Option<MonsterKill> {
+        let monster = self.active_monsters.get_mut(monster_id)?;
+        monster.take_effect(effects);
+        let killed = (monster.stats.health == 0).then(|| monster.monster_id.clone());
+        let attacker = attacker?;
+        monster.attach_target(attacker);
+        let position = (monster.row, monster.col);
+
+        // Camp-mates within their aggro range join the fight
+        let camp_mates = self
+            .camp_of(*monster_id)
+            .map(|camp| camp.monsters.clone())
+            .unwrap_or_default();
+        for id in camp_mates {
+            if let Some(mate) = self.active_monsters.get_mut(&id)
+                && mate.row.abs_diff(position.0) <= mate.aggro_range.0
+                && mate.col.abs_diff(position.1) <= mate.aggro_range.1
+            {
+                mate.attach_target(attacker);
             }
         }
-        None
+
+        let MonsterTarget::Champion(killer) = attacker else {
+            return None;
+        };
+        let monster_def = self.monster_definitions.get(&killed?)?;
+        Some(MonsterKill {
+            killer,
+            xp: monster_def.xp_reward,
+            buff: monster_def.reward_buff.clone(),
+        })
     }
 
     pub fn update(
         &mut self,
         board: &mut Board,
+        registry: &mut EntityRegistry,
         champions: &HashMap<PlayerId, Champion>,
+        minions: &HashMap<MinionId, Minion>,
+        pathfinder: &mut Pathfinder,
     ) -> (
         Vec<(Target, Vec<GameplayEffect>)>,
         Vec<Box<dyn AnimationTrait>>,
     ) {
-        println!("MonsterState: {:?}", self.active_monsters);
+        trace!("MonsterState: {:?}", self.active_monsters);
         let mut pending_damages: Vec<(Target, Vec<GameplayEffect>)> = Vec::new();
         let mut new_animations: Vec<Box<dyn AnimationTrait>> = Vec::new();
-        let mut dead_monster: Vec<(MonsterId, String)> = Vec::new();
+        let mut dead_monsters: Vec<(MonsterId, Instant)> = Vec::new();
+        let position_of = |target: MonsterTarget| match target {
+            MonsterTarget::Champion(id) => champions
+                .get(&id)
+                .filter(|champion| !champion.is_dead())
+                .map(|champion| (champion.row, champion.col)),
+            MonsterTarget::Minion(id) => minions.get(&id).map(|minion| (minion.row, minion.col)),
+        };
         for monster in self.active_monsters.values_mut() {
+            monster.move_timer.recharge(monster.stats.movement_speed);
             match monster.state {
                 MonsterState::Idle => {}
                 MonsterState::Aggro => {
-                    //  First we ensure the monster has a valid target champion.
-                    if let Some(champion_id) = monster.target_champion_id {
-                        if let Some(champion) = champions.get(&champion_id) {
-                            // 1. We check leash range
-                            let delta_row = monster.row.abs_diff(monster.spawn_row);
-                            let delta_col = monster.col.abs_diff(monster.spawn_col);
-                            if delta_row >= monster.leash_range as u16
-                                || delta_col >= monster.leash_range as u16
-                            {
-                                monster.start_returning(board);
-                                continue; // We stop processing for this monster
-                            }
-                            // 2. We check enemy is in range
-                            if is_adjacent_to_goal(
+                    if let Some((damage, hit)) = monster.cast_ability(champions) {
+                        for champion_id in hit {
+                            pending_damages.push((
+                                Target::Champion(champion_id),
+                                vec![GameplayEffect::Damage(Damage::magic(
+                                    damage,
+                                    DamageSource::Monster(monster.id),
+                                ))],
+                            ));
+                        }
+                    }
+                    // 1. We check leash range
+                    if !monster.in_leash((monster.row, monster.col)) {
+                        monster.start_returning(board, pathfinder);
+                        continue; // We stop processing for this monster
+                    }
+                    // 2. A dead or fled target is replaced by the nearest attacker
+                    if monster
+                        .target
+                        .and_then(position_of)
+                        .is_none_or(|cell| !monster.in_leash(cell))
+                    {
+                        monster.retarget(position_of);
+                    }
+                    let Some((target, target_position)) = monster
+                        .target
+                        .and_then(|target| position_of(target).map(|cell| (target, cell)))
+                    else {
+                        monster.start_returning(board, pathfinder);
+                        continue;
+                    };
+                    let target = match target {
+                        MonsterTarget::Champion(id) => Target::Champion(id),
+                        MonsterTarget::Minion(id) => Target::Minion(id),
+                    };
+                    // 3. We check enemy is in range
+                    if is_adjacent_to_goal((monster.row, monster.col), target_position) {
+                        if let Some(attack_action) = monster.can_attack()
+                            && let AttackAction::Melee { damage, animation } = attack_action
+                        {
+                            new_animations.push(animation);
+                            pending_damages.push((target, vec![GameplayEffect::Damage(damage)]));
+                        }
+                    } else {
+                        if monster.path.is_none() {
+                            monster.path = pathfinder.find_path(
+                                board,
                                 (monster.row, monster.col),
-                                (champion.row, champion.col),
-                            ) {
-                                if let Some(attack_action) = monster.can_attack() {
-                                    if let AttackAction::Melee { damage, animation } = attack_action
-                                    {
-                                        new_animations.push(animation);
-                                        pending_damages.push((
-                                            Target::Champion(champion_id),
-                                            vec![GameplayEffect::Damage(damage)],
-                                        ));
-                                    }
+                                target_position,
+                            );
+                        }
+                        if let Some(path) = &mut monster.path {
+                            if let Some(next_path) = path.front().copied() {
+                                let diagonal =
+                                    next_path.0 != monster.row && next_path.1 != monster.col;
+                                if !monster
+                                    .move_timer
+                                    .try_step(monster.stats.movement_speed, diagonal)
+                                {
+                                    continue;
                                 }
+                                path.pop_front();
+                                let old_row = monster.row;
+                                let old_col = monster.col;
+                                monster.row = next_path.0;
+                                monster.col = next_path.1;
+                                board.move_cell(
+                                    old_row as usize,
+                                    old_col as usize,
+                                    monster.row as usize,
+                                    monster.col as usize,
+                                );
                             } else {
-                                if monster.path.is_none() {
-                                    monster.path = find_path_on_board(
-                                        board,
-                                        (monster.row, monster.col),
-                                        (champion.row, champion.col),
-                                    );
-                                }
-                                if let Some(path) = &mut monster.path {
-                                    if let Some(next_path) = path.pop_front() {
-                                        let old_row = monster.row;
-                                        let old_col = monster.col;
-                                        monster.row = next_path.0;
-                                        monster.col = next_path.1;
-                                        board.move_cell(
-                                            old_row as usize,
-                                            old_col as usize,
-                                            monster.row as usize,
-                                            monster.col as usize,
-                                        );
-                                    } else {
-                                        monster.path = None;
-                                    }
-                                }
+                                monster.path = None;
                             }
-                        } else {
-                            // Target champion doesn't exist anymore
-                            monster.start_returning(board);
                         }
                     }
                 }
                 MonsterState::Returning => {
+                    monster.regenerate();
                     if let Some(path) = &mut monster.path {
-                        if let Some(p) = path.pop_front() {
+                        if let Some(p) = path.front().copied() {
+                            let diagonal = p.0 != monster.row && p.1 != monster.col;
+                            if !monster
+                                .move_timer
+                                .try_step(monster.stats.movement_speed, diagonal)
+                            {
+                                continue;
+                            }
+                            path.pop_front();
                             let old_row = monster.row;
                             let old_col = monster.col;
                             monster.row = p.0;
@@ -174,49 +349,71 @@ impl MonsterManager {
                 }
                 MonsterState::Dead => {
                     board.clear_cell(monster.row as usize, monster.col as usize);
-                    if monster.can_respawn() {
-                        dead_monster.push((monster.id, monster.monster_id.clone()));
-                    }
+                    dead_monsters.push((monster.id, monster.death_time.unwrap_or(Instant::now())));
                 }
             }
         }
-        for (id, monster_id) in dead_monster.iter() {
-            self.active_monsters.remove(id);
-            self.spawn_monster(monster_id, board);
+        for (id, death_time) in dead_monsters {
+            self.active_monsters.remove(&id);
+            registry.despawn(id);
+            // The respawn timer of a camp starts with the death of its last monster
+            if let Some(camp) = self
+                .camps
+                .iter_mut()
+                .find(|camp| camp.monsters.contains(&id))
+            {
+                camp.monsters.retain(|monster| *monster != id);
+                if camp.monsters.is_empty() {
+                    camp.next_spawn = Some(death_time + camp.respawn_timer);
+                }
+            }
         }
-        return (pending_damages, new_animations);
+        self.spawn_ready_camps(board, registry);
+        (pending_damages, new_animations)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        config::ChampionStats,
-        game::{Board, Champion, cell::Team, entities::monster::MonsterState},
+        config::{AttackType, CampMember, ChampionStats, MinionStats, MonsterAbilityStats},
+        game::{
+            Board, Champion,
+            cell::Team,
+            entities::{minion::Lane, monster::MonsterState},
+            map::LaneLayout,
+        },
     };
 
     use super::*;
 
     // Helper to create monster stats for testing
-    fn create_test_monster_stats(id: &str, spawn_row: u16, spawn_col: u16) -> MonsterStats {
+    fn create_test_monster_stats(id: &str) -> MonsterStats {
         MonsterStats {
             id: id.to_string(),
-            spawn_row,
-            spawn_col,
             health: 100,
             armor: 5,
+            magic_resist: 0,
             attack_damage: 10,
-            attack_range_row: 1,
-            attack_range_col: 1,
             aggro_range_row: 8,
             aggro_range_col: 8,
             leash_range: 10,
             xp_reward: 30,
             respawn_timer_secs: 60,
             attack_speed_ms: 1000,
+            movement_speed: 1,
+            reward_buff: None,
+            ability: None,
         }
     }
 
+    fn create_test_camps() -> Vec<CampLayout> {
+        vec![CampLayout {
+            monster: "wolf_red".to_string(),
+            position: (10, 10),
+        }]
+    }
+
     fn create_default_champion_stats() -> ChampionStats {
         ChampionStats {
             attack_damage: 20,
@@ -224,6 +421,11 @@ mod tests {
             health: 200,
             mana: 100,
             armor: 5,
+            magic_resist: 0,
+            armor_penetration_flat: 0,
+            armor_penetration_percent: 0.,
+            crit_chance: 0.,
+            crit_multiplier: 1.75,
             xp_per_level: vec![
                 35, 40, 45, 50, 55, 60, 65, 70, 75, 80, 85, 90, 95, 100, 105, 110, 115,
             ],
@@ -232,6 +434,8 @@ mod tests {
             level_up_armor_increase: 2,
             attack_range_row: 3,
             attack_range_col: 3,
+            attack: AttackType::Melee,
+            movement_speed: 1,
         }
     }
 
@@ -246,40 +450,31 @@ mod tests {
     #[test]
     fn test_new_monster_manager_initializes_correctly() {
         let monster_defs = vec![
-            create_test_monster_stats("wolf_red", 10, 10),
-            create_test_monster_stats("wolf_blue", 15, 15),
+            create_test_monster_stats("wolf_red"),
+            create_test_monster_stats("wolf_blue"),
         ];
 
-        let manager = MonsterManager::new(monster_defs);
+        let manager = MonsterManager::new(monster_defs, vec![], create_test_camps());
 
         // Check that definitions are stored correctly
         assert_eq!(manager.monster_definitions.len(), 2);
         assert!(manager.monster_definitions.contains_key("wolf_red"));
         assert!(manager.monster_definitions.contains_key("wolf_blue"));
-        assert_eq!(
-            manager
-                .monster_definitions
-                .get("wolf_red")
-                .unwrap()
-                .spawn_row,
-            10
-        );
+        assert_eq!(manager.camps.len(), 1);
 
         // Check that active monsters list is empty initially
         assert!(manager.active_monsters.is_empty());
-
-        // Check that the instance ID counter is initialized
-        assert_eq!(manager.next_instance_id, 1);
     }
 
     #[test]
     fn test_spawn_monster_creates_and_adds_monster() {
-        let monster_defs = vec![create_test_monster_stats("wolf_red", 10, 10)];
-        let mut manager = MonsterManager::new(monster_defs);
+        let monster_defs = vec![create_test_monster_stats("wolf_red")];
+        let mut manager = MonsterManager::new(monster_defs, vec![], create_test_camps());
         let mut board = Board::new(100, 100);
+        let mut registry = EntityRegistry::new();
 
         // Spawn the monster
-        manager.spawn_monster("wolf_red", &mut board);
+        manager.spawn_monster("wolf_red", (10, 10), &mut board, &mut registry);
 
         // Check that there is one active monster
         assert_eq!(
@@ -288,14 +483,14 @@ mod tests {
             "Should be one active monster"
         );
         // Check that the cellcontent got correctly set
-        if let Some(cell) = board.get_cell(10, 10) {
-            if let Some(content) = &cell.content {
-                assert_eq!(CellContent::Monster(1), *content)
-            }
+        if let Some(cell) = board.get_cell(10, 10)
+            && let Some(content) = &cell.content
+        {
+            assert_eq!(CellContent::Monster(1), *content)
         }
 
-        // Check that the next ID has been incremented
-        assert_eq!(manager.next_instance_id, 2, "Next instance ID should be 2");
+        // The registry handed out the id
+        assert_eq!(registry.kind(1), Some(EntityKind::Monster));
 
         // Get the monster and verify its properties
         let monster = manager
@@ -309,7 +504,7 @@ mod tests {
             "Monster should spawn at the definition's coordinates"
         );
         assert_eq!(monster.spawn_col, 10);
-        assert_eq!(manager.next_instance_id, 2, "Next instance ID should be 2");
+        assert_eq!(registry.len(), 1, "Only one id should be handed out");
 
         // Get the monster and verify its properties
         let monster = manager
@@ -327,59 +522,123 @@ mod tests {
         assert_eq!(monster.col, 10);
     }
 
+    #[test]
+    fn test_spawn_initial_monsters_uses_camp_positions() {
+        let monster_defs = vec![
+            create_test_monster_stats("wolf_red"),
+            create_test_monster_stats("wolf_blue"),
+        ];
+        let camps = vec![
+            CampLayout {
+                monster: "wolf_red".to_string(),
+                position: (10, 10),
+            },
+            CampLayout {
+                monster: "wolf_red".to_string(),
+                position: (20, 30),
+            },
+            CampLayout {
+                monster: "unknown".to_string(),
+                position: (5, 5),
+            },
+        ];
+        let mut manager = MonsterManager::new(monster_defs, vec![], camps);
+        let mut board = Board::new(100, 100);
+        let mut registry = EntityRegistry::new();
+
+        manager.spawn_initial_monsters(&mut board, &mut registry);
+
+        // Only camps with a known monster spawn, at the camp position
+        assert_eq!(manager.active_monsters.len(), 2);
+        let mut spawns: Vec<_> = manager
+            .active_monsters
+            .values()
+            .map(|monster| (monster.spawn_row, monster.spawn_col))
+            .collect();
+        spawns.sort();
+        assert_eq!(spawns, vec![(10, 10), (20, 30)]);
+        assert_eq!(
+            board.get_cell(20, 30).unwrap().content,
+            Some(CellContent::Monster(2))
+        );
+    }
+
     #[test]
     fn test_apply_effects_sets_aggro_on_idle_monster() {
-        let monster_defs = vec![create_test_monster_stats("wolf_red", 10, 10)];
-        let mut manager = MonsterManager::new(monster_defs);
+        let monster_defs = vec![create_test_monster_stats("wolf_red")];
+        let mut manager = MonsterManager::new(monster_defs, vec![], create_test_camps());
         let mut board = Board::new(100, 100);
-        manager.spawn_monster("wolf_red", &mut board);
+        let mut registry = EntityRegistry::new();
+        manager.spawn_monster("wolf_red", (10, 10), &mut board, &mut registry);
 
         let monster_id = 1;
         let attacker_id = 42; // Player's ID
 
         // Apply damage effect
-        let effects = vec![GameplayEffect::Damage(30)];
-        manager.apply_effects_to_monster(&monster_id, effects, attacker_id);
+        let effects = vec![GameplayEffect::Damage(Damage::physical(
+            30,
+            DamageSource::Champion(1),
+        ))];
+        manager.apply_effects_to_monster(
+            &monster_id,
+            effects,
+            Some(MonsterTarget::Champion(attacker_id)),
+        );
 
         // Get the monster to check its new state
         let monster = manager.active_monsters.get(&monster_id).unwrap();
 
         // Verify health, state, and target
-        assert_eq!(monster.stats.health, 70);
+        assert_eq!(monster.stats.health, 71);
         assert_eq!(monster.state, MonsterState::Aggro);
-        assert_eq!(monster.target_champion_id, Some(attacker_id));
+        assert_eq!(monster.target, Some(MonsterTarget::Champion(attacker_id)));
     }
 
     #[test]
     fn test_apply_effects_does_not_change_target_on_aggro_monster() {
-        let monster_defs = vec![create_test_monster_stats("wolf_red", 10, 10)];
-        let mut manager = MonsterManager::new(monster_defs);
+        let monster_defs = vec![create_test_monster_stats("wolf_red")];
+        let mut manager = MonsterManager::new(monster_defs, vec![], create_test_camps());
         let mut board = Board::new(100, 100);
-        manager.spawn_monster("wolf_red", &mut board);
+        let mut registry = EntityRegistry::new();
+        manager.spawn_monster("wolf_red", (10, 10), &mut board, &mut registry);
 
         let monster_id = 1;
         let attacker_1 = 42; // First attacker
         let attacker_2 = 99; // Second attacker
 
         // First attack sets the aggro
-        manager.apply_effects_to_monster(&monster_id, vec![GameplayEffect::Damage(10)], attacker_1);
+        manager.apply_effects_to_monster(
+            &monster_id,
+            vec![GameplayEffect::Damage(Damage::physical(
+                10,
+                DamageSource::Champion(1),
+            ))],
+            Some(MonsterTarget::Champion(attacker_1)),
+        );
         let monster = manager.active_monsters.get(&monster_id).unwrap();
         assert_eq!(
-            monster.target_champion_id,
-            Some(attacker_1),
+            monster.target,
+            Some(MonsterTarget::Champion(attacker_1)),
             "Target should be the first attacker"
         );
         assert_eq!(monster.stats.health, 90);
 
         // Second attack from a different champion
-        manager.apply_effects_to_monster(&monster_id, vec![GameplayEffect::Damage(10)], attacker_2);
+        manager.apply_effects_to_monster(
+            &monster_id,
+            vec![GameplayEffect::Damage(Damage::physical(
+                10,
+                DamageSource::Champion(1),
+            ))],
+            Some(MonsterTarget::Champion(attacker_2)),
+        );
         let monster = manager.active_monsters.get(&monster_id).unwrap();
 
         // Verify health is reduced, but target remains unchanged
         assert_eq!(monster.stats.health, 80, "Health should be further reduced");
         assert_eq!(
-            monster.target_champion_id,
-            Some(attacker_1),
+            monster.target,
+            Some(MonsterTarget::Champion(attacker_1)),
             "Target should NOT change to the second attacker"
         );
     }
@@ -387,10 +646,11 @@ mod tests {
     #[test]
     fn test_update_leashes_monster_when_far_from_spawn() {
         // Leash range in test stats is 10. Spawn is (10, 10).
-        let monster_defs = vec![create_test_monster_stats("wolf_red", 10, 10)];
-        let mut manager = MonsterManager::new(monster_defs);
+        let monster_defs = vec![create_test_monster_stats("wolf_red")];
+        let mut manager = MonsterManager::new(monster_defs, vec![], create_test_camps());
         let mut board = Board::new(100, 100);
-        manager.spawn_monster("wolf_red", &mut board);
+        let mut registry = EntityRegistry::new();
+        manager.spawn_monster("wolf_red", (10, 10), &mut board, &mut registry);
         let monster_id = 1;
         let attacker_id = 42;
 
@@ -398,7 +658,11 @@ mod tests {
         champions.insert(attacker_id, create_champion(15, 15)); // Champion position is irrelevant for the leash calculation itself
 
         // Make the monster aggro
-        manager.apply_effects_to_monster(&monster_id, vec![], attacker_id);
+        manager.apply_effects_to_monster(
+            &monster_id,
+            vec![],
+            Some(MonsterTarget::Champion(attacker_id)),
+        );
 
         // Manually move the monster far from its spawn point to simulate it being kited
         let monster = manager.active_monsters.get_mut(&monster_id).unwrap();
@@ -411,7 +675,13 @@ mod tests {
         );
 
         // Call the update loop
-        manager.update(&mut board, &champions);
+        manager.update(
+            &mut board,
+            &mut registry,
+            &champions,
+            &HashMap::new(),
+            &mut Pathfinder::new(10_000),
+        );
 
         // Verify the monster is now returning because it's too far from its spawn
         let monster = manager.active_monsters.get(&monster_id).unwrap();
@@ -421,7 +691,7 @@ mod tests {
             "Monster should be returning after being leashed"
         );
         assert!(
-            monster.target_champion_id.is_none(),
+            monster.target.is_none(),
             "Monster target should be cleared when returning"
         );
     }
@@ -429,10 +699,11 @@ mod tests {
     #[test]
     fn test_update_moves_aggro_monster_towards_target() {
         // Attack range is 1, Leash range is 10. Spawn is (10, 10)
-        let monster_defs = vec![create_test_monster_stats("wolf_red", 10, 10)];
-        let mut manager = MonsterManager::new(monster_defs);
+        let monster_defs = vec![create_test_monster_stats("wolf_red")];
+        let mut manager = MonsterManager::new(monster_defs, vec![], create_test_camps());
         let mut board = Board::new(100, 100);
-        manager.spawn_monster("wolf_red", &mut board);
+        let mut registry = EntityRegistry::new();
+        manager.spawn_monster("wolf_red", (10, 10), &mut board, &mut registry);
         let monster_id = 1;
         let attacker_id = 42;
 
@@ -441,13 +712,23 @@ mod tests {
         champions.insert(attacker_id, create_champion(15, 10));
 
         // Make monster aggro
-        manager.apply_effects_to_monster(&monster_id, vec![], attacker_id);
+        manager.apply_effects_to_monster(
+            &monster_id,
+            vec![],
+            Some(MonsterTarget::Champion(attacker_id)),
+        );
         let monster = manager.active_monsters.get(&monster_id).unwrap();
         let initial_pos = (monster.row, monster.col);
         assert_eq!(initial_pos, (10, 10));
 
         // Call the update loop
-        manager.update(&mut board, &champions);
+        manager.update(
+            &mut board,
+            &mut registry,
+            &champions,
+            &HashMap::new(),
+            &mut Pathfinder::new(10_000),
+        );
 
         // Verify the monster has moved one step towards the champion
         let monster = manager.active_monsters.get(&monster_id).unwrap();
@@ -456,7 +737,7 @@ mod tests {
         // The path should be straight down in this case
         assert_eq!(
             new_pos,
-            (11, 9),
+            (11, 10),
             "Monster should move one step along the path to the target"
         );
     }
@@ -464,10 +745,11 @@ mod tests {
     #[test]
     fn test_update_attacks_champion_in_range() {
         // Attack range is 1. Spawn is (10, 10).
-        let monster_defs = vec![create_test_monster_stats("wolf_red", 10, 10)];
-        let mut manager = MonsterManager::new(monster_defs);
+        let monster_defs = vec![create_test_monster_stats("wolf_red")];
+        let mut manager = MonsterManager::new(monster_defs, vec![], create_test_camps());
         let mut board = Board::new(100, 100);
-        manager.spawn_monster("wolf_red", &mut board);
+        let mut registry = EntityRegistry::new();
+        manager.spawn_monster("wolf_red", (10, 10), &mut board, &mut registry);
         let monster_id = 1;
         let attacker_id = 42;
 
@@ -476,13 +758,23 @@ mod tests {
         champions.insert(attacker_id, create_champion(10, 11));
 
         // Make monster aggro and expire its attack cooldown so it can attack immediately
-        manager.apply_effects_to_monster(&monster_id, vec![], attacker_id);
+        manager.apply_effects_to_monster(
+            &monster_id,
+            vec![],
+            Some(MonsterTarget::Champion(attacker_id)),
+        );
         let monster = manager.active_monsters.get_mut(&monster_id).unwrap();
         monster.last_attacked = std::time::Instant::now() - std::time::Duration::from_secs(5);
         let initial_pos = (monster.row, monster.col);
 
         // Call the update loop
-        let (pending_effects, animation) = manager.update(&mut board, &mut champions);
+        let (pending_effects, animation) = manager.update(
+            &mut board,
+            &mut registry,
+            &champions,
+            &HashMap::new(),
+            &mut Pathfinder::new(10_000),
+        );
 
         // Verify the monster did NOT move
         let monster = manager.active_monsters.get(&monster_id).unwrap();
@@ -502,29 +794,39 @@ mod tests {
         let (target, effect) = &pending_effects[0];
         assert_eq!(*target, Target::Champion(attacker_id));
         assert_eq!(effect.len(), 1);
-        assert_eq!(effect[0], GameplayEffect::Damage(10))
+        assert_eq!(
+            effect[0],
+            GameplayEffect::Damage(Damage::physical(10, DamageSource::Monster(1)))
+        )
     }
 
     #[test]
     fn test_update_moves_returning_monster_towards_spawn() {
-        let monster_defs = vec![create_test_monster_stats("wolf_red", 10, 10)];
-        let mut manager = MonsterManager::new(monster_defs);
+        let monster_defs = vec![create_test_monster_stats("wolf_red")];
+        let mut manager = MonsterManager::new(monster_defs, vec![], create_test_camps());
         let mut board = Board::new(100, 100);
-        manager.spawn_monster("wolf_red", &mut board);
+        let mut registry = EntityRegistry::new();
+        manager.spawn_monster("wolf_red", (10, 10), &mut board, &mut registry);
         let monster_id = 1;
 
-        let mut champions = HashMap::new();
+        let champions = HashMap::new();
 
         // Manually put the monster in a returning state from a different position
         let monster = manager.active_monsters.get_mut(&monster_id).unwrap();
         monster.row = 15;
         monster.col = 15;
-        monster.start_returning(&board);
+        monster.start_returning(&board, &mut Pathfinder::new(10_000));
         assert_eq!(monster.state, MonsterState::Returning);
         let initial_pos = (monster.row, monster.col);
 
         // Call the update loop
-        manager.update(&mut board, &mut champions);
+        manager.update(
+            &mut board,
+            &mut registry,
+            &champions,
+            &HashMap::new(),
+            &mut Pathfinder::new(10_000),
+        );
 
         // Verify the monster has moved one step towards its spawn diagonally
         let monster = manager.active_monsters.get(&monster_id).unwrap();
@@ -539,13 +841,14 @@ mod tests {
 
     #[test]
     fn test_update_resets_monster_when_it_reaches_spawn() {
-        let monster_defs = vec![create_test_monster_stats("wolf_red", 10, 10)];
-        let mut manager = MonsterManager::new(monster_defs);
+        let monster_defs = vec![create_test_monster_stats("wolf_red")];
+        let mut manager = MonsterManager::new(monster_defs, vec![], create_test_camps());
         let mut board = Board::new(100, 100);
-        manager.spawn_monster("wolf_red", &mut board);
+        let mut registry = EntityRegistry::new();
+        manager.spawn_monster("wolf_red", (10, 10), &mut board, &mut registry);
         let monster_id = 1;
 
-        let mut champions = HashMap::new();
+        let champions = HashMap::new();
 
         // Manually put the monster in a returning state, right next to its spawn
         // We create a scope for the mutable borrow
@@ -554,14 +857,26 @@ mod tests {
             monster.row = 11;
             monster.col = 11;
             monster.stats.health = 50; // Make sure it needs healing
-            monster.start_returning(&board);
+            monster.start_returning(&board, &mut Pathfinder::new(10_000));
         }
-        manager.update(&mut board, &champions);
+        manager.update(
+            &mut board,
+            &mut registry,
+            &champions,
+            &HashMap::new(),
+            &mut Pathfinder::new(10_000),
+        );
         let monster = manager.active_monsters.get_mut(&monster_id).unwrap();
         assert_eq!(monster.state, MonsterState::Returning);
 
         // Call the update loop
-        manager.update(&mut board, &mut champions);
+        manager.update(
+            &mut board,
+            &mut registry,
+            &champions,
+            &HashMap::new(),
+            &mut Pathfinder::new(10_000),
+        );
 
         // Verify the monster has been reset
         let monster = manager.active_monsters.get(&monster_id).unwrap();
@@ -587,30 +902,38 @@ mod tests {
 
     #[test]
     fn test_update_respawns_monster_when_ready() {
-        let monster_defs = vec![create_test_monster_stats("wolf_red", 10, 10)];
-        let mut manager = MonsterManager::new(monster_defs);
+        let monster_defs = vec![create_test_monster_stats("wolf_red")];
+        let mut manager = MonsterManager::new(monster_defs, vec![], create_test_camps());
         let mut board = Board::new(100, 100);
-        manager.spawn_monster("wolf_red", &mut board);
+        let mut registry = EntityRegistry::new();
+        manager.spawn_initial_monsters(&mut board, &mut registry);
         let monster_id = 1;
 
         let champions = HashMap::new();
 
         // Manually kill the monster and set its death time to be in the past
-        // to ensure its `can_respawn()` method will return true.
+        // so that the respawn timer of its camp is already over.
         let monster = manager.active_monsters.get_mut(&monster_id).unwrap();
         monster.state = MonsterState::Dead;
-        let respawn_duration = monster.respawn_timer;
+        let respawn_duration = manager.camps[0].respawn_timer;
+        let monster = manager.active_monsters.get_mut(&monster_id).unwrap();
         monster.death_time =
             Some(std::time::Instant::now() - respawn_duration - std::time::Duration::from_secs(1));
 
-        let next_id = manager.next_instance_id;
+        let next_id = monster_id + 1;
 
         // Call the update loop
-        manager.update(&mut board, &champions);
+        manager.update(
+            &mut board,
+            &mut registry,
+            &champions,
+            &HashMap::new(),
+            &mut Pathfinder::new(10_000),
+        );
 
         // The old monster should be gone, and a new one should exist.
         assert!(
-            manager.active_monsters.get(&monster_id).is_none(),
+            !manager.active_monsters.contains_key(&monster_id),
             "Old monster should be removed"
         );
         assert_eq!(
@@ -619,9 +942,9 @@ mod tests {
             "There should be one active monster after respawn"
         );
         assert_eq!(
-            manager.next_instance_id,
-            next_id + 1,
-            "Next instance ID should be incremented"
+            registry.kind(monster_id),
+            None,
+            "The dead monster should leave the registry"
         );
 
         // The new monster should exist with the next ID.
@@ -632,4 +955,454 @@ mod tests {
         assert_eq!(new_monster.state, MonsterState::Idle);
         assert_eq!(new_monster.stats.health, new_monster.stats.max_health);
     }
+
+    fn create_wolf_camp() -> CampStats {
+        CampStats {
+            id: "wolves".to_string(),
+            members: vec![
+                CampMember {
+                    monster: "wolf_red".to_string(),
+                    offset: (0, 0),
+                },
+                CampMember {
+                    monster: "wolf_pup".to_string(),
+                    offset: (1, -1),
+                },
+                CampMember {
+                    monster: "wolf_pup".to_string(),
+                    offset: (1, 1),
+                },
+            ],
+            respawn_timer_secs: 90,
+            first_spawn_secs: 0,
+        }
+    }
+
+    fn create_wolf_manager(board: &mut Board, registry: &mut EntityRegistry) -> MonsterManager {
+        let monster_defs = vec![
+            create_test_monster_stats("wolf_red"),
+            create_test_monster_stats("wolf_pup"),
+        ];
+        let camps = vec![CampLayout {
+            monster: "wolves".to_string(),
+            position: (10, 10),
+        }];
+        let mut manager = MonsterManager::new(monster_defs, vec![create_wolf_camp()], camps);
+        manager.spawn_initial_monsters(board, registry);
+        manager
+    }
+
+    #[test]
+    fn test_camp_spawns_every_member_around_its_position() {
+        let mut board = Board::new(100, 100);
+        let mut registry = EntityRegistry::new();
+        let manager = create_wolf_manager(&mut board, &mut registry);
+
+        assert_eq!(manager.active_monsters.len(), 3);
+        let mut spawns: Vec<_> = manager
+            .active_monsters
+            .values()
+            .map(|monster| (monster.monster_id.as_str(), monster.row, monster.col))
+            .collect();
+        spawns.sort();
+        assert_eq!(
+            spawns,
+            vec![
+                ("wolf_pup", 11, 9),
+                ("wolf_pup", 11, 11),
+                ("wolf_red", 10, 10)
+            ]
+        );
+        assert_eq!(manager.camps[0].monsters.len(), 3);
+    }
+
+    #[test]
+    fn test_hitting_a_camp_member_aggroes_the_whole_camp() {
+        let mut board = Board::new(100, 100);
+        let mut registry = EntityRegistry::new();
+        let mut manager = create_wolf_manager(&mut board, &mut registry);
+        let attacker_id = 42;
+
+        manager.apply_effects_to_monster(
+            &1,
+            vec![GameplayEffect::Damage(Damage::physical(
+                10,
+                DamageSource::Champion(1),
+            ))],
+            Some(MonsterTarget::Champion(attacker_id)),
+        );
+
+        for monster in manager.active_monsters.values() {
+            assert_eq!(monster.state, MonsterState::Aggro);
+            assert_eq!(monster.target, Some(MonsterTarget::Champion(attacker_id)));
+        }
+    }
+
+    #[test]
+    fn test_camp_respawns_once_every_member_is_dead() {
+        let mut board = Board::new(100, 100);
+        let mut registry = EntityRegistry::new();
+        let mut manager = create_wolf_manager(&mut board, &mut registry);
+        let champions = HashMap::new();
+        let long_ago = Instant::now() - Duration::from_secs(100);
+
+        // Two of the three monsters die, the camp waits for the last one
+        for id in [1, 2] {
+            let monster = manager.active_monsters.get_mut(&id).unwrap();
+            monster.state = MonsterState::Dead;
+            monster.death_time = Some(long_ago);
+        }
+        manager.update(
+            &mut board,
+            &mut registry,
+            &champions,
+            &HashMap::new(),
+            &mut Pathfinder::new(10_000),
+        );
+        assert_eq!(manager.active_monsters.len(), 1);
+        assert_eq!(manager.camps[0].monsters, vec![3]);
+
+        // The last death starts the timer, the camp comes back in full
+        let monster = manager.active_monsters.get_mut(&3).unwrap();
+        monster.state = MonsterState::Dead;
+        monster.death_time = Some(Instant::now());
+        manager.update(
+            &mut board,
+            &mut registry,
+            &champions,
+            &HashMap::new(),
+            &mut Pathfinder::new(10_000),
+        );
+        assert!(manager.active_monsters.is_empty());
+
+        manager.camps[0].next_spawn = Some(Instant::now());
+        manager.update(
+            &mut board,
+            &mut registry,
+            &champions,
+            &HashMap::new(),
+            &mut Pathfinder::new(10_000),
+        );
+        assert_eq!(manager.active_monsters.len(), 3);
+        assert_eq!(manager.camps[0].monsters, vec![4, 5, 6]);
+    }
+
+    #[test]
+    fn test_epic_camp_waits_for_its_first_spawn() {
+        let monster_defs = vec![create_test_monster_stats("drake")];
+        let epic_camp = CampStats {
+            id: "drake".to_string(),
+            members: vec![CampMember {
+                monster: "drake".to_string(),
+                offset: (0, 0),
+            }],
+            respawn_timer_secs: 360,
+            first_spawn_secs: 300,
+        };
+        let camps = vec![CampLayout {
+            monster: "drake".to_string(),
+            position: (20, 20),
+        }];
+        let mut manager = MonsterManager::new(monster_defs, vec![epic_camp], camps);
+        let mut board = Board::new(100, 100);
+        let mut registry = EntityRegistry::new();
+
+        manager.spawn_initial_monsters(&mut board, &mut registry);
+        assert!(manager.active_monsters.is_empty());
+
+        manager.camps[0].next_spawn = Some(Instant::now());
+        manager.update(
+            &mut board,
+            &mut registry,
+            &HashMap::new(),
+            &HashMap::new(),
+            &mut Pathfinder::new(10_000),
+        );
+        assert_eq!(manager.active_monsters.len(), 1);
+    }
+
+    #[test]
+    fn test_kill_returns_the_reward_buff() {
+        let buff = KillBuffStats {
+            id: "golem_might".to_string(),
+            duration_secs: 90,
+            team_wide: false,
+            bonus_attack_damage: 10,
+            health_regen: 2,
+        };
+        let mut golem = create_test_monster_stats("golem_red");
+        golem.reward_buff = Some(buff.clone());
+        let camps = vec![CampLayout {
+            monster: "golem_red".to_string(),
+            position: (10, 10),
+        }];
+        let mut manager = MonsterManager::new(vec![golem], vec![], camps);
+        let mut board = Board::new(100, 100);
+        let mut registry = EntityRegistry::new();
+        manager.spawn_initial_monsters(&mut board, &mut registry);
+
+        let kill = manager.apply_effects_to_monster(
+            &1,
+            vec![GameplayEffect::Damage(Damage::physical(
+                500,
+                DamageSource::Champion(1),
+            ))],
+            Some(MonsterTarget::Champion(42)),
+        );
+
+        assert_eq!(
+            kill,
+            Some(MonsterKill {
+                killer: 42,
+                xp: 30,
+                buff: Some(buff),
+            })
+        );
+    }
+
+    #[test]
+    fn test_ability_hits_champions_around_the_monster() {
+        let mut drake = create_test_monster_stats("drake");
+        drake.ability = Some(MonsterAbilityStats {
+            damage: 40,
+            radius: 3,
+            cooldown_secs: 8,
+        });
+        let mut manager = MonsterManager::new(vec![drake], vec![], vec![]);
+        let mut board = Board::new(100, 100);
+        let mut registry = EntityRegistry::new();
+        manager.spawn_monster("drake", (10, 10), &mut board, &mut registry);
+
+        let mut champions = HashMap::new();
+        champions.insert(1, create_champion(12, 13));
+        champions.insert(2, create_champion(20, 20));
+        manager.apply_effects_to_monster(&1, vec![], Some(MonsterTarget::Champion(1)));
+        let monster = manager.active_monsters.get_mut(&1).unwrap();
+        monster.last_ability = Instant::now() - Duration::from_secs(10);
+
+        let (pending_effects, _) = manager.update(
+            &mut board,
+            &mut registry,
+            &champions,
+            &HashMap::new(),
+            &mut Pathfinder::new(10_000),
+        );
+
+        assert!(pending_effects.contains(&(
+            Target::Champion(1),
+            vec![GameplayEffect::Damage(Damage::magic(
+                40,
+                DamageSource::Monster(1)
+            ))]
+        )));
+        assert!(
+            !pending_effects
+                .iter()
+                .any(|(target, _)| *target == Target::Champion(2)),
+            "Champions outside the radius should not be hit"
+        );
+    }
+
+    fn create_minion(id: MinionId, position: (u16, u16)) -> Minion {
+        let lane = LaneLayout {
+            team: Team::Blue,
+            lane: Lane::Mid,
+            spawn: position,
+            waypoints: vec![position],
+        };
+        let stats = MinionStats {
+            attack_damage: 10,
+            attack_speed_ms: 1000,
+            health: 100,
+            armor: 0,
+            magic_resist: 0,
+            aggro_range_row: 5,
+            aggro_range_col: 5,
+            attack_range_row: 1,
+            attack_range_col: 1,
+            attack: AttackType::Melee,
+            movement_speed: 1,
+        };
+        Minion::new(id, &lane, stats)
+    }
+
+    #[test]
+    fn test_returning_monster_regenerates_on_the_way() {
+        let monster_defs = vec![create_test_monster_stats("wolf_red")];
+        let mut manager = MonsterManager::new(monster_defs, vec![], create_test_camps());
+        let mut board = Board::new(100, 100);
+        let mut registry = EntityRegistry::new();
+        manager.spawn_initial_monsters(&mut board, &mut registry);
+        let mut pathfinder = Pathfinder::new(10_000);
+
+        let monster = manager.active_monsters.get_mut(&1).unwrap();
+        monster.row = 15;
+        monster.col = 15;
+        monster.stats.health = 20;
+        monster.start_returning(&board, &mut pathfinder);
+
+        manager.update(
+            &mut board,
+            &mut registry,
+            &HashMap::new(),
+            &HashMap::new(),
+            &mut pathfinder,
+        );
+
+        let monster = manager.active_monsters.get(&1).unwrap();
+        assert_eq!(monster.state, MonsterState::Returning);
+        assert_eq!(monster.stats.health, 30, "A tenth of the health per tick");
+    }
+
+    #[test]
+    fn test_monster_retargets_the_nearest_attacker_when_its_target_leaves() {
+        let monster_defs = vec![create_test_monster_stats("wolf_red")];
+        let mut manager = MonsterManager::new(monster_defs, vec![], create_test_camps());
+        let mut board = Board::new(100, 100);
+        let mut registry = EntityRegistry::new();
+        manager.spawn_initial_monsters(&mut board, &mut registry);
+
+        let mut champions = HashMap::new();
+        champions.insert(1, create_champion(10, 11));
+        champions.insert(2, create_champion(16, 10));
+        champions.insert(3, create_champion(12, 12));
+        for attacker in [1, 2, 3] {
+            manager.apply_effects_to_monster(&1, vec![], Some(MonsterTarget::Champion(attacker)));
+        }
+
+        // The first attacker runs out of the leash, the closest one is next
+        champions.get_mut(&1).unwrap().row = 40;
+        manager.update(
+            &mut board,
+            &mut registry,
+            &champions,
+            &HashMap::new(),
+            &mut Pathfinder::new(10_000),
+        );
+        let monster = manager.active_monsters.get(&1).unwrap();
+        assert_eq!(monster.state, MonsterState::Aggro);
+        assert_eq!(monster.target, Some(MonsterTarget::Champion(3)));
+
+        // Without any attacker left, the monster walks back to its camp
+        champions.clear();
+        manager.update(
+            &mut board,
+            &mut registry,
+            &champions,
+            &HashMap::new(),
+            &mut Pathfinder::new(10_000),
+        );
+        let monster = manager.active_monsters.get(&1).unwrap();
+        assert_eq!(monster.state, MonsterState::Returning);
+        assert!(monster.attackers.is_empty());
+    }
+
+    #[test]
+    fn test_monster_fights_back_a_minion() {
+        let monster_defs = vec![create_test_monster_stats("wolf_red")];
+        let mut manager = MonsterManager::new(monster_defs, vec![], create_test_camps());
+        let mut board = Board::new(100, 100);
+        let mut registry = EntityRegistry::new();
+        manager.spawn_initial_monsters(&mut board, &mut registry);
+        let mut minions = HashMap::new();
+        minions.insert(7, create_minion(7, (11, 10)));
+
+        let kill = manager.apply_effects_to_monster(
+            &1,
+            vec![GameplayEffect::Damage(Damage::physical(
+                15,
+                DamageSource::Champion(1),
+            ))],
+            Some(MonsterTarget::Minion(7)),
+        );
+        assert!(kill.is_none());
+        let monster = manager.active_monsters.get_mut(&1).unwrap();
+        monster.last_attacked = Instant::now() - Duration::from_secs(5);
+
+        let (pending_effects, _) = manager.update(
+            &mut board,
+            &mut registry,
+            &HashMap::new(),
+            &minions,
+            &mut Pathfinder::new(10_000),
+        );
+
+        assert_eq!(
+            pending_effects,
+            vec![(
+                Target::Minion(7),
+                vec![GameplayEffect::Damage(Damage::physical(
+                    10,
+                    DamageSource::Monster(1)
+                ))]
+            )]
+        );
+    }
+
+    #[test]
+    fn test_damage_without_attacker_keeps_the_monster_idle() {
+        let monster_defs = vec![create_test_monster_stats("wolf_red")];
+        let mut manager = MonsterManager::new(monster_defs, vec![], create_test_camps());
+        let mut board = Board::new(100, 100);
+        let mut registry = EntityRegistry::new();
+        manager.spawn_initial_monsters(&mut board, &mut registry);
+
+        // A tower projectile hits the monster, nothing to fight back
+        manager.apply_effects_to_monster(
+            &1,
+            vec![GameplayEffect::Damage(Damage::physical(
+                15,
+                DamageSource::Champion(1),
+            ))],
+            None,
+        );
+        manager.update(
+            &mut board,
+            &mut registry,
+            &HashMap::new(),
+            &HashMap::new(),
+            &mut Pathfinder::new(10_000),
+        );
+        let monster = manager.active_monsters.get(&1).unwrap();
+        assert_eq!(monster.state, MonsterState::Idle);
+        assert_eq!(monster.stats.health, 86);
+
+        // A killing blow without attacker gives no reward
+        let kill = manager.apply_effects_to_monster(
+            &1,
+            vec![GameplayEffect::Damage(Damage::physical(
+                500,
+                DamageSource::Champion(1),
+            ))],
+            None,
+        );
+        assert!(kill.is_none());
+        assert_eq!(
+            manager.active_monsters.get(&1).unwrap().state,
+            MonsterState::Dead
+        );
+    }
+
+    #[test]
+    fn test_camp_mates_out_of_aggro_range_are_not_pulled() {
+        let monster_defs = vec![
+            create_test_monster_stats("wolf_red"),
+            create_test_monster_stats("wolf_pup"),
+        ];
+        let mut camp = create_wolf_camp();
+        camp.members[2].offset = (9, 9);
+        let camps = vec![CampLayout {
+            monster: "wolves".to_string(),
+            position: (10, 10),
+        }];
+        let mut manager = MonsterManager::new(monster_defs, vec![camp], camps);
+        let mut board = Board::new(100, 100);
+        let mut registry = EntityRegistry::new();
+        manager.spawn_initial_monsters(&mut board, &mut registry);
+
+        manager.apply_effects_to_monster(&1, vec![], Some(MonsterTarget::Champion(42)));
+
+        assert_eq!(manager.active_monsters[&2].state, MonsterState::Aggro);
+        assert_eq!(manager.active_monsters[&3].state, MonsterState::Idle);
+    }
 }
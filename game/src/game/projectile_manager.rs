@@ -1,7 +1,5 @@
-use crate::game::spell::ProjectileType;
-
 use super::animation::{AnimationCommand, AnimationTrait};
-use super::cell::{CellAnimation, MonsterId, Team};
+use super::cell::{CellAnimation, EntityId, MonsterId, Team};
 use super::entities::Target;
 use super::entities::minion::Minion;
 use super::entities::monster::Monster;
@@ -11,6 +9,7 @@ use super::spell::ProjectileBlueprint;
 use super::{Board, CellContent, Champion, MinionId, PlayerId, TowerId};
 use std::collections::HashMap;
 
+#[derive(Default)]
 pub struct ProjectileManager {
     pub projectiles: HashMap<u64, Projectile>,
     next_projectile_id: u64,
@@ -25,37 +24,20 @@ impl ProjectileManager {
     }
 
     pub fn create_from_blueprint(&mut self, blueprint: ProjectileBlueprint) {
-        match blueprint.projectile_type {
-            ProjectileType::LockOn => {
-                if let Some(target_id) = blueprint.target_id {
-                    self.create_homing_projectile(
-                        blueprint.owner_id,
-                        blueprint.team_id,
-                        target_id,
-                        blueprint.start_pos,
-                        blueprint.speed,
-                        blueprint.payloads,
-                        blueprint.visual_cell_type,
-                    );
-                }
-            }
-            ProjectileType::SkillShot => {
-                self.create_skillshot_projectile(
-                    blueprint.owner_id,
-                    blueprint.team_id,
-                    blueprint.start_pos,
-                    blueprint.end_pos,
-                    blueprint.speed,
-                    blueprint.payloads,
-                    blueprint.visual_cell_type,
-                );
-            }
-        }
+        self.create_skillshot_projectile(
+            blueprint.owner_id,
+            blueprint.team_id,
+            blueprint.start_pos,
+            blueprint.end_pos,
+            blueprint.speed,
+            blueprint.payloads,
+            blueprint.visual_cell_type,
+        );
     }
 
     pub fn create_skillshot_projectile(
         &mut self,
-        owner_id: u64,
+        owner_id: EntityId,
         team_id: Team,
         start_pos: (u16, u16),
         end_pos: (u16, u16),
@@ -66,7 +48,6 @@ impl ProjectileManager {
         let id = self.next_projectile_id;
         self.next_projectile_id += 1;
         let projectile = Projectile::from_skillshot(
-            id,
             owner_id,
             team_id,
             start_pos,
@@ -80,7 +61,7 @@ impl ProjectileManager {
 
     pub fn create_homing_projectile(
         &mut self,
-        owner_id: u64,
+        owner_id: EntityId,
         team_id: Team,
         target_id: Target,
         start_pos: (u16, u16),
@@ -91,7 +72,6 @@ impl ProjectileManager {
         let id = self.next_projectile_id;
         self.next_projectile_id += 1;
         let projectile = Projectile::from_homing_shot(
-            id,
             owner_id,
             team_id,
             start_pos,
@@ -110,49 +90,50 @@ impl ProjectileManager {
         minions: &HashMap<MinionId, Minion>,
         towers: &HashMap<TowerId, Tower>,
         monsters: &HashMap<MonsterId, Monster>,
-    ) -> (Vec<(usize, Target, Vec<GameplayEffect>)>, Vec<AnimationCommand>) {
+    ) -> (Vec<(Target, Vec<GameplayEffect>)>, Vec<AnimationCommand>) {
         let mut projectiles_to_remove: Vec<u64> = Vec::new();
-        let mut pending_effects: Vec<(usize, Target, Vec<GameplayEffect>)> = Vec::new();
+        let mut pending_effects: Vec<(Target, Vec<GameplayEffect>)> = Vec::new();
         let mut animation_commands_executable: Vec<AnimationCommand> = Vec::new();
 
         for (id, projectile) in self.projectiles.iter_mut() {
             let (target_row, target_col) = match &projectile.pathing {
                 PathingLogic::Straight { .. } => (0, 0),
                 PathingLogic::LockOn { target_id } => match target_id {
-                    Target::Champion(id) => {
-                        if let Some(champion) = champions.get(id) {
+                    Target::Champion(target) => {
+                        // Projectiles chasing a dead champion vanish
+                        if let Some(champion) = champions.get(target).filter(|c| !c.is_dead()) {
                             (champion.row, champion.col)
                         } else {
-                            projectiles_to_remove.push(*id as u64);
+                            projectiles_to_remove.push(*id);
                             continue;
                         }
                     }
-                    Target::Minion(id) => {
-                        if let Some(minion) = minions.get(id) {
+                    Target::Minion(target) => {
+                        if let Some(minion) = minions.get(target) {
                             (minion.row, minion.col)
                         } else {
-                            projectiles_to_remove.push(*id as u64);
+                            projectiles_to_remove.push(*id);
                             continue;
                         }
                     }
-                    Target::Tower(id) => {
-                        if let Some(tower) = towers.get(id) {
+                    Target::Tower(target) => {
+                        if let Some(tower) = towers.get(target) {
                             (tower.row, tower.col)
                         } else {
-                            projectiles_to_remove.push(*id as u64);
+                            projectiles_to_remove.push(*id);
                             continue;
                         }
                     }
-                    Target::Monster(id) => {
-                        if let Some(monster) = monsters.get(id) {
+                    Target::Monster(target) => {
+                        if let Some(monster) = monsters.get(target) {
                             (monster.row, monster.col)
                         } else {
-                            projectiles_to_remove.push(*id as u64);
+                            projectiles_to_remove.push(*id);
                             continue;
                         }
                     }
                     _ => {
-                        projectiles_to_remove.push(*id as u64);
+                        projectiles_to_remove.push(*id);
                         continue;
                     }
                 },
@@ -181,7 +162,6 @@ impl ProjectileManager {
                             Some(CellContent::Champion(target_id, target_team)) => {
                                 hit_target = add_effects(
                                     &mut pending_effects,
-                                    projectile.owner_id as usize,
                                     Target::Champion(target_id),
                                     projectile.payloads.clone(),
                                     projectile.team_id,
@@ -191,7 +171,6 @@ impl ProjectileManager {
                             Some(CellContent::Minion(target_id, target_team)) => {
                                 hit_target = add_effects(
                                     &mut pending_effects,
-                                    projectile.owner_id as usize,
                                     Target::Minion(target_id),
                                     projectile.payloads.clone(),
                                     projectile.team_id,
@@ -201,7 +180,6 @@ impl ProjectileManager {
                             Some(CellContent::Monster(target_id)) => {
                                 hit_target = add_effects(
                                     &mut pending_effects,
-                                    projectile.owner_id as usize,
                                     Target::Monster(target_id),
                                     projectile.payloads.clone(),
                                     projectile.team_id,
@@ -211,7 +189,6 @@ impl ProjectileManager {
                             Some(CellContent::Tower(target_id, target_team)) => {
                                 hit_target = add_effects(
                                     &mut pending_effects,
-                                    projectile.owner_id as usize,
                                     Target::Tower(target_id),
                                     projectile.payloads.clone(),
                                     projectile.team_id,
@@ -246,8 +223,7 @@ impl ProjectileManager {
 }
 
 fn add_effects(
-    pending_effects: &mut Vec<(usize, Target, Vec<GameplayEffect>)>,
-    owner: usize,
+    pending_effects: &mut Vec<(Target, Vec<GameplayEffect>)>,
     target: Target,
     payloads: Vec<GameplayEffect>,
     projectile_team: Team,
@@ -258,21 +234,21 @@ fn add_effects(
         None => true, // Neutral targets (like monsters) are always enemies
     };
 
-    if is_enemy {
-        if !payloads.is_empty() {
-            pending_effects.push((owner, target, payloads));
-            return true;
-        }
+    if is_enemy && !payloads.is_empty() {
+        pending_effects.push((target, payloads));
+        return true;
     }
-    return false;
+    false
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::{ChampionStats, MonsterStats, TowerStats};
+    use crate::config::{AttackType, ChampionStats, MonsterStats, TowerStats, TowerTier};
     use crate::game::cell::{CellAnimation, MonsterId, Team};
     use crate::game::entities::champion::Champion;
+    use crate::game::entities::damage::{Damage, DamageSource};
+    use crate::game::entities::minion::Lane;
     use crate::game::entities::monster::Monster;
     use crate::game::entities::projectile::PathingLogic;
     use crate::game::entities::tower::Tower;
@@ -289,12 +265,19 @@ mod tests {
             health: 500,
             mana: 100,
             armor: 10,
+            magic_resist: 0,
+            armor_penetration_flat: 0,
+            armor_penetration_percent: 0.,
+            crit_chance: 0.,
+            crit_multiplier: 1.75,
             xp_per_level: vec![100, 200],
             level_up_health_increase: 50,
             level_up_attack_damage_increase: 5,
             level_up_armor_increase: 2,
             attack_range_row: 3,
             attack_range_col: 3,
+            attack: AttackType::Melee,
+            movement_speed: 1,
         }
     }
 
@@ -304,27 +287,30 @@ mod tests {
             attack_speed_secs: 2,
             health: 1000,
             armor: 20,
+            magic_resist: 0,
             attack_range_row: 7,
             attack_range_col: 9,
+            damage_ramp_percent: 40,
+            max_damage_ramp_hits: 3,
         }
     }
 
-    fn create_test_monster_stats(id: &str, spawn_row: u16, spawn_col: u16) -> MonsterStats {
+    fn create_test_monster_stats(id: &str) -> MonsterStats {
         MonsterStats {
             id: id.to_string(),
-            spawn_row,
-            spawn_col,
             health: 100,
             armor: 5,
+            magic_resist: 0,
             attack_damage: 10,
-            attack_range_row: 1,
-            attack_range_col: 1,
             aggro_range_row: 8,
             aggro_range_col: 8,
             leash_range: 10,
             xp_reward: 30,
             respawn_timer_secs: 60,
             attack_speed_ms: 1000,
+            movement_speed: 1,
+            reward_buff: None,
+            ability: None,
         }
     }
 
@@ -337,7 +323,10 @@ mod tests {
             (10, 10),
             (20, 20),
             1,
-            vec![GameplayEffect::Damage(50)],
+            vec![GameplayEffect::Damage(Damage::physical(
+                50,
+                DamageSource::Champion(1),
+            ))],
             CellAnimation::Projectile,
         );
         assert_eq!(manager.projectiles.len(), 1);
@@ -354,7 +343,10 @@ mod tests {
             Target::Champion(202),
             (5, 5),
             2,
-            vec![GameplayEffect::Damage(30)],
+            vec![GameplayEffect::Damage(Damage::physical(
+                30,
+                DamageSource::Champion(1),
+            ))],
             CellAnimation::Projectile,
         );
         assert_eq!(manager.projectiles.len(), 1);
@@ -362,38 +354,19 @@ mod tests {
         assert!(matches!(projectile.pathing, PathingLogic::LockOn { .. }));
     }
 
-    #[test]
-    fn test_create_lockon_from_blueprint() {
-        let mut manager = ProjectileManager::new();
-        let blueprint = ProjectileBlueprint {
-            projectile_type: ProjectileType::LockOn,
-            owner_id: 101,
-            team_id: Team::Blue,
-            target_id: Option::Some(Target::Minion(5)),
-            start_pos: (0, 0),
-            end_pos: (10, 10),
-            speed: 2,
-            payloads: vec![GameplayEffect::Damage(5)],
-            visual_cell_type: CellAnimation::Projectile,
-        };
-        manager.create_from_blueprint(blueprint);
-        assert_eq!(manager.projectiles.len(), 1);
-        let projectile = manager.projectiles.get(&0).unwrap();
-        assert!(matches!(projectile.pathing, PathingLogic::LockOn { .. }));
-    }
-
     #[test]
     fn test_create_skillshot_from_blueprint() {
         let mut manager = ProjectileManager::new();
         let blueprint = ProjectileBlueprint {
-            projectile_type: ProjectileType::SkillShot,
             owner_id: 101,
             team_id: Team::Blue,
-            target_id: Option::Some(Target::Minion(5)),
             start_pos: (0, 0),
             end_pos: (10, 10),
             speed: 2,
-            payloads: vec![GameplayEffect::Damage(5)],
+            payloads: vec![GameplayEffect::Damage(Damage::physical(
+                5,
+                DamageSource::Champion(1),
+            ))],
             visual_cell_type: CellAnimation::Projectile,
         };
         manager.create_from_blueprint(blueprint);
@@ -417,7 +390,10 @@ mod tests {
             (0, 0),
             (2, 0),
             1,
-            vec![GameplayEffect::Damage(10)],
+            vec![GameplayEffect::Damage(Damage::physical(
+                10,
+                DamageSource::Champion(1),
+            ))],
             CellAnimation::Projectile,
         );
 
@@ -466,7 +442,10 @@ mod tests {
             (10, 10),
             target_pos,
             1,
-            vec![GameplayEffect::Damage(50)],
+            vec![GameplayEffect::Damage(Damage::physical(
+                50,
+                DamageSource::Champion(1),
+            ))],
             CellAnimation::Projectile,
         );
 
@@ -477,9 +456,12 @@ mod tests {
         // Tick 3: Projectile should hit the target
         let (damages, _) =
             manager.update_and_check_collisions(&board, &champions, &minions, &towers, &monsters);
-        assert_eq!(damages[0].1, Target::Champion(target_id));
-        assert_eq!(damages[0].2.len(), 1);
-        assert!(matches!(damages[0].2[0], GameplayEffect::Damage(50)));
+        assert_eq!(damages[0].0, Target::Champion(target_id));
+        assert_eq!(damages[0].1.len(), 1);
+        assert!(matches!(
+            damages[0].1[0],
+            GameplayEffect::Damage(Damage { amount: 50, .. })
+        ));
         assert!(manager.projectiles.is_empty());
     }
 
@@ -497,6 +479,8 @@ mod tests {
         let target_tower = Tower::new(
             target_id,
             Team::Red,
+            Lane::Mid,
+            TowerTier::Outer,
             target_pos.0,
             target_pos.1,
             mock_tower_stats(),
@@ -514,7 +498,10 @@ mod tests {
             Target::Tower(target_id),
             (0, 2),
             1,
-            vec![GameplayEffect::Damage(50)],
+            vec![GameplayEffect::Damage(Damage::physical(
+                50,
+                DamageSource::Champion(1),
+            ))],
             CellAnimation::Projectile,
         );
 
@@ -525,9 +512,12 @@ mod tests {
             manager.update_and_check_collisions(&board, &champions, &minions, &towers, &monsters);
 
         assert_eq!(damages.len(), 1);
-        assert_eq!(damages[0].1, Target::Tower(target_id));
-        assert_eq!(damages[0].2.len(), 1);
-        assert!(matches!(damages[0].2[0], GameplayEffect::Damage(50)));
+        assert_eq!(damages[0].0, Target::Tower(target_id));
+        assert_eq!(damages[0].1.len(), 1);
+        assert!(matches!(
+            damages[0].1[0],
+            GameplayEffect::Damage(Damage { amount: 50, .. })
+        ));
         assert!(manager.projectiles.is_empty());
     }
 
@@ -542,8 +532,8 @@ mod tests {
 
         let target_id = 101 as MonsterId;
         let target_pos = (10, 12);
-        let monster_stats = create_test_monster_stats("test_monster", target_pos.0, target_pos.1);
-        let target_monster = Monster::new(target_id, monster_stats);
+        let monster_stats = create_test_monster_stats("test_monster");
+        let target_monster = Monster::new(target_id, monster_stats, target_pos);
         monsters.insert(target_id, target_monster);
         board.place_cell(
             CellContent::Monster(target_id),
@@ -557,7 +547,10 @@ mod tests {
             (10, 10),
             target_pos,
             1,
-            vec![GameplayEffect::Damage(50)],
+            vec![GameplayEffect::Damage(Damage::physical(
+                50,
+                DamageSource::Champion(1),
+            ))],
             CellAnimation::Projectile,
         );
 
@@ -569,9 +562,12 @@ mod tests {
         let (damages, _) =
             manager.update_and_check_collisions(&board, &champions, &minions, &towers, &monsters);
         assert_eq!(damages.len(), 1);
-        assert_eq!(damages[0].1, Target::Monster(target_id));
-        assert_eq!(damages[0].2.len(), 1);
-        assert!(matches!(damages[0].2[0], GameplayEffect::Damage(50)));
+        assert_eq!(damages[0].0, Target::Monster(target_id));
+        assert_eq!(damages[0].1.len(), 1);
+        assert!(matches!(
+            damages[0].1[0],
+            GameplayEffect::Damage(Damage { amount: 50, .. })
+        ));
         assert!(manager.projectiles.is_empty());
     }
 
@@ -601,7 +597,10 @@ mod tests {
             Target::Champion(target_id),
             (10, 10),
             1,
-            vec![GameplayEffect::Damage(30)],
+            vec![GameplayEffect::Damage(Damage::physical(
+                30,
+                DamageSource::Champion(1),
+            ))],
             CellAnimation::Projectile,
         );
 
@@ -619,5 +618,33 @@ mod tests {
         let proj2 = manager.projectiles.get(&0).unwrap();
         assert_eq!(proj2.current_position, (11, 12)); // Moves diagonally
     }
-}
 
+    #[test]
+    fn test_homing_projectile_on_a_missing_target_is_removed() {
+        let mut manager = ProjectileManager::new();
+        let board = create_dummy_board(20, 20);
+
+        // The target id differs from the projectile id, which is 0
+        manager.create_homing_projectile(
+            102,
+            Team::Blue,
+            Target::Minion(7),
+            (10, 10),
+            1,
+            vec![GameplayEffect::Damage(Damage::physical(
+                30,
+                DamageSource::Champion(102),
+            ))],
+            CellAnimation::Projectile,
+        );
+
+        manager.update_and_check_collisions(
+            &board,
+            &HashMap::new(),
+            &HashMap::new(),
+            &HashMap::new(),
+            &HashMap::new(),
+        );
+        assert!(manager.projectiles.is_empty());
+    }
+}
@@ -0,0 +1,269 @@
+use std::collections::HashMap;
+
+use super::buffs::{Buff, HasBuff};
+use super::cell::{EntityId, Team};
+use super::entities::monster::MonsterState;
+use super::entities::{Stats, Target, Unit};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EntityKind {
+    Champion,
+    Minion,
+    Tower,
+    Monster,
+}
+
+/// What drives a unit every tick
+#[derive(Debug, Clone, PartialEq)]
+pub enum Ai {
+    /// The actions of its player, none while it waits to respawn
+    Player { dead: bool },
+    /// Walks toward the next waypoint of its lane
+    Lane { goal: (u16, u16) },
+    /// Guards its camp
+    Camp(MonsterState),
+    /// Shoots its target until it dies or leaves the range
+    Turret { target: Option<Target> },
+}
+
+/// Hands out the id of every unit of the game, champions, minions, towers and
+/// monsters share a single sequence so two units never get the same id.
+/// Components are kept per id. Kinds, teams and buffs live here only, the
+/// position, stats and AI are published by the store of each unit once per
+/// tick, see `update`
+#[derive(Debug)]
+pub struct EntityRegistry {
+    next_id: EntityId,
+    kinds: HashMap<EntityId, EntityKind>,
+    teams: HashMap<EntityId, Team>,
+    buffs: HashMap<EntityId, HashMap<String, Box<dyn Buff>>>,
+    positions: HashMap<EntityId, (u16, u16)>,
+    stats: HashMap<EntityId, Stats>,
+    ais: HashMap<EntityId, Ai>,
+}
+
+impl Default for EntityRegistry {
+    fn default() -> Self {
+        EntityRegistry::new()
+    }
+}
+
+impl EntityRegistry {
+    pub fn new() -> Self {
+        EntityRegistry {
+            next_id: 1,
+            kinds: HashMap::new(),
+            teams: HashMap::new(),
+            buffs: HashMap::new(),
+            positions: HashMap::new(),
+            stats: HashMap::new(),
+            ais: HashMap::new(),
+        }
+    }
+
+    /// Registers a new unit, neutral monsters have no team
+    pub fn spawn(&mut self, kind: EntityKind, team: Option<Team>) -> EntityId {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.kinds.insert(id, kind);
+        if let Some(team) = team {
+            self.teams.insert(id, team);
+        }
+        id
+    }
+
+    /// Forgets a dead unit and its components, its id is never handed out
+    /// again
+    pub fn despawn(&mut self, id: EntityId) {
+        self.kinds.remove(&id);
+        self.teams.remove(&id);
+        self.buffs.remove(&id);
+        self.positions.remove(&id);
+        self.stats.remove(&id);
+        self.ais.remove(&id);
+    }
+
+    /// Publishes the position, stats and AI of a live unit
+    pub fn update(&mut self, unit: &dyn Unit) {
+        let id = unit.id();
+        if !self.kinds.contains_key(&id) {
+            return;
+        }
+        self.positions.insert(id, unit.position());
+        self.stats.insert(id, unit.stats().clone());
+        self.ais.insert(id, unit.ai());
+    }
+
+    pub fn kind(&self, id: EntityId) -> Option<EntityKind> {
+        self.kinds.get(&id).copied()
+    }
+
+    pub fn team(&self, id: EntityId) -> Option<Team> {
+        self.teams.get(&id).copied()
+    }
+
+    pub fn is(&self, id: EntityId, kind: EntityKind) -> bool {
+        self.kind(id) == Some(kind)
+    }
+
+    /// Position as of the last `update`
+    pub fn position(&self, id: EntityId) -> Option<(u16, u16)> {
+        self.positions.get(&id).copied()
+    }
+
+    /// Stats as of the last `update`
+    pub fn stats(&self, id: EntityId) -> Option<&Stats> {
+        self.stats.get(&id)
+    }
+
+    /// AI state as of the last `update`
+    pub fn ai(&self, id: EntityId) -> Option<&Ai> {
+        self.ais.get(&id)
+    }
+
+    /// Applies a buff to the unit `id`, a refreshed buff undoes the previous
+    /// one first
+    pub fn add_buff(&mut self, id: EntityId, mut buff: Box<dyn Buff>, unit: &mut dyn HasBuff) {
+        if !self.kinds.contains_key(&id) {
+            return;
+        }
+        let buffs = self.buffs.entry(id).or_default();
+        if let Some(mut previous) = buffs.remove(buff.id()) {
+            previous.on_remove(unit);
+        }
+        buff.on_apply(unit);
+        buffs.insert(buff.id().to_string(), buff);
+    }
+
+    /// Ticks every buff of the unit `id`, expired buffs are removed
+    pub fn tick_buffs(&mut self, id: EntityId, unit: &mut dyn HasBuff) {
+        if let Some(buffs) = self.buffs.get_mut(&id) {
+            buffs.retain(|_, buff| {
+                let expired = buff.on_tick(unit);
+                if expired {
+                    buff.on_remove(unit);
+                }
+                !expired
+            });
+            if buffs.is_empty() {
+                self.buffs.remove(&id);
+            }
+        }
+    }
+
+    /// Removes every buff of the unit `id`, e.g. when a champion dies
+    pub fn clear_buffs(&mut self, id: EntityId, unit: &mut dyn HasBuff) {
+        for (_, mut buff) in self.buffs.remove(&id).unwrap_or_default() {
+            buff.on_remove(unit);
+        }
+    }
+
+    /// Ids of the units holding at least one buff, ordered by id
+    pub fn buffed(&self) -> Vec<EntityId> {
+        let mut ids: Vec<EntityId> = self.buffs.keys().copied().collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    pub fn buffs(&self, id: EntityId) -> impl Iterator<Item = &str> {
+        self.buffs
+            .get(&id)
+            .into_iter()
+            .flat_map(|buffs| buffs.keys().map(String::as_str))
+    }
+
+    /// Live units of a kind, ordered by id
+    pub fn ids(&self, kind: EntityKind) -> Vec<EntityId> {
+        let mut ids: Vec<EntityId> = self
+            .kinds
+            .iter()
+            .filter(|(_, entity_kind)| **entity_kind == kind)
+            .map(|(id, _)| *id)
+            .collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    pub fn len(&self) -> usize {
+        self.kinds.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.kinds.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ids_are_unique_across_kinds() {
+        let mut registry = EntityRegistry::new();
+        let tower = registry.spawn(EntityKind::Tower, Some(Team::Blue));
+        let minion = registry.spawn(EntityKind::Minion, Some(Team::Red));
+        let monster = registry.spawn(EntityKind::Monster, None);
+        let champion = registry.spawn(EntityKind::Champion, Some(Team::Blue));
+
+        let mut ids = vec![tower, minion, monster, champion];
+        ids.dedup();
+        assert_eq!(ids.len(), 4);
+        assert_eq!(registry.kind(monster), Some(EntityKind::Monster));
+        assert_eq!(registry.team(monster), None);
+        assert_eq!(registry.team(minion), Some(Team::Red));
+        assert!(registry.is(champion, EntityKind::Champion));
+        assert_eq!(registry.ids(EntityKind::Tower), vec![tower]);
+    }
+
+    #[test]
+    fn test_refreshed_buff_replaces_the_previous_one() {
+        use crate::game::buffs::stun_buff::StunBuff;
+        use std::time::Duration;
+
+        #[derive(Default)]
+        struct Dummy {
+            stuns: u8,
+        }
+
+        impl HasBuff for Dummy {
+            fn is_stunned(&self) -> bool {
+                self.stuns > 0
+            }
+
+            fn set_stunned(&mut self, stunned: bool, _duration: Option<Duration>) {
+                if stunned {
+                    self.stuns += 1;
+                } else {
+                    self.stuns -= 1;
+                }
+            }
+        }
+
+        let mut registry = EntityRegistry::new();
+        let id = registry.spawn(EntityKind::Minion, Some(Team::Red));
+        let mut unit = Dummy::default();
+        registry.add_buff(id, Box::new(StunBuff::new(5)), &mut unit);
+        registry.add_buff(id, Box::new(StunBuff::new(5)), &mut unit);
+        assert_eq!(unit.stuns, 1);
+        assert_eq!(registry.buffs(id).collect::<Vec<_>>(), vec!["Stun"]);
+
+        // Buffs of a dead unit go with it
+        registry.despawn(id);
+        assert!(registry.buffed().is_empty());
+        registry.add_buff(id, Box::new(StunBuff::new(5)), &mut unit);
+        assert!(registry.buffed().is_empty());
+    }
+
+    #[test]
+    fn test_despawned_ids_are_not_reused() {
+        let mut registry = EntityRegistry::new();
+        let first = registry.spawn(EntityKind::Minion, Some(Team::Red));
+        registry.despawn(first);
+        assert_eq!(registry.kind(first), None);
+        assert!(registry.is_empty());
+
+        let second = registry.spawn(EntityKind::Minion, Some(Team::Red));
+        assert_ne!(first, second);
+        assert_eq!(registry.len(), 1);
+    }
+}
@@ -0,0 +1,203 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use super::PlayerId;
+
+/// Counters of a player for the whole match
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PlayerScore {
+    pub kills: u16,
+    pub deaths: u16,
+    pub assists: u16,
+    pub minion_kills: u16,
+    pub monster_kills: u16,
+    pub towers_destroyed: u16,
+    pub damage_dealt: u32,
+    pub damage_taken: u32,
+}
+
+/// Credit given for the death of a champion
+#[derive(Debug, PartialEq, Eq)]
+pub struct ChampionKill {
+    pub killer: Option<PlayerId>,
+    pub assists: Vec<PlayerId>,
+}
+
+/// Keeps the score of every player, and the champions who recently hurt each
+/// champion to credit kills and assists
+pub struct Scoreboard {
+    scores: HashMap<PlayerId, PlayerScore>,
+    recent_damage: HashMap<PlayerId, Vec<(PlayerId, Instant)>>,
+    assist_window: Duration,
+}
+
+impl Scoreboard {
+    pub fn new(assist_window: Duration) -> Self {
+        Scoreboard {
+            scores: HashMap::new(),
+            recent_damage: HashMap::new(),
+            assist_window,
+        }
+    }
+
+    pub fn add_player(&mut self, player_id: PlayerId) {
+        self.scores.entry(player_id).or_default();
+    }
+
+    pub fn score(&self, player_id: PlayerId) -> PlayerScore {
+        self.scores.get(&player_id).copied().unwrap_or_default()
+    }
+
+    /// Scores of every player, ordered by player id
+    pub fn scores(&self) -> Vec<(PlayerId, PlayerScore)> {
+        let mut scores: Vec<(PlayerId, PlayerScore)> = self
+            .scores
+            .iter()
+            .map(|(player_id, score)| (*player_id, *score))
+            .collect();
+        scores.sort_by_key(|(player_id, _)| *player_id);
+        scores
+    }
+
+    /// Damage dealt by a champion, after armor
+    pub fn record_damage_dealt(&mut self, attacker: PlayerId, amount: u16) {
+        let score = self.scores.entry(attacker).or_default();
+        score.damage_dealt = score.damage_dealt.saturating_add(amount as u32);
+    }
+
+    /// Damage taken by a champion, remembered for the assist window when it
+    /// comes from another champion
+    pub fn record_champion_hit(
+        &mut self,
+        attacker: Option<PlayerId>,
+        victim: PlayerId,
+        amount: u16,
+    ) {
+        if amount == 0 {
+            return;
+        }
+        let score = self.scores.entry(victim).or_default();
+        score.damage_taken = score.damage_taken.saturating_add(amount as u32);
+        if let Some(attacker) = attacker
+            && attacker != victim
+        {
+            self.record_damage_dealt(attacker, amount);
+            self.recent_damage
+                .entry(victim)
+                .or_default()
+                .push((attacker, Instant::now()));
+        }
+    }
+
+    /// The last champion to hit the victim within the window gets the kill,
+    /// the other champions who hit it within the window an assist
+    pub fn record_champion_death(&mut self, victim: PlayerId) -> ChampionKill {
+        self.scores.entry(victim).or_default().deaths += 1;
+
+        let hits = self.recent_damage.remove(&victim).unwrap_or_default();
+        let recent: Vec<PlayerId> = hits
+            .into_iter()
+            .filter(|(_, at)| at.elapsed() <= self.assist_window)
+            .map(|(attacker, _)| attacker)
+            .collect();
+        let killer = recent.last().copied();
+        let mut assists: Vec<PlayerId> = Vec::new();
+        for attacker in recent {
+            if Some(attacker) != killer && !assists.contains(&attacker) {
+                assists.push(attacker);
+            }
+        }
+
+        if let Some(killer) = killer {
+            self.scores.entry(killer).or_default().kills += 1;
+        }
+        for assist in assists.iter() {
+            self.scores.entry(*assist).or_default().assists += 1;
+        }
+        ChampionKill { killer, assists }
+    }
+
+    pub fn record_minion_kill(&mut self, player_id: PlayerId) {
+        self.scores.entry(player_id).or_default().minion_kills += 1;
+    }
+
+    pub fn record_monster_kill(&mut self, player_id: PlayerId) {
+        self.scores.entry(player_id).or_default().monster_kills += 1;
+    }
+
+    pub fn record_tower_destroyed(&mut self, player_id: PlayerId) {
+        self.scores.entry(player_id).or_default().towers_destroyed += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_last_hitter_gets_the_kill_and_others_assist() {
+        let mut scoreboard = Scoreboard::new(Duration::from_secs(10));
+        scoreboard.record_champion_hit(Some(2), 1, 30);
+        scoreboard.record_champion_hit(Some(3), 1, 20);
+        scoreboard.record_champion_hit(Some(2), 1, 10);
+        scoreboard.record_champion_hit(Some(4), 1, 40);
+
+        let kill = scoreboard.record_champion_death(1);
+        assert_eq!(kill.killer, Some(4));
+        assert_eq!(kill.assists, vec![2, 3]);
+
+        assert_eq!(scoreboard.score(1).deaths, 1);
+        assert_eq!(scoreboard.score(1).damage_taken, 100);
+        assert_eq!(scoreboard.score(4).kills, 1);
+        assert_eq!(scoreboard.score(2).assists, 1);
+        assert_eq!(scoreboard.score(2).damage_dealt, 40);
+        assert_eq!(scoreboard.score(3).assists, 1);
+    }
+
+    #[test]
+    fn test_hits_outside_the_window_are_not_credited() {
+        let mut scoreboard = Scoreboard::new(Duration::ZERO);
+        scoreboard.record_champion_hit(Some(2), 1, 30);
+        std::thread::sleep(Duration::from_millis(5));
+
+        let kill = scoreboard.record_champion_death(1);
+        assert_eq!(kill.killer, None);
+        assert!(kill.assists.is_empty());
+        assert_eq!(scoreboard.score(2).kills, 0);
+        // The damage still counts
+        assert_eq!(scoreboard.score(2).damage_dealt, 30);
+    }
+
+    #[test]
+    fn test_death_resets_recent_damage() {
+        let mut scoreboard = Scoreboard::new(Duration::from_secs(10));
+        scoreboard.record_champion_hit(Some(2), 1, 30);
+        scoreboard.record_champion_death(1);
+
+        // Killed by a minion right after respawning
+        scoreboard.record_champion_hit(None, 1, 30);
+        let kill = scoreboard.record_champion_death(1);
+        assert_eq!(kill.killer, None);
+        assert_eq!(scoreboard.score(1).deaths, 2);
+        assert_eq!(scoreboard.score(2).kills, 1);
+    }
+
+    #[test]
+    fn test_scores_are_ordered_by_player() {
+        let mut scoreboard = Scoreboard::new(Duration::from_secs(10));
+        scoreboard.add_player(3);
+        scoreboard.add_player(1);
+        scoreboard.record_minion_kill(1);
+        scoreboard.record_monster_kill(3);
+        scoreboard.record_tower_destroyed(3);
+
+        let scores = scoreboard.scores();
+        assert_eq!(scores.len(), 2);
+        assert_eq!(scores[0].0, 1);
+        assert_eq!(scores[0].1.minion_kills, 1);
+        assert_eq!(scores[1].1.monster_kills, 1);
+        assert_eq!(scores[1].1.towers_destroyed, 1);
+    }
+}
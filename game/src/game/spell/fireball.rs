@@ -5,14 +5,19 @@ use crate::game::projectile_manager::ProjectileManager;
 use crate::game::{
     Champion,
     cell::CellAnimation,
-    entities::{champion::Direction, projectile::GameplayEffect},
+    entities::{
+        champion::Direction,
+        damage::{Damage, DamageSource},
+        projectile::GameplayEffect,
+    },
 };
 
-use super::{ProjectileBlueprint, ProjectileType, Spell};
+use super::{CastError, ProjectileBlueprint, Spell, cooldown_remaining};
 
 #[derive(Debug, Clone)]
 pub struct FireballSpell {
     last_casted: Option<Instant>,
+    rank: u8,
     stats: SpellStats,
 }
 
@@ -20,6 +25,7 @@ impl FireballSpell {
     pub fn new(spell_stats: SpellStats) -> FireballSpell {
         FireballSpell {
             last_casted: None,
+            rank: 1,
             stats: spell_stats,
         }
     }
@@ -31,11 +37,30 @@ impl Spell for FireballSpell {
     }
 
     fn mana_cost(&self) -> &u16 {
-        &self.stats.mana_cost
+        &self.stats.rank_stats(self.rank).mana_cost
     }
 
-    fn clone_box(&self) -> Box<dyn Spell> {
-        Box::new(self.clone())
+    fn rank(&self) -> u8 {
+        self.rank
+    }
+
+    fn max_rank(&self) -> u8 {
+        self.stats.max_rank()
+    }
+
+    fn rank_up(&mut self) -> bool {
+        if self.rank >= self.stats.max_rank() {
+            return false;
+        }
+        self.rank += 1;
+        true
+    }
+
+    fn cooldown_remaining(&self) -> Duration {
+        cooldown_remaining(
+            self.last_casted,
+            self.stats.rank_stats(self.rank).cooldown_secs,
+        )
     }
 
     fn cast(
@@ -43,24 +68,27 @@ impl Spell for FireballSpell {
         caster: &mut Champion,
         caster_damage: u16,
         projectile_manager: &mut ProjectileManager,
-    ) {
+    ) -> Result<(), CastError> {
+        let rank_stats = self.stats.rank_stats(self.rank).clone();
         // Cooldown check
-        if let Some(last_casted) = self.last_casted {
-            if last_casted.elapsed() < Duration::from_secs(self.stats.cooldown_secs as u64) {
-                return ();
-            }
+        let cooldown_remaining = self.cooldown_remaining();
+        if !cooldown_remaining.is_zero() {
+            return Err(CastError::OnCooldown(cooldown_remaining));
         }
         // Mana check
-        if caster.stats.mana < self.stats.mana_cost {
-            return ();
+        if caster.stats.mana < rank_stats.mana_cost {
+            return Err(CastError::NotEnoughMana {
+                required: rank_stats.mana_cost,
+                available: caster.stats.mana,
+            });
         } else {
-            caster.stats.mana -= self.stats.mana_cost;
+            caster.stats.mana -= rank_stats.mana_cost;
         }
 
         self.last_casted = Some(Instant::now());
 
         let spell_damage =
-            (caster_damage as f32 * self.stats.damage_ratio + self.stats.base_damage as f32) as u16;
+            (caster_damage as f32 * rank_stats.damage_ratio + rank_stats.base_damage as f32) as u16;
 
         let (proj_start_row, proj_start_col) = match caster.direction {
             Direction::Up => (caster.row.saturating_sub(1), caster.col),
@@ -77,16 +105,18 @@ impl Spell for FireballSpell {
         };
 
         let blueprint = ProjectileBlueprint {
-            projectile_type: ProjectileType::SkillShot,
-            owner_id: caster.player_id as u64,
+            owner_id: caster.player_id,
             team_id: caster.team_id,
-            target_id: None,
             start_pos: (proj_start_row, proj_start_col),
             end_pos: (proj_end_row, proj_end_col),
             speed: self.stats.speed,
-            payloads: vec![GameplayEffect::Damage(spell_damage)],
+            payloads: vec![GameplayEffect::Damage(Damage::magic(
+                spell_damage,
+                DamageSource::Champion(caster.player_id),
+            ))],
             visual_cell_type: CellAnimation::FireBall,
         };
         projectile_manager.create_from_blueprint(blueprint);
+        Ok(())
     }
 }
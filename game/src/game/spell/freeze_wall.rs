@@ -6,14 +6,19 @@ use crate::game::projectile_manager::ProjectileManager;
 use crate::game::{
     Champion,
     cell::CellAnimation,
-    entities::{champion::Direction, projectile::GameplayEffect},
+    entities::{
+        champion::Direction,
+        damage::{Damage, DamageSource},
+        projectile::GameplayEffect,
+    },
 };
 
-use super::{ProjectileBlueprint, ProjectileType, Spell};
+use super::{CastError, ProjectileBlueprint, Spell, cooldown_remaining};
 
 #[derive(Debug, Clone)]
 pub struct FreezeWallSpell {
     last_casted: Option<Instant>,
+    rank: u8,
     stats: SpellStats,
 }
 
@@ -21,6 +26,7 @@ impl FreezeWallSpell {
     pub fn new(spell_stats: SpellStats) -> FreezeWallSpell {
         FreezeWallSpell {
             last_casted: None,
+            rank: 1,
             stats: spell_stats,
         }
     }
@@ -32,11 +38,30 @@ impl Spell for FreezeWallSpell {
     }
 
     fn mana_cost(&self) -> &u16 {
-        &self.stats.mana_cost
+        &self.stats.rank_stats(self.rank).mana_cost
     }
 
-    fn clone_box(&self) -> Box<dyn Spell> {
-        Box::new(self.clone())
+    fn rank(&self) -> u8 {
+        self.rank
+    }
+
+    fn max_rank(&self) -> u8 {
+        self.stats.max_rank()
+    }
+
+    fn rank_up(&mut self) -> bool {
+        if self.rank >= self.stats.max_rank() {
+            return false;
+        }
+        self.rank += 1;
+        true
+    }
+
+    fn cooldown_remaining(&self) -> Duration {
+        cooldown_remaining(
+            self.last_casted,
+            self.stats.rank_stats(self.rank).cooldown_secs,
+        )
     }
 
     fn cast(
@@ -44,25 +69,27 @@ impl Spell for FreezeWallSpell {
         caster: &mut Champion,
         caster_damage: u16,
         projectile_manager: &mut ProjectileManager,
-    ) {
-        // TODO: return Err maybe instead of empty Vec
+    ) -> Result<(), CastError> {
+        let rank_stats = self.stats.rank_stats(self.rank).clone();
         // Cooldown check
-        if let Some(last_casted) = self.last_casted {
-            if last_casted.elapsed() < Duration::from_secs(self.stats.cooldown_secs as u64) {
-                return ();
-            }
+        let cooldown_remaining = self.cooldown_remaining();
+        if !cooldown_remaining.is_zero() {
+            return Err(CastError::OnCooldown(cooldown_remaining));
         }
         // Mana check
-        if caster.stats.mana < self.stats.mana_cost {
-            return ();
+        if caster.stats.mana < rank_stats.mana_cost {
+            return Err(CastError::NotEnoughMana {
+                required: rank_stats.mana_cost,
+                available: caster.stats.mana,
+            });
         } else {
-            caster.stats.mana -= self.stats.mana_cost;
+            caster.stats.mana -= rank_stats.mana_cost;
         }
 
         self.last_casted = Some(Instant::now());
 
         let spell_damage =
-            (caster_damage as f32 * self.stats.damage_ratio + self.stats.base_damage as f32) as u16;
+            (caster_damage as f32 * rank_stats.damage_ratio + rank_stats.base_damage as f32) as u16;
 
         let (wall_center_row, wall_center_col) = match caster.direction {
             Direction::Up => (caster.row.saturating_sub(1), caster.col),
@@ -105,18 +132,19 @@ impl Spell for FreezeWallSpell {
             // Once build.rs is done we would check that stun duration is initalized
             // We will then always be sure to have Some(duration)
             let mut payloads: Vec<GameplayEffect> = Vec::new();
-            if let Some(duration) = self.stats.stun_duration {
+            if let Some(duration) = rank_stats.stun_duration {
                 payloads = vec![
-                    GameplayEffect::Damage(spell_damage),
+                    GameplayEffect::Damage(Damage::magic(
+                        spell_damage,
+                        DamageSource::Champion(caster.player_id),
+                    )),
                     GameplayEffect::Buff(Box::new(StunBuff::new(duration as u64))),
                 ];
             };
 
             let blueprint = ProjectileBlueprint {
-                projectile_type: ProjectileType::SkillShot,
-                owner_id: caster.player_id as u64,
+                owner_id: caster.player_id,
                 team_id: caster.team_id,
-                target_id: None,
                 start_pos: (proj_start_row, proj_start_col),
                 end_pos: (proj_end_row, proj_end_col),
                 speed: self.stats.speed,
@@ -125,5 +153,6 @@ impl Spell for FreezeWallSpell {
             };
             projectile_manager.create_from_blueprint(blueprint);
         }
+        Ok(())
     }
 }
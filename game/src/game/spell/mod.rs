@@ -1,21 +1,22 @@
 use std::fmt::Debug;
+use std::time::{Duration, Instant};
+
+use thiserror::Error;
 
 use super::{
     Champion,
-    cell::{CellAnimation, Team},
-    entities::{Target, projectile::GameplayEffect},
+    cell::{CellAnimation, EntityId, Team},
+    entities::projectile::GameplayEffect,
     projectile_manager::ProjectileManager,
 };
-use crate::config::SpellStats;
+use crate::config::{SpellKind, SpellStats};
 
 pub mod fireball;
 pub mod freeze_wall;
 
 pub struct ProjectileBlueprint {
-    pub projectile_type: ProjectileType,
-    pub owner_id: u64,
+    pub owner_id: EntityId,
     pub team_id: Team,
-    pub target_id: Option<Target>,
     pub start_pos: (u16, u16),
     pub end_pos: (u16, u16),
     pub speed: u32,
@@ -23,9 +24,12 @@ pub struct ProjectileBlueprint {
     pub visual_cell_type: CellAnimation,
 }
 
-pub enum ProjectileType {
-    LockOn,
-    SkillShot,
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum CastError {
+    #[error("Spell is on cooldown for {0:?}")]
+    OnCooldown(Duration),
+    #[error("Not enough mana: {required} needed, {available} available")]
+    NotEnoughMana { required: u16, available: u16 },
 }
 
 pub trait Spell: Send + Sync + Debug + 'static {
@@ -36,15 +40,31 @@ pub trait Spell: Send + Sync + Debug + 'static {
         caster: &mut Champion,
         caster_damage: u16,
         projectile_manager: &mut ProjectileManager,
-    );
-    fn clone_box(&self) -> Box<dyn Spell>;
+    ) -> Result<(), CastError>;
+    fn rank(&self) -> u8;
+    fn max_rank(&self) -> u8;
+    // Raise the spell by one rank, return false if already at max rank
+    fn rank_up(&mut self) -> bool;
+    fn cooldown_remaining(&self) -> Duration;
+    // Cooldown is over and the caster has enough mana
+    fn is_ready(&self, caster_mana: u16) -> bool {
+        self.cooldown_remaining().is_zero() && caster_mana >= *self.mana_cost()
+    }
+}
+
+pub fn cooldown_remaining(last_casted: Option<Instant>, cooldown_secs: u8) -> Duration {
+    match last_casted {
+        Some(last_casted) => {
+            Duration::from_secs(cooldown_secs as u64).saturating_sub(last_casted.elapsed())
+        }
+        None => Duration::ZERO,
+    }
 }
 
-pub fn create_spell_from_id(id: u8, stats: SpellStats) -> Box<dyn Spell> {
-    match id {
-        0 => Box::new(freeze_wall::FreezeWallSpell::new(stats)),
-        1 => Box::new(fireball::FireballSpell::new(stats)),
-        _ => panic!("Unknown spell ID: {}", id),
+pub fn create_spell(stats: SpellStats) -> Box<dyn Spell> {
+    match stats.kind {
+        SpellKind::FreezeWall => Box::new(freeze_wall::FreezeWallSpell::new(stats)),
+        SpellKind::Fireball => Box::new(fireball::FireballSpell::new(stats)),
     }
 }
 
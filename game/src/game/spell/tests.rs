@@ -1,16 +1,20 @@
 use crate::game::entities::projectile::PathingLogic;
 use std::collections::HashMap;
+use std::time::Duration;
 
 use crate::{
-    config::{ChampionStats, SpellStats},
+    config::{AttackType, ChampionStats, SpellKind, SpellRankStats, SpellStats},
     game::{
         cell::Team,
         entities::{
             champion::{Champion, Direction},
+            damage::{Damage, DamageSource},
             projectile::GameplayEffect,
         },
         projectile_manager::ProjectileManager,
-        spell::{Spell, fireball::FireballSpell, freeze_wall::FreezeWallSpell},
+        spell::{
+            CastError, Spell, create_spell, fireball::FireballSpell, freeze_wall::FreezeWallSpell,
+        },
     },
 };
 
@@ -21,40 +25,64 @@ fn mock_champion_stats() -> ChampionStats {
         health: 500,
         mana: 500,
         armor: 10,
+        magic_resist: 0,
+        armor_penetration_flat: 0,
+        armor_penetration_percent: 0.,
+        crit_chance: 0.,
+        crit_multiplier: 1.75,
         xp_per_level: vec![100, 200],
         level_up_health_increase: 50,
         level_up_attack_damage_increase: 5,
         level_up_armor_increase: 2,
         attack_range_row: 3,
         attack_range_col: 3,
+        attack: AttackType::Melee,
+        movement_speed: 1,
     }
 }
 
 fn mock_fireball_spell_stats() -> SpellStats {
     SpellStats {
         id: 1,
-        mana_cost: 50,
-        damage_ratio: 1.2,
-        base_damage: 60,
+        kind: SpellKind::Fireball,
         range: 5,
-        cooldown_secs: 10,
         speed: 1,
         width: 1,
-        stun_duration: None,
+        signature: false,
+        ranks: vec![
+            SpellRankStats {
+                mana_cost: 50,
+                cooldown_secs: 10,
+                damage_ratio: 1.2,
+                base_damage: 60,
+                stun_duration: None,
+            },
+            SpellRankStats {
+                mana_cost: 60,
+                cooldown_secs: 8,
+                damage_ratio: 1.5,
+                base_damage: 90,
+                stun_duration: None,
+            },
+        ],
     }
 }
 
 fn mock_freezewall_spell_stats() -> SpellStats {
     SpellStats {
         id: 0,
-        mana_cost: 100,
-        damage_ratio: 0.8,
-        base_damage: 40,
+        kind: SpellKind::FreezeWall,
         range: 3,
-        cooldown_secs: 20,
         speed: 1,
         width: 3,
-        stun_duration: Some(2),
+        signature: false,
+        ranks: vec![SpellRankStats {
+            mana_cost: 100,
+            cooldown_secs: 20,
+            damage_ratio: 0.8,
+            base_damage: 40,
+            stun_duration: Some(2),
+        }],
     }
 }
 
@@ -65,7 +93,11 @@ fn test_fireball_cast_creates_projectile() {
     let mut fireball_spell = FireballSpell::new(mock_fireball_spell_stats());
     let mut projectile_manager = ProjectileManager::new();
 
-    fireball_spell.cast(&mut champion, 50, &mut projectile_manager);
+    assert!(
+        fireball_spell
+            .cast(&mut champion, 50, &mut projectile_manager)
+            .is_ok()
+    );
 
     assert_eq!(projectile_manager.projectiles.len(), 1);
     let projectile = projectile_manager.projectiles.values().next().unwrap();
@@ -88,7 +120,10 @@ fn test_fireball_cast_creates_projectile() {
 
     assert_eq!(
         projectile.payloads,
-        vec![GameplayEffect::Damage((50.0 * 1.2 + 60.0) as u16)]
+        vec![GameplayEffect::Damage(Damage::magic(
+            (50.0 * 1.2 + 60.0) as u16,
+            DamageSource::Champion(1)
+        ))]
     );
 }
 
@@ -99,11 +134,16 @@ fn test_fireball_cast_respects_cooldown() {
     let mut projectile_manager = ProjectileManager::new();
 
     // First cast
-    fireball_spell.cast(&mut champion, 50, &mut projectile_manager);
+    assert!(
+        fireball_spell
+            .cast(&mut champion, 50, &mut projectile_manager)
+            .is_ok()
+    );
     assert_eq!(projectile_manager.projectiles.len(), 1);
 
     // Second cast, should be on cooldown
-    fireball_spell.cast(&mut champion, 50, &mut projectile_manager);
+    let result = fireball_spell.cast(&mut champion, 50, &mut projectile_manager);
+    assert!(matches!(result, Err(CastError::OnCooldown(_))));
     assert_eq!(projectile_manager.projectiles.len(), 1);
 }
 
@@ -114,7 +154,15 @@ fn test_fireball_cast_checks_mana() {
     let mut fireball_spell = FireballSpell::new(mock_fireball_spell_stats());
     let mut projectile_manager = ProjectileManager::new();
 
-    fireball_spell.cast(&mut champion, 50, &mut projectile_manager);
+    let result = fireball_spell.cast(&mut champion, 50, &mut projectile_manager);
+
+    assert_eq!(
+        result,
+        Err(CastError::NotEnoughMana {
+            required: 50,
+            available: 20
+        })
+    );
 
     assert_eq!(projectile_manager.projectiles.len(), 0);
 }
@@ -126,7 +174,11 @@ fn test_freezewall_cast_creates_multiple_projectiles() {
     let mut freezewall_spell = FreezeWallSpell::new(mock_freezewall_spell_stats());
     let mut projectile_manager = ProjectileManager::new();
 
-    freezewall_spell.cast(&mut champion, 50, &mut projectile_manager);
+    assert!(
+        freezewall_spell
+            .cast(&mut champion, 50, &mut projectile_manager)
+            .is_ok()
+    );
 
     assert_eq!(projectile_manager.projectiles.len(), 3);
     let mut projectiles: Vec<_> = projectile_manager.projectiles.values().collect();
@@ -186,11 +238,16 @@ fn test_freezewall_cast_respects_cooldown() {
     let mut projectile_manager = ProjectileManager::new();
 
     // First cast
-    freezewall_spell.cast(&mut champion, 50, &mut projectile_manager);
+    assert!(
+        freezewall_spell
+            .cast(&mut champion, 50, &mut projectile_manager)
+            .is_ok()
+    );
     assert_eq!(projectile_manager.projectiles.len(), 3);
 
     // Second cast, should be on cooldown
-    freezewall_spell.cast(&mut champion, 50, &mut projectile_manager);
+    let result = freezewall_spell.cast(&mut champion, 50, &mut projectile_manager);
+    assert!(matches!(result, Err(CastError::OnCooldown(_))));
     assert_eq!(projectile_manager.projectiles.len(), 3);
 }
 
@@ -201,7 +258,100 @@ fn test_freezewall_cast_checks_mana() {
     let mut freezewall_spell = FreezeWallSpell::new(mock_freezewall_spell_stats());
     let mut projectile_manager = ProjectileManager::new();
 
-    freezewall_spell.cast(&mut champion, 50, &mut projectile_manager);
+    let result = freezewall_spell.cast(&mut champion, 50, &mut projectile_manager);
+
+    assert_eq!(
+        result,
+        Err(CastError::NotEnoughMana {
+            required: 100,
+            available: 50
+        })
+    );
 
     assert_eq!(projectile_manager.projectiles.len(), 0);
 }
+
+#[test]
+fn test_spell_rank_up_stops_at_max_rank() {
+    let mut fireball_spell = FireballSpell::new(mock_fireball_spell_stats());
+    assert_eq!(fireball_spell.rank(), 1);
+    assert_eq!(fireball_spell.max_rank(), 2);
+
+    assert!(fireball_spell.rank_up(), "Rank 1 -> 2 should succeed");
+    assert_eq!(fireball_spell.rank(), 2);
+
+    assert!(!fireball_spell.rank_up(), "Spell is already at max rank");
+    assert_eq!(fireball_spell.rank(), 2);
+}
+
+#[test]
+fn test_fireball_cast_uses_current_rank_values() {
+    let mut champion = Champion::new(1, Team::Blue, 10, 10, mock_champion_stats(), HashMap::new());
+    champion.direction = Direction::Right;
+    let mut fireball_spell = FireballSpell::new(mock_fireball_spell_stats());
+    let mut projectile_manager = ProjectileManager::new();
+    fireball_spell.rank_up();
+
+    assert!(
+        fireball_spell
+            .cast(&mut champion, 50, &mut projectile_manager)
+            .is_ok()
+    );
+
+    assert_eq!(
+        champion.stats.mana,
+        500 - 60,
+        "Rank 2 mana cost should be used"
+    );
+    let projectile = projectile_manager.projectiles.values().next().unwrap();
+    assert_eq!(
+        projectile.payloads,
+        vec![GameplayEffect::Damage(Damage::magic(
+            (50.0 * 1.5 + 90.0) as u16,
+            DamageSource::Champion(1)
+        ))]
+    );
+}
+
+#[test]
+fn test_spell_cooldown_remaining() {
+    let mut champion = Champion::new(1, Team::Blue, 10, 10, mock_champion_stats(), HashMap::new());
+    let mut fireball_spell = FireballSpell::new(mock_fireball_spell_stats());
+    let mut projectile_manager = ProjectileManager::new();
+
+    assert!(
+        fireball_spell.cooldown_remaining().is_zero(),
+        "A spell never casted should be ready"
+    );
+
+    assert!(
+        fireball_spell
+            .cast(&mut champion, 50, &mut projectile_manager)
+            .is_ok()
+    );
+
+    let remaining = fireball_spell.cooldown_remaining();
+    assert!(remaining > Duration::from_secs(9));
+    assert!(remaining <= Duration::from_secs(10));
+}
+
+#[test]
+fn test_spells_are_created_from_their_kind() {
+    let mut stats = mock_fireball_spell_stats();
+    stats.id = 6;
+    let spell = create_spell(stats);
+    assert_eq!(spell.id(), 6);
+    assert!(format!("{:?}", spell).starts_with("FireballSpell"));
+
+    let mut stats = mock_freezewall_spell_stats();
+    stats.id = 7;
+    let spell = create_spell(stats);
+    assert_eq!(spell.id(), 7);
+    assert!(format!("{:?}", spell).starts_with("FreezeWallSpell"));
+}
+
+#[test]
+fn test_unknown_spell_kind_is_rejected() {
+    let spell = "id = 6\nkind = \"meteor\"\nrange = 1\nspeed = 1\nwidth = 1\nranks = []\n";
+    assert!(toml::from_str::<SpellStats>(spell).is_err());
+}
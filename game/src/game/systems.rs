@@ -0,0 +1,947 @@
+use std::{
+    collections::HashMap,
+    mem::take,
+    time::{Duration, Instant},
+};
+
+use log::{debug, info, warn};
+
+use super::{
+    ClientMessage, GameManager, PlayerId, SCOREBOARD_INTERVAL_TICKS, TICK_RATE_INTERVAL_TICKS,
+    animation::{AnimationCommand, AnimationTrait},
+    buffs::HasBuff,
+    cell::Team,
+    entities::{
+        AttackAction, Fighter, Target, Unit,
+        projectile::{GameplayEffect, damage_source, split_buffs},
+    },
+    monster_manager::{MonsterKill, MonsterManager},
+    registry::EntityKind,
+};
+use crate::errors::GameError;
+use crate::metrics::TickMetrics;
+use crate::packet::board_packet::{BoardPacket, SpellStatus};
+use crate::packet::cast_failed_packet::CastFailedPacket;
+use crate::packet::end_game_packet::EndGamePacket;
+use crate::packet::respawn_packet::RespawnPacket;
+use crate::packet::scoreboard_packet::ScoreboardPacket;
+use crate::packet::tick_rate_packet::TickRatePacket;
+
+/// A phase of the game tick. Whatever subset is run, systems always run in
+/// the order of `System::ALL`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum System {
+    Camps,
+    Buffs,
+    Champions,
+    Minions,
+    Monsters,
+    /// Queued flow fields use the budget left by the units
+    Pathfinding,
+    Towers,
+    Projectiles,
+    Effects,
+    Rewards,
+    /// Publishes the components of the units to the registry
+    Registry,
+    Animations,
+    EndGame,
+    Packets,
+}
+
+impl System {
+    pub const ALL: [System; 14] = [
+        System::Camps,
+        System::Buffs,
+        System::Champions,
+        System::Minions,
+        System::Monsters,
+        System::Pathfinding,
+        System::Towers,
+        System::Projectiles,
+        System::Effects,
+        System::Rewards,
+        System::Registry,
+        System::Animations,
+        System::EndGame,
+        System::Packets,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            System::Camps => "camps",
+            System::Buffs => "buffs",
+            System::Champions => "champions",
+            System::Minions => "minions",
+            System::Monsters => "monsters",
+            System::Pathfinding => "pathfinding",
+            System::Towers => "towers",
+            System::Projectiles => "projectiles",
+            System::Effects => "effects",
+            System::Rewards => "rewards",
+            System::Registry => "registry",
+            System::Animations => "animations",
+            System::EndGame => "end_game",
+            System::Packets => "packets",
+        }
+    }
+}
+
+/// What the systems of a tick hand over to the next ones
+#[derive(Default)]
+pub struct TickState {
+    pub new_animations: Vec<Box<dyn AnimationTrait>>,
+    pub animation_commands: Vec<AnimationCommand>,
+    /// Target and effects, applied by `System::Effects`. Hits carry their
+    /// attacker in `Damage::source`
+    pub pending_effects: Vec<(Target, Vec<GameplayEffect>)>,
+    pub monster_kills: Vec<MonsterKill>,
+    pub cast_failures: Vec<(PlayerId, CastFailedPacket)>,
+    /// Result of the game, sent after the boards of its last tick
+    pub end_game: Option<EndGamePacket>,
+    pub updates: HashMap<PlayerId, ClientMessage>,
+}
+
+impl GameManager {
+    /// Runs one tick made of the given systems, in pipeline order. The
+    /// metrics of the tick are kept until the next one
+    pub fn run_systems(&mut self, systems: &[System]) -> HashMap<PlayerId, ClientMessage> {
+        let tick_start = Instant::now();
+        self.tick = self.tick.saturating_add(1);
+        self.pathfinder.start_tick();
+        debug!("Tick {}", self.tick);
+        self.print_game_state();
+
+        let mut state = TickState::default();
+        let mut timings = Vec::with_capacity(systems.len());
+        for system in System::ALL
+            .into_iter()
+            .filter(|system| systems.contains(system))
+        {
+            let start = Instant::now();
+            self.run_system(system, &mut state);
+            timings.push((system, start.elapsed()));
+        }
+
+        self.tick_metrics = TickMetrics {
+            tick: self.tick,
+            duration: tick_start.elapsed(),
+            budget: self.tick_period,
+            drift: self.tick_timing.drift,
+            skipped: self.tick_timing.skipped,
+            rate: self.tick_timing.rate,
+            systems: timings,
+            champions: self.champions.len(),
+            minions: self.minion_manager.minions.len(),
+            monsters: self.monster_manager.active_monsters.len(),
+            towers: self.towers.len(),
+            projectiles: self.projectile_manager.projectiles.len(),
+            animations: self.animations.len(),
+            path_searches: self.pathfinder.searches(),
+            path_nodes: self.pathfinder.nodes_expanded(),
+            // Counted by `send_to_player` since the metrics were last taken
+            bytes_per_player: take(&mut self.tick_metrics.bytes_per_player),
+        };
+        if self.tick_metrics.is_overrun()
+            && let Some((system, duration)) = self.tick_metrics.slowest_system()
+        {
+            warn!(
+                "Tick {} took {:?}, over its {:?} budget, {} took {:?}",
+                self.tick,
+                self.tick_metrics.duration,
+                self.tick_period,
+                system.name(),
+                duration
+            );
+        }
+        state.updates
+    }
+
+    /// Measures of the last tick
+    pub fn tick_metrics(&self) -> &TickMetrics {
+        &self.tick_metrics
+    }
+
+    /// Measures of the last tick, once its updates were sent. Bytes sent
+    /// from now on count toward the next tick
+    pub fn take_tick_metrics(&mut self) -> TickMetrics {
+        take(&mut self.tick_metrics)
+    }
+
+    /// Time spent in each system during the last tick
+    pub fn system_timings(&self) -> &[(System, Duration)] {
+        &self.tick_metrics.systems
+    }
+
+    fn run_system(&mut self, system: System, state: &mut TickState) {
+        match system {
+            System::Camps => self.run_camps(),
+            System::Buffs => self.run_buffs(),
+            System::Champions => self.run_champions(state),
+            System::Minions => self.run_minions(state),
+            System::Monsters => self.run_monsters(state),
+            System::Pathfinding => self.run_pathfinding(),
+            System::Towers => self.run_towers(),
+            System::Projectiles => self.run_projectiles(state),
+            System::Effects => self.run_effects(state),
+            System::Rewards => self.run_rewards(state),
+            System::Registry => self.run_registry(),
+            System::Animations => self.run_animations(state),
+            System::EndGame => self.run_end_game(state),
+            System::Packets => self.run_packets(state),
+        }
+    }
+
+    /// Camps start their timers 5 seconds after the game started
+    fn run_camps(&mut self) {
+        if let Some(start_time) = self.game_start_time
+            && !self.initial_monsters_spawned
+            && start_time.elapsed() >= Duration::from_secs(5)
+        {
+            self.monster_manager
+                .spawn_initial_monsters(&mut self.board, &mut self.registry);
+            self.initial_monsters_spawned = true;
+        }
+    }
+
+    /// Ticks the buffs kept by the registry, expired buffs are removed
+    fn run_buffs(&mut self) {
+        for id in self.registry.buffed() {
+            let unit: Option<&mut dyn HasBuff> = match self.registry.kind(id) {
+                Some(EntityKind::Champion) => self
+                    .champions
+                    .get_mut(&id)
+                    .map(|unit| unit as &mut dyn HasBuff),
+                Some(EntityKind::Minion) => self
+                    .minion_manager
+                    .minions
+                    .get_mut(&id)
+                    .map(|unit| unit as &mut dyn HasBuff),
+                _ => None,
+            };
+            if let Some(unit) = unit {
+                self.registry.tick_buffs(id, unit);
+            }
+        }
+    }
+
+    /// Respawns dead champions, plays the action of each player and the auto attacks
+    fn run_champions(&mut self, state: &mut TickState) {
+        for (player_id, champ) in &mut self.champions {
+            // 0. A dead champion waits off the board, then respawns once
+            // a cell of its spawn zone is free
+            if champ.is_dead() {
+                if champ.can_respawn()
+                    && let Some(spawn) = self
+                        .map_objects
+                        .spawn_zone(champ.team_id)
+                        .and_then(|zone| zone.free_cell(&self.board))
+                {
+                    champ.respawn(&mut self.board, spawn);
+                }
+                continue;
+            }
+            // 1. Iterate through player action
+            champ.recharge_movement();
+            if let Some(action) = self.player_action.get(player_id)
+                && let Err(e) =
+                    champ.take_action(action, &mut self.board, &mut self.projectile_manager)
+            {
+                debug!("Error on action of player {}: {}", player_id, e);
+                if let GameError::CastFailed(slot, cast_error) = e {
+                    state
+                        .cast_failures
+                        .push((*player_id, CastFailedPacket::new(slot, &cast_error)));
+                }
+            }
+            // Walks toward the clicked cell unless a direction was sent this tick
+            champ.follow_move_order(&mut self.board, &mut self.pathfinder);
+
+            // 2. auto_attack
+            if let Some(target) = champ
+                .get_potential_target(&self.board)
+                .and_then(|enemy| enemy.content.as_ref())
+                .and_then(Target::from_content)
+            {
+                match champ.can_attack() {
+                    Some(AttackAction::Melee { damage, animation }) => {
+                        state.new_animations.push(animation);
+                        state
+                            .pending_effects
+                            .push((target, vec![GameplayEffect::Damage(damage)]))
+                    }
+                    Some(AttackAction::Projectile {
+                        damage,
+                        speed,
+                        visual,
+                    }) => self.projectile_manager.create_homing_projectile(
+                        *player_id,
+                        champ.team_id,
+                        target,
+                        (champ.row, champ.col),
+                        speed,
+                        vec![GameplayEffect::Damage(damage)],
+                        visual,
+                    ),
+                    None => {}
+                }
+            }
+        }
+    }
+
+    /// Moves the minions, spawns the waves and queues the minion attacks
+    fn run_minions(&mut self, state: &mut TickState) {
+        self.minion_manager
+            .manage_minions_mouvements(&mut self.board, &mut self.pathfinder);
+        let game_time = self
+            .game_start_time
+            .map(|start| start.elapsed())
+            .unwrap_or_default();
+        self.minion_manager
+            .make_wave(&mut self.board, &mut self.registry, game_time);
+        debug!(
+            "Minions: {} | Minions per wave {}",
+            self.minion_manager.minions.len(),
+            self.minion_manager.minions_this_wave,
+        );
+
+        // Adding minion damages dealt
+        self.minion_manager.manage_minions_attack(
+            &mut self.board,
+            &mut self.projectile_manager,
+            &mut state.new_animations,
+            &mut state.pending_effects,
+        );
+    }
+
+    /// Moves the monsters and queues their attacks and abilities
+    fn run_monsters(&mut self, state: &mut TickState) {
+        let (monster_effects, monster_animations) = self.monster_manager.update(
+            &mut self.board,
+            &mut self.registry,
+            &self.champions,
+            &self.minion_manager.minions,
+            &mut self.pathfinder,
+        );
+        state.pending_effects.extend(monster_effects);
+        state.new_animations.extend(monster_animations);
+    }
+
+    fn run_pathfinding(&mut self) {
+        // Queued flow fields use the budget left by the units
+        self.pathfinder.build_fields(&self.board);
+    }
+
+    /// Towers scan their range and shoot the closest enemy
+    fn run_towers(&mut self) {
+        self.tower_turn();
+    }
+
+    /// Moves the projectiles and queues the effects of those hitting a unit
+    fn run_projectiles(&mut self, state: &mut TickState) {
+        let (projectile_effects, projectile_commands) =
+            self.projectile_manager.update_and_check_collisions(
+                &self.board,
+                &self.champions,
+                &self.minion_manager.minions,
+                &self.towers,
+                &self.monster_manager.active_monsters,
+            );
+        state.pending_effects.extend(projectile_effects);
+        state.animation_commands.extend(projectile_commands);
+    }
+
+    /// Applies the queued effects, and handles the deaths they cause
+    fn run_effects(&mut self, state: &mut TickState) {
+        take(&mut state.pending_effects)
+            .into_iter()
+            .for_each(|(target, effect)| {
+                // Hits carry their unit, buffs alone are credited to nobody
+                let source = damage_source(&effect);
+                match target {
+                    Target::Tower(id) => {
+                        let attacker = self.champion_attacker(source);
+                        if let Some(tower) = self.towers.get_mut(&id) {
+                            let health_before = tower.health();
+                            tower.take_effect(effect);
+                            if let Some(attacker) = attacker {
+                                self.scoreboard.record_damage_dealt(
+                                    attacker,
+                                    health_before.saturating_sub(tower.health()),
+                                );
+                            }
+                            if tower.is_destroyed() {
+                                if let Some(attacker) = attacker {
+                                    self.scoreboard.record_tower_destroyed(attacker);
+                                }
+                                tower.destroy_tower(&mut self.board);
+                                self.pathfinder
+                                    .cells_changed(&self.board, &tower.footprint());
+                                if let Some(tower) = self.towers.remove(&id) {
+                                    self.registry.despawn(id);
+                                    self.handle_tower_destroyed(&tower);
+                                }
+                            }
+                        }
+                    }
+                    Target::Minion(id) => {
+                        let attacker = self.champion_attacker(source);
+                        if let Some(minion) = self.minion_manager.minions.get_mut(&id) {
+                            let (hits, buffs) = split_buffs(effect);
+                            let health_before = minion.stats.health;
+                            minion.take_effect(hits);
+                            if !minion.is_dead() {
+                                for buff in buffs {
+                                    self.registry.add_buff(id, buff, minion);
+                                }
+                            }
+                            if let Some(attacker) = attacker {
+                                self.scoreboard.record_damage_dealt(
+                                    attacker,
+                                    health_before.saturating_sub(minion.stats.health),
+                                );
+                                if minion.is_dead() {
+                                    self.scoreboard.record_minion_kill(attacker);
+                                }
+                            }
+                            self.handle_minion_death(&id);
+                        }
+                    }
+                    Target::Champion(id) => {
+                        let attacker = self.champion_attacker(source);
+                        if let Some(champ) = self.champions.get_mut(&id) {
+                            let (hits, buffs) = split_buffs(effect);
+                            let health_before = champ.stats.health;
+                            champ.take_effect(hits);
+                            let health_after = champ.stats.health;
+                            // A dead champion cannot be buffed, nor one the
+                            // hits killed
+                            if health_after > 0 && !champ.is_dead() {
+                                for buff in buffs {
+                                    self.registry.add_buff(id, buff, champ);
+                                }
+                            }
+                            self.scoreboard.record_champion_hit(
+                                attacker,
+                                id,
+                                health_before.saturating_sub(health_after),
+                            );
+                            if health_before > 0 && health_after == 0 {
+                                let respawn_delay =
+                                    self.config.rules.champion_rules.respawn_delay(champ.level);
+                                champ.die(&mut self.board, respawn_delay);
+                                self.registry.clear_buffs(id, champ);
+                                self.scoreboard.record_champion_death(id);
+                            }
+                        }
+                        if let Some(attacker) = attacker {
+                            self.alert_towers(attacker, id);
+                        }
+                    }
+                    Target::Base(team) => {
+                        let attacker = self.champion_attacker(source);
+                        let base = match team {
+                            Team::Red => &mut self.red_base,
+                            Team::Blue => &mut self.blue_base,
+                        };
+                        let health_before = base.stats.health;
+                        base.take_effect(effect);
+                        if let Some(attacker) = attacker {
+                            self.scoreboard.record_damage_dealt(
+                                attacker,
+                                health_before.saturating_sub(base.stats.health),
+                            );
+                        }
+                    }
+                    Target::Monster(id) => {
+                        let health = |manager: &MonsterManager| {
+                            manager
+                                .active_monsters
+                                .get(&id)
+                                .map_or(0, |monster| monster.stats.health)
+                        };
+                        let health_before = health(&self.monster_manager);
+                        let attacker = self.monster_attacker(source);
+                        let kill = self
+                            .monster_manager
+                            .apply_effects_to_monster(&id, effect, attacker);
+                        if let Some(attacker) = self.champion_attacker(source) {
+                            self.scoreboard.record_damage_dealt(
+                                attacker,
+                                health_before.saturating_sub(health(&self.monster_manager)),
+                            );
+                        }
+                        if let Some(kill) = kill {
+                            state.monster_kills.push(kill);
+                        }
+                    }
+                }
+            });
+    }
+
+    /// Distributes XP and buffs of the monsters and minions killed this tick
+    fn run_rewards(&mut self, state: &mut TickState) {
+        // Distribute XP and buffs from dead monsters
+        for kill in take(&mut state.monster_kills) {
+            self.reward_monster_kill(kill);
+        }
+        // Distribute XP from dead minions
+        for (minion_row, minion_col, minion_team) in self.dead_minion_positions.drain(..) {
+            let mut champions_in_range = Vec::new();
+            for (_, champion) in self.champions.iter_mut() {
+                // Check if champion is alive, in 5x5 range and on the opposing team,
+                // a dead champion keeps the position it died at
+                if !champion.is_dead()
+                    && champion.team_id != minion_team
+                    && (champion.row as i32 - minion_row as i32).abs() <= 2
+                    && (champion.col as i32 - minion_col as i32).abs() <= 2
+                {
+                    champions_in_range.push(champion);
+                }
+            }
+
+            if !champions_in_range.is_empty() {
+                let xp_per_champion = 5 / champions_in_range.len() as u32;
+                for champion in champions_in_range {
+                    champion.add_xp(xp_per_champion);
+                }
+            }
+        }
+    }
+
+    /// Publishes the position, stats and AI of every unit, the systems
+    /// after this one read them from the registry
+    fn run_registry(&mut self) {
+        let units = self
+            .champions
+            .values()
+            .map(|unit| unit as &dyn Unit)
+            .chain(
+                self.minion_manager
+                    .minions
+                    .values()
+                    .map(|unit| unit as &dyn Unit),
+            )
+            .chain(self.towers.values().map(|unit| unit as &dyn Unit))
+            .chain(
+                self.monster_manager
+                    .active_monsters
+                    .values()
+                    .map(|unit| unit as &dyn Unit),
+            );
+        for unit in units {
+            self.registry.update(unit);
+        }
+    }
+
+    /// Draws the next frame of every animation
+    fn run_animations(&mut self, state: &mut TickState) {
+        let mut kept_animations: Vec<Box<dyn AnimationTrait>> = Vec::new();
+
+        // 1. clear past frame animation
+        for anim in &self.animations {
+            if let Some((row, col)) = anim.get_last_drawn_pos() {
+                state
+                    .animation_commands
+                    .push(AnimationCommand::Clear { row, col })
+            }
+        }
+        // 2. Process next frame animations
+        for mut anim in take(&mut self.animations) {
+            let owner_pos = self.registry.position(anim.get_owner_id());
+
+            if let Some((owner_row, owner_col)) = owner_pos {
+                let command = anim.next_frame(owner_row, owner_col);
+                match command {
+                    AnimationCommand::Done => {}
+                    AnimationCommand::Draw { .. } => {
+                        state.animation_commands.push(command);
+                        kept_animations.push(anim);
+                    }
+                    AnimationCommand::Clear { .. } => {
+                        // This command should be handle before
+                    }
+                }
+            } else {
+                // Owner is gone, animation should finish and clear in its last frame
+            }
+        }
+        kept_animations.extend(take(&mut state.new_animations));
+        self.animations = kept_animations;
+
+        // 3. Execute animation command
+        for command in take(&mut state.animation_commands) {
+            match command {
+                AnimationCommand::Draw {
+                    row,
+                    col,
+                    animation_type,
+                } => {
+                    // Add bounds check
+                    if row < self.board.rows as u16 && col < self.board.cols as u16 {
+                        self.board
+                            .place_animation(animation_type, row as usize, col as usize);
+                    } else {
+                        warn!("Animation draw position ({}, {}) out of bounds!", row, col);
+                    }
+                }
+                AnimationCommand::Clear { row, col } => {
+                    if row < self.board.rows as u16 && col < self.board.cols as u16 {
+                        self.board.clean_animation(row as usize, col as usize);
+                    } else {
+                        warn!("Animation clear position ({}, {}) out of bounds!", row, col);
+                    }
+                }
+                AnimationCommand::Done => {
+                    // This command should be handled in the loop above, not executed on the board
+                }
+            }
+        }
+    }
+
+    /// Queues the result and ends the game once a base is destroyed. The tick
+    /// loop sends the last updates before stopping the server
+    fn run_end_game(&mut self, state: &mut TickState) {
+        let destroyed = if self.red_base.stats.health == 0 {
+            info!("Sending EndGamePacket: Red base destroyed, Blue team wins!");
+            Team::Red
+        } else if self.blue_base.stats.health == 0 {
+            info!("Sending EndGamePacket: Blue base destroyed, Red team wins!");
+            Team::Blue
+        } else {
+            return;
+        };
+        let packet = EndGamePacket::new(destroyed, self.score_entries());
+        debug!("EndGamePacket: {:?}", packet);
+        state.end_game = Some(packet);
+        self.game_over = true;
+    }
+
+    /// Builds the board update of every player, followed by its extra packets
+    fn run_packets(&mut self, state: &mut TickState) {
+        for (player_id, champion) in &self.champions {
+            // 1. Get player-specific board view
+            let board_rle_vec =
+                self.board
+                    .run_length_encode(champion.row, champion.col, &self.minion_manager);
+            // 2. Create the board packet
+            let health = champion.get_health();
+            let xp_needed = champion.xp_for_next_level().unwrap_or(0); // Get XP needed, 0 if max level
+            let (view_row, view_col) = self.board.view_origin(champion.row, champion.col, 21, 51);
+            let spells = (0..3)
+                .map(|slot| match champion.spells.get(&slot) {
+                    Some(spell) => SpellStatus {
+                        rank: spell.rank(),
+                        cooldown_remaining_ms: spell.cooldown_remaining().as_millis() as u32,
+                        ready: spell.is_ready(champion.stats.mana),
+                    },
+                    None => SpellStatus {
+                        rank: 0,
+                        cooldown_remaining_ms: 0,
+                        ready: false,
+                    },
+                })
+                .collect();
+            let board_packet = BoardPacket::new(
+                health.0,
+                health.1,
+                champion.stats.mana,
+                champion.stats.max_mana,
+                champion.level,
+                champion.xp,
+                xp_needed,
+                champion.skill_points,
+                view_row,
+                view_col,
+                spells,
+                board_rle_vec,
+            );
+            let serialized_packet = board_packet.serialize();
+            // 3. Store the serialized packet to be sent later
+            state.updates.insert(*player_id, serialized_packet);
+        }
+        // Dead champions get the time left before they respawn
+        for (player_id, champion) in &self.champions {
+            if let Some(remaining) = champion.respawn_remaining()
+                && let Some(message) = state.updates.get_mut(player_id)
+            {
+                message.extend_from_slice(&RespawnPacket::new(remaining).serialize());
+            }
+        }
+        // The scoreboard is sent every second, or right away when requested
+        let scoreboard_due = self.tick.is_multiple_of(SCOREBOARD_INTERVAL_TICKS);
+        if scoreboard_due || !self.scoreboard_requests.is_empty() {
+            let packet = ScoreboardPacket::new(self.score_entries()).serialize();
+            for (player_id, message) in state.updates.iter_mut() {
+                if scoreboard_due || self.scoreboard_requests.contains(player_id) {
+                    message.extend_from_slice(&packet);
+                }
+            }
+            self.scoreboard_requests.clear();
+        }
+        // The measured tick rate lets the clients interpolate between updates
+        if self.tick.is_multiple_of(TICK_RATE_INTERVAL_TICKS) {
+            let packet = TickRatePacket::new(self.tick_period, &self.tick_timing).serialize();
+            for message in state.updates.values_mut() {
+                message.extend_from_slice(&packet);
+            }
+        }
+        // Cast failures are sent right after the board update of the same tick
+        for (player_id, packet) in take(&mut state.cast_failures) {
+            if let Some(message) = state.updates.get_mut(&player_id) {
+                message.extend_from_slice(&packet.serialize());
+            }
+        }
+        if let Some(packet) = take(&mut state.end_game) {
+            let packet = packet.serialize();
+            for message in state.updates.values_mut() {
+                message.extend_from_slice(&packet);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GameConfig;
+    use crate::game::entities::damage::{Damage, DamageSource};
+    use crate::game::map::MapRegistry;
+    use crate::tick_clock::TickTiming;
+
+    fn create_manager() -> GameManager {
+        let config = GameConfig::load("stats.toml", "spells.toml", "rules.toml").unwrap();
+        let map = MapRegistry::load("maps").unwrap().load_map(None).unwrap();
+        GameManager::new(config, map).unwrap()
+    }
+
+    fn timed_systems(manager: &GameManager) -> Vec<System> {
+        manager
+            .system_timings()
+            .iter()
+            .map(|(system, _)| *system)
+            .collect()
+    }
+
+    #[test]
+    fn test_subset_runs_in_pipeline_order() {
+        let mut manager = create_manager();
+        manager.run_systems(&[System::Packets, System::Buffs]);
+        assert_eq!(
+            timed_systems(&manager),
+            vec![System::Buffs, System::Packets]
+        );
+    }
+
+    #[test]
+    fn test_game_tick_runs_every_system() {
+        let mut manager = create_manager();
+        let player_id = manager.add_player(0, 0, 1).unwrap();
+
+        let updates = manager.game_tick();
+        assert_eq!(timed_systems(&manager), System::ALL.to_vec());
+        assert!(updates.contains_key(&player_id));
+    }
+
+    #[test]
+    fn test_effects_credit_the_champion_behind_the_damage() {
+        let mut manager = create_manager();
+        let player_id = manager.add_player(0, 0, 1).unwrap();
+        manager
+            .monster_manager
+            .spawn_initial_monsters(&mut manager.board, &mut manager.registry);
+        let mut monsters = manager.monster_manager.active_monsters.keys().copied();
+        let (killed, hit) = (monsters.next().unwrap(), monsters.next().unwrap());
+
+        let mut state = TickState::default();
+        state.pending_effects.push((
+            Target::Monster(killed),
+            vec![GameplayEffect::Damage(Damage::magic(
+                u16::MAX,
+                DamageSource::Champion(player_id),
+            ))],
+        ));
+        // A minion gets no credit
+        state.pending_effects.push((
+            Target::Monster(hit),
+            vec![GameplayEffect::Damage(Damage::magic(
+                u16::MAX,
+                DamageSource::Minion(1),
+            ))],
+        ));
+        manager.run_system(System::Effects, &mut state);
+
+        assert_eq!(state.monster_kills.len(), 1);
+        assert_eq!(state.monster_kills[0].killer, player_id);
+        assert!(manager.scoreboard.score(player_id).damage_dealt > 0);
+    }
+
+    #[test]
+    fn test_effects_system_applies_queued_damage() {
+        let mut manager = create_manager();
+        let player_id = manager.add_player(0, 0, 1).unwrap();
+        let (health, _) = manager.champions[&player_id].get_health();
+
+        let mut state = TickState::default();
+        state.pending_effects.push((
+            Target::Champion(player_id),
+            vec![GameplayEffect::Damage(Damage::magic(
+                10,
+                DamageSource::Minion(1),
+            ))],
+        ));
+        manager.run_system(System::Effects, &mut state);
+
+        assert!(state.pending_effects.is_empty());
+        assert!(manager.champions[&player_id].get_health().0 < health);
+    }
+
+    #[test]
+    fn test_buffs_are_kept_and_ticked_by_the_registry() {
+        use crate::game::buffs::stun_buff::StunBuff;
+
+        let mut manager = create_manager();
+        let player_id = manager.add_player(0, 0, 1).unwrap();
+        let mut state = TickState::default();
+        state.pending_effects.push((
+            Target::Champion(player_id),
+            vec![GameplayEffect::Buff(Box::new(StunBuff::new(0)))],
+        ));
+        manager.run_system(System::Effects, &mut state);
+        assert_eq!(manager.registry.buffed(), vec![player_id]);
+
+        // The stun lasts no time, it expires on the next tick
+        std::thread::sleep(Duration::from_millis(1));
+        manager.run_system(System::Buffs, &mut state);
+        assert!(manager.registry.buffed().is_empty());
+
+        // A champion the hits kill is not buffed
+        state.pending_effects.push((
+            Target::Champion(player_id),
+            vec![
+                GameplayEffect::Damage(Damage::physical(u16::MAX, DamageSource::Minion(1))),
+                GameplayEffect::Buff(Box::new(StunBuff::new(5))),
+            ],
+        ));
+        manager.run_system(System::Effects, &mut state);
+        assert!(manager.champions[&player_id].is_dead());
+        assert!(manager.registry.buffed().is_empty());
+    }
+
+    #[test]
+    fn test_registry_system_publishes_the_components() {
+        use crate::game::registry::Ai;
+
+        let mut manager = create_manager();
+        let player_id = manager.add_player(0, 0, 1).unwrap();
+        assert_eq!(manager.registry.position(player_id), None);
+
+        manager.run_system(System::Registry, &mut TickState::default());
+        let champion = &manager.champions[&player_id];
+        assert_eq!(
+            manager.registry.position(player_id),
+            Some((champion.row, champion.col))
+        );
+        assert_eq!(
+            manager.registry.stats(player_id).map(|stats| stats.health),
+            Some(champion.stats.health)
+        );
+        assert_eq!(
+            manager.registry.ai(player_id),
+            Some(&Ai::Player { dead: false })
+        );
+        let tower_id = *manager.towers.keys().next().unwrap();
+        assert_eq!(
+            manager.registry.ai(tower_id),
+            Some(&Ai::Turret { target: None })
+        );
+    }
+
+    #[test]
+    fn test_dead_champion_gets_no_xp_from_minions_dying_where_it_died() {
+        let mut manager = create_manager();
+        let player_id = manager.add_player(0, 0, 1).unwrap();
+        let champion = manager.champions.get_mut(&player_id).unwrap();
+        let (row, col) = (champion.row, champion.col);
+        champion.die(&mut manager.board, Duration::from_secs(10));
+        for _ in 0..20 {
+            manager.dead_minion_positions.push((row, col, Team::Red));
+        }
+
+        manager.run_system(System::Rewards, &mut TickState::default());
+
+        let champion = &manager.champions[&player_id];
+        assert_eq!(champion.xp, 0);
+        assert_eq!(champion.level, 1);
+        assert!(champion.is_dead());
+        assert!(
+            manager
+                .board
+                .get_cell(row as usize, col as usize)
+                .unwrap()
+                .content
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_packets_system_alone_leaves_the_game_untouched() {
+        let mut manager = create_manager();
+        let player_id = manager.add_player(0, 0, 1).unwrap();
+        manager.minion_manager.wave_creation_time = Instant::now();
+
+        let updates = manager.run_systems(&[System::Packets]);
+        assert!(updates.contains_key(&player_id));
+        assert!(manager.minion_manager.minions.is_empty());
+    }
+
+    #[test]
+    fn test_tick_period_comes_from_the_timing_rules() {
+        let mut config = GameConfig::load("stats.toml", "spells.toml", "rules.toml").unwrap();
+        config.rules.timing_rules.game_tick_ms = 50;
+        let map = MapRegistry::load("maps").unwrap().load_map(None).unwrap();
+        let mut manager = GameManager::new(config, map).unwrap();
+
+        assert_eq!(manager.tick_period(), Duration::from_millis(50));
+        manager.run_systems(&[]);
+        assert_eq!(manager.tick_metrics().budget, Duration::from_millis(50));
+        assert_eq!(manager.tick_metrics().rate, 20.0);
+    }
+
+    #[test]
+    fn test_end_game_is_sent_after_the_board() {
+        let mut manager = create_manager();
+        let player_id = manager.add_player(0, 0, 1).unwrap();
+        let updates = manager.run_systems(&[System::EndGame, System::Packets]);
+        assert!(!manager.is_game_over());
+
+        manager.red_base.stats.health = 0;
+        let updates_after = manager.run_systems(&[System::EndGame, System::Packets]);
+        let packet = EndGamePacket::new(Team::Red, manager.score_entries()).serialize();
+
+        assert!(manager.is_game_over());
+        assert!(!updates[&player_id].ends_with(&packet));
+        assert!(updates_after[&player_id].ends_with(&packet));
+    }
+
+    #[test]
+    fn test_packets_system_reports_the_tick_rate_every_second() {
+        let mut manager = create_manager();
+        let player_id = manager.add_player(0, 0, 1).unwrap();
+        let timing = TickTiming {
+            drift: Duration::from_millis(2),
+            rate: 24.5,
+            ..TickTiming::default()
+        };
+        manager.set_tick_timing(timing);
+        let packet = TickRatePacket::new(manager.tick_period(), &timing).serialize();
+
+        manager.tick = TICK_RATE_INTERVAL_TICKS - 1;
+        let updates = manager.run_systems(&[System::Packets]);
+        assert!(updates[&player_id].ends_with(&packet));
+        assert_eq!(manager.tick_metrics().rate, 24.5);
+
+        let updates = manager.run_systems(&[System::Packets]);
+        assert!(!updates[&player_id].ends_with(&packet));
+    }
+}
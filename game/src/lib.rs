@@ -0,0 +1,8 @@
+#![allow(clippy::too_many_arguments, clippy::type_complexity)]
+
+pub mod config;
+pub mod errors;
+pub mod game;
+pub mod metrics;
+pub mod packet;
+pub mod tick_clock;
@@ -1,21 +1,25 @@
-use crate::game::{ClientMessage, GameManager, PlayerId};
 use clap::Parser;
-use packet::action_packet::ActionPacket;
-use packet::start_packet::StartPacket;
+use game::config;
+use game::errors::GameError;
+use game::game::input::{INPUT_CHANNEL_SIZE, OUTBOUND_CHANNEL_SIZE, PlayerInput};
+use game::game::map::MapRegistry;
+use game::game::{ClientMessage, GameManager, PlayerId};
+use game::metrics::{self, Metrics};
+use game::packet::action_packet::ActionPacket;
+use game::packet::move_to_packet::MoveToPacket;
+use game::packet::spell_selection_packet::SpellSelectionPacket;
+use game::tick_clock::{MissedTicks, TickClock};
+use log::{debug, info, trace, warn};
 use std::collections::HashMap;
+use std::io::ErrorKind;
 use std::net::SocketAddr;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
 use tokio::io::{AsyncReadExt, AsyncWriteExt, BufReader, split};
 use tokio::net::{TcpListener, TcpStream};
-use tokio::spawn;
-use tokio::sync::Mutex;
-use tokio::sync::mpsc;
-use tokio::time::{Duration, sleep};
-
-mod config;
-mod errors;
-mod game;
-mod packet;
+use tokio::sync::{mpsc, oneshot};
+use tokio::time::{Duration, sleep, sleep_until};
+use tokio::{select, spawn};
 
 // Cli Parser
 #[derive(Parser, Debug)]
@@ -26,85 +30,97 @@ struct CliArgs {
 
     #[arg(long = "map", value_name = "MAP_ID", value_parser = clap::value_parser!(u8))]
     map_id: Option<u8>,
+
+    /// Serves the metrics of the game on 127.0.0.1, at `/metrics`
+    #[arg(long = "metrics-port", value_name = "PORT", value_parser = clap::value_parser!(u16))]
+    metrics_port: Option<u16>,
+
+    /// What to do with the ticks missed while the server runs late
+    #[arg(long = "missed-ticks", value_enum, default_value_t = MissedTicks::CatchUp)]
+    missed_ticks: MissedTicks,
 }
 
-async fn handle_client(stream: TcpStream, addr: SocketAddr, game_manager: Arc<Mutex<GameManager>>) {
-    println!("Handler task started for connection from: {:?}", addr);
+async fn handle_client(stream: TcpStream, addr: SocketAddr, inputs: mpsc::Sender<PlayerInput>) {
+    debug!("Handler task started for connection from: {:?}", addr);
 
     let (reader, mut writer) = split(stream);
     let mut buf_reader = BufReader::new(reader);
 
     // --- Initial Packet: Spell Selection ---
-    let mut initial_packet_header = [0; 2]; // Read version and code
-    if buf_reader
-        .read_exact(&mut initial_packet_header)
-        .await
-        .is_err()
-    {
-        eprintln!("Error reading initial packet header from {:?}", addr);
+    let mut initial_packet = [0; 5]; // Read version, code, spell1, spell2 and champion
+    if buf_reader.read_exact(&mut initial_packet).await.is_err() {
+        warn!("Error reading initial packet from {:?}", addr);
         if let Err(e) = writer.shutdown().await {
-            eprintln!("Error shutting down stream for {:?}: {}", addr, e);
+            warn!("Error shutting down stream for {:?}: {}", addr, e);
         }
         return;
     }
 
-    let version = initial_packet_header[0];
-    let code = initial_packet_header[1];
-
-    let (spell1, spell2) = if version == 1 && code == 13 {
+    let (champion, spell1, spell2) = match SpellSelectionPacket::deserialize(&initial_packet) {
         // Code for SpellSelectionPacket
-        let mut spell_payload = [0; 2]; // Read spell1 and spell2
-        if buf_reader.read_exact(&mut spell_payload).await.is_err() {
-            eprintln!("Error reading spell payload from {:?}", addr);
+        Ok(packet) if packet.version == 1 && packet.code == 13 => {
+            (packet.champion, packet.spell1, packet.spell2)
+        }
+        Ok(packet) => {
+            warn!(
+                "Invalid initial packet from {:?}: Version={}, Code={}",
+                addr, packet.version, packet.code
+            );
             if let Err(e) = writer.shutdown().await {
-                eprintln!("Error shutting down stream for {:?}: {}", addr, e);
+                warn!("Error shutting down stream for {:?}: {}", addr, e);
             }
             return;
         }
-        (spell_payload[0], spell_payload[1])
-    } else {
-        eprintln!(
-            "Invalid initial packet from {:?}: Version={}, Code={}",
-            addr, version, code
-        );
-        if let Err(e) = writer.shutdown().await {
-            eprintln!("Error shutting down stream for {:?}: {}", addr, e);
+        Err(e) => {
+            warn!("Invalid initial packet from {:?}: {}", addr, e);
+            if let Err(e) = writer.shutdown().await {
+                warn!("Error shutting down stream for {:?}: {}", addr, e);
+            }
+            return;
         }
-        return;
     };
 
-    let player_id: PlayerId;
-    let (tx, mut rx) = mpsc::channel::<ClientMessage>(32);
-
-    {
-        let mut manager = game_manager.lock().await;
-        if let Some(id) = manager.add_player(spell1, spell2) {
-            player_id = id;
-            manager.client_channel.insert(id, tx);
-            println!(
-                "Player {} ({:?}) joined with spells {} and {}",
-                id, addr, spell1, spell2
-            );
-        } else {
-            println!("Rejecting connection from {:?}: Server is full.", addr);
-            let rejection_msg = "Server is full. Try again later.\n";
+    // The tick loop owns the game, it answers with the id of the player
+    let (tx, mut rx) = mpsc::channel::<ClientMessage>(OUTBOUND_CHANNEL_SIZE);
+    let (flushed_tx, flushed) = oneshot::channel();
+    let (reply, reply_rx) = oneshot::channel();
+    let join = PlayerInput::Join {
+        champion,
+        spell1,
+        spell2,
+        outbound: tx,
+        flushed,
+        reply,
+    };
+    let player_id = match inputs.send(join).await {
+        Ok(()) => reply_rx.await.unwrap_or(Err(GameError::GameFull)),
+        Err(_) => Err(GameError::GameFull),
+    };
+    let player_id = match player_id {
+        Ok(player_id) => player_id,
+        Err(e) => {
+            info!("Rejecting connection from {:?}: {}", addr, e);
+            let rejection_msg = match e {
+                GameError::GameFull => "Server is full. Try again later.\n".to_string(),
+                e => format!("{}.\n", e),
+            };
             if let Err(e) = writer.write_all(rejection_msg.as_bytes()).await {
-                eprintln!("Error sending rejection message to {:?}: {}", addr, e);
+                warn!("Error sending rejection message to {:?}: {}", addr, e);
             }
             if let Err(e) = writer.shutdown().await {
-                eprintln!("Error shutting down rejected stream for {:?}: {}", addr, e);
+                warn!("Error shutting down rejected stream for {:?}: {}", addr, e);
             }
             return;
         }
-    }
+    };
 
     // -- Split Stream and Spawn Writer Task --
     // The reader and writer are already split from the initial read
     // Spawn a separate task that owns the 'writer' and listens on 'rx'
-    let _ = spawn(async move {
+    spawn(async move {
         while let Some(message) = rx.recv().await {
             if writer.write_all(&message).await.is_err() {
-                eprintln!(
+                warn!(
                     "Error writting message to client {}, connection likely closed.",
                     player_id
                 );
@@ -112,143 +128,232 @@ async fn handle_client(stream: TcpStream, addr: SocketAddr, game_manager: Arc<Mu
                 break;
             }
         }
-        println!("Writer task for player {} ending.", player_id);
+        debug!("Writer task for player {} ending.", player_id);
         if let Err(e) = writer.shutdown().await {
-            eprintln!("Error shutting down writer for player {}: {}", player_id, e);
+            warn!("Error shutting down writer for player {}: {}", player_id, e);
         }
+        let _ = flushed_tx.send(());
     });
 
-    // -- Verify if we can start game --
-    // Scope to release the lock
-    {
-        let manager = game_manager.lock().await;
-        if manager.game_started {
-            println!("Sending StartPacket to all client");
-            for player_id in manager.client_channel.keys() {
-                let message = StartPacket::new(0).serialize();
-                manager.send_to_player(*player_id, message).await;
-            }
-        }
-    }
-
     // -- Read Client Action loop --
-    println!("Listening for Player {} ({:?}) actions...", player_id, addr);
+    debug!("Listening for Player {} ({:?}) actions...", player_id, addr);
     loop {
-        let mut packet_buffer = [0; 3];
-        match buf_reader.read_exact(&mut packet_buffer).await {
-            Ok(3) => match ActionPacket::deserialize(&packet_buffer) {
-                Ok(packet) => {
-                    if packet.version == 1 && packet.code == 8 {
-                        let mut manager = game_manager.lock().await;
-                        manager.store_player_action(player_id, packet.action);
-                        println!("Received action from: {} ({:?})", player_id, addr);
-                        drop(manager);
-                    } else {
-                        eprintln!(
-                            "Player {} send packet with invalid version/code: V={}, C={}",
-                            player_id, packet.version, packet.code
-                        );
-                    }
-                }
-                Err(e) => {
-                    eprintln!("Player {} sent invalid packet format: {}", player_id, e);
-                }
-            },
-            Ok(0) => {
+        // Version and code, the code gives the size of the packet
+        let mut header = [0; 2];
+        match buf_reader.read_exact(&mut header).await {
+            Ok(_) => {}
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => {
                 // Connection closed by client
-                println!(
-                    "Player {} ({:?}) disconnected (read 0 bytes)",
-                    player_id, addr
-                );
-                break;
-            }
-            Ok(_n) => {
-                println!(
-                    "Incomplete read for player {} ({:?}), likely disconnected",
-                    player_id, addr
-                );
+                info!("Player {} ({:?}) disconnected", player_id, addr);
                 break;
             }
             Err(e) => {
-                println!(
+                warn!(
                     "Error reading action for player {} ({:?}): {}. Disconnecting.",
                     player_id, addr, e
                 );
                 break;
             }
         }
+        let packet_size = match header {
+            [1, 8] => 3,
+            [1, 15] => 7,
+            [version, code] => {
+                warn!(
+                    "Player {} send packet with invalid version/code: V={}, C={}. Disconnecting.",
+                    player_id, version, code
+                );
+                break;
+            }
+        };
+        let mut packet_buffer = [0; 7];
+        packet_buffer[..2].copy_from_slice(&header);
+        if let Err(e) = buf_reader
+            .read_exact(&mut packet_buffer[2..packet_size])
+            .await
+        {
+            info!(
+                "Incomplete read for player {} ({:?}): {}, likely disconnected",
+                player_id, addr, e
+            );
+            break;
+        }
+        let packet = &packet_buffer[..packet_size];
+
+        let input = match header[1] {
+            8 => match ActionPacket::deserialize(packet) {
+                Ok(packet) => PlayerInput::Action {
+                    player_id,
+                    action: packet.action,
+                },
+                Err(e) => {
+                    warn!("Player {} sent invalid packet format: {}", player_id, e);
+                    continue;
+                }
+            },
+            _ => match MoveToPacket::deserialize(packet) {
+                Ok(packet) => PlayerInput::MoveTo {
+                    player_id,
+                    destination: (packet.row, packet.col),
+                    attack_move: packet.attack_move,
+                },
+                Err(e) => {
+                    warn!("Player {} sent invalid packet format: {}", player_id, e);
+                    continue;
+                }
+            },
+        };
+        if inputs.send(input).await.is_err() {
+            debug!("Game loop ended, disconnecting player {}", player_id);
+            break;
+        }
+        trace!("Received action from: {} ({:?})", player_id, addr);
     }
-    println!("Reader loop for player {} ({:?}) ended.", player_id, addr);
+    debug!("Reader loop for player {} ({:?}) ended.", player_id, addr);
 
     // -- CLeanup --
-    {
-        let mut manager = game_manager.lock().await;
-        manager.remove_player(&player_id);
-    }
-    println!(
+    let _ = inputs.send(PlayerInput::Leave { player_id }).await;
+    debug!(
         "Handler task for player {} ({:?}) finished cleanup.",
         player_id, addr
     );
 }
 
+/// Owns the game: applies the inputs of the connections between two ticks,
+/// runs the ticks on the clock and queues the updates of the players
+async fn run_game(
+    mut manager: GameManager,
+    mut inputs: mpsc::Receiver<PlayerInput>,
+    missed_ticks: MissedTicks,
+    metrics: Arc<Mutex<Metrics>>,
+) {
+    loop {
+        info!("Waiting for all players to connect...");
+        while !manager.game_started {
+            match inputs.recv().await {
+                Some(input) => manager.handle_input(input),
+                None => return,
+            }
+        }
+
+        // Ticks are due on a fixed grid, the time spent in a tick does not
+        // delay the next ones
+        let mut clock = TickClock::new(manager.tick_period(), missed_ticks, Instant::now());
+        while manager.game_started {
+            sleep_until(clock.deadline().into()).await;
+            let timing = clock.tick(Instant::now());
+            if timing.skipped > 0 {
+                warn!(
+                    "Tick {} started {:?} late, skipping {} ticks",
+                    timing.slot, timing.drift, timing.skipped
+                );
+            }
+
+            // Only the inputs already queued, busy connections do not hold
+            // the tick back
+            for _ in 0..inputs.len() {
+                match inputs.try_recv() {
+                    Ok(input) => manager.handle_input(input),
+                    Err(_) => break,
+                }
+            }
+            if !manager.game_started {
+                break;
+            }
+
+            manager.set_tick_timing(timing);
+            let updates = manager.game_tick();
+            manager.clear_action();
+            if manager.is_game_over() {
+                finish_game(&mut manager, updates).await;
+                return;
+            }
+            for (player_id, message) in updates {
+                trace!("Message length to be sent: {:?}", message.len());
+                manager.send_to_player(player_id, message);
+            }
+            let tick_metrics = manager.take_tick_metrics();
+            if let Ok(mut metrics) = metrics.lock() {
+                metrics.record(tick_metrics);
+            }
+        }
+    }
+}
+
+/// Sends the last updates, which hold the result of the game, then waits
+/// for the writer tasks to send everything queued to the players
+async fn finish_game(manager: &mut GameManager, mut updates: HashMap<PlayerId, ClientMessage>) {
+    info!("Game over, sending the last updates");
+    let mut writers = Vec::new();
+    for (player_id, outbound, flushed) in manager.close_connections() {
+        if let Some(message) = updates.remove(&player_id)
+            && outbound.send(message).await.is_err()
+        {
+            warn!("Player {} left before the end of the game", player_id);
+        }
+        // Dropping the channel lets the writer stop once it is empty
+        drop(outbound);
+        writers.push(flushed);
+    }
+    for flushed in writers {
+        let _ = flushed.await;
+    }
+}
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
     let args = CliArgs::parse();
     let address = format!("0.0.0.0:{}", args.port);
     let listener = TcpListener::bind(&address).await?;
-    println!("Server listening  on {}", address);
+    info!("Server listening  on {}", address);
 
-    let config = config::GameConfig::load("game/stats.toml", "game/spells.toml")
+    let config = config::GameConfig::load("game/stats.toml", "game/spells.toml", "game/rules.toml")
         .expect("Failed to load game configuration");
-    let game_manager = GameManager::new(config);
-    let arc_gm = Arc::new(Mutex::new(game_manager));
-    println!("GameManager created and wrapped.");
+    let map_registry = MapRegistry::load("game/maps")?;
+    for summary in map_registry.maps() {
+        info!(
+            "Map {}: {} ({}x{}, team sizes {:?})",
+            summary.metadata.id,
+            summary.metadata.name,
+            summary.rows,
+            summary.cols,
+            summary.metadata.team_sizes
+        );
+    }
+    let map = map_registry.load_map(args.map_id)?;
+    let game_manager = GameManager::new(config, map)?;
+    let (input_tx, input_rx) = mpsc::channel::<PlayerInput>(INPUT_CHANNEL_SIZE);
+    debug!("GameManager created, inputs go through a channel.");
+
+    let metrics = Arc::new(Mutex::new(Metrics::default()));
+    if let Some(port) = args.metrics_port {
+        let metrics_listener = TcpListener::bind(("127.0.0.1", port)).await?;
+        info!("Metrics served on 127.0.0.1:{}/metrics", port);
+        spawn(metrics::serve(metrics_listener, Arc::clone(&metrics)));
+    }
 
     // -- Game Tick Task --
-    let tick_manager = Arc::clone(&arc_gm);
-    spawn(async move {
-        loop {
-            let game_started: bool;
-            {
-                let manager = tick_manager.lock().await;
-                game_started = manager.game_started;
-            }
-            if game_started {
-                sleep(Duration::from_millis(40)).await;
-
-                let updates: HashMap<PlayerId, ClientMessage>;
-                {
-                    let mut manager = tick_manager.lock().await;
-                    updates = manager.game_tick();
-                    manager.clear_action();
-                }
-                let manager = tick_manager.lock().await;
-                for (player_id, message) in updates {
-                    println!("Message length to be sent: {:?}", message.len());
-                    manager.send_to_player(player_id, message).await;
-                }
-                drop(manager);
-            } else {
-                sleep(Duration::from_secs(5)).await;
-                println!("Waiting for all players to connect...");
-            }
-        }
-    });
+    let mut game = spawn(run_game(game_manager, input_rx, args.missed_ticks, metrics));
 
     // -- Accept Connections Loop --
+    // The server stops once the game is over and its players got the result
     loop {
-        match listener.accept().await {
-            Ok((stream, addr)) => {
-                println!("Accepted connection form {:?}", addr);
-                let game_manager_for_task = Arc::clone(&arc_gm);
-                spawn(async move {
-                    handle_client(stream, addr, game_manager_for_task).await;
-                });
-            }
-            Err(e) => {
-                eprintln!("Error accepting connection: {}", e);
-                let _ = sleep(Duration::from_secs(1));
+        select! {
+            _ = &mut game => {
+                info!("Game over, shutting down the server");
+                return Ok(());
             }
+            accepted = listener.accept() => match accepted {
+                Ok((stream, addr)) => {
+                    info!("Accepted connection form {:?}", addr);
+                    spawn(handle_client(stream, addr, input_tx.clone()));
+                }
+                Err(e) => {
+                    warn!("Error accepting connection: {}", e);
+                    sleep(Duration::from_secs(1)).await;
+                }
+            },
         }
     }
 }
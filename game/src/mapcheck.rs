@@ -0,0 +1,71 @@
+use clap::Parser;
+use game::game::map::MapData;
+use game::game::map_check::check_map;
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+// Cli Parser
+#[derive(Parser, Debug)]
+#[command(author, version, about = "Checks map files for the game server", long_about = None)]
+struct CliArgs {
+    /// Map files to check, every map of the maps directory when empty
+    files: Vec<PathBuf>,
+
+    #[arg(long = "maps-dir", value_name = "DIR", default_value = "game/maps")]
+    maps_dir: PathBuf,
+}
+
+fn main() -> ExitCode {
+    let args = CliArgs::parse();
+    let files = if args.files.is_empty() {
+        match fs::read_dir(&args.maps_dir) {
+            Ok(entries) => {
+                let mut files: Vec<PathBuf> = entries
+                    .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                    .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+                    .collect();
+                files.sort();
+                files
+            }
+            Err(e) => {
+                eprintln!("Cannot read {}: {}", args.maps_dir.display(), e);
+                return ExitCode::FAILURE;
+            }
+        }
+    } else {
+        args.files
+    };
+
+    let mut failed = false;
+    for file in files.iter() {
+        // The map is parsed without MapData::from_json, which stops at the first error
+        let map: MapData = match fs::read_to_string(file)
+            .map_err(|e| e.to_string())
+            .and_then(|contents| serde_json::from_str(&contents).map_err(|e| e.to_string()))
+        {
+            Ok(map) => map,
+            Err(e) => {
+                println!("{}: cannot be loaded: {}", file.display(), e);
+                failed = true;
+                continue;
+            }
+        };
+        let issues = check_map(&map);
+        if issues.is_empty() {
+            println!("{}: ok", file.display());
+        } else {
+            failed = true;
+            println!("{}: {} issue(s)", file.display(), issues.len());
+            for issue in issues.iter() {
+                println!("  - {}", issue);
+            }
+        }
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
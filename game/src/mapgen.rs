@@ -0,0 +1,106 @@
+use clap::Parser;
+use game::config::GameConfig;
+use game::game::map::MapMetadata;
+use game::game::map_generator::{GeneratorSettings, generate_map};
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+// Cli Parser
+#[derive(Parser, Debug)]
+#[command(author, version, about = "Generates map files for the game server", long_about = None)]
+struct CliArgs {
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+
+    /// Rows and columns of the map
+    #[arg(long, default_value_t = 200)]
+    size: u16,
+
+    /// 1 is a single mid lane, 2 are top and bottom, 3 are all lanes
+    #[arg(long, default_value_t = 3)]
+    lanes: u8,
+
+    /// Share of the jungle covered by walls, from 0 to 1
+    #[arg(long = "jungle-density", default_value_t = 0.4)]
+    jungle_density: f32,
+
+    /// Chance for a walkable cell to be a bush, from 0 to 1
+    #[arg(long = "bush-frequency", default_value_t = 0.05)]
+    bush_frequency: f32,
+
+    #[arg(long)]
+    id: u8,
+
+    #[arg(long)]
+    name: String,
+
+    #[arg(long = "team-sizes", value_delimiter = ',', default_value = "1")]
+    team_sizes: Vec<u8>,
+
+    /// Camps cycle through the neutral monsters of this file
+    #[arg(long, default_value = "game/stats.toml")]
+    stats: String,
+
+    #[arg(long, short)]
+    output: PathBuf,
+}
+
+fn main() -> ExitCode {
+    let args = CliArgs::parse();
+    let config = match GameConfig::load(&args.stats, "game/spells.toml", "game/rules.toml") {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Cannot load the game configuration: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let settings = GeneratorSettings {
+        seed: args.seed,
+        size: args.size,
+        lanes: args.lanes,
+        jungle_density: args.jungle_density,
+        bush_frequency: args.bush_frequency,
+        // Camps with a first spawn delay are epic objectives, placed by hand
+        monsters: if config.neutral_camps.is_empty() {
+            config
+                .neutral_monsters
+                .iter()
+                .map(|monster| monster.id.clone())
+                .collect()
+        } else {
+            config
+                .neutral_camps
+                .iter()
+                .filter(|camp| camp.first_spawn_secs == 0)
+                .map(|camp| camp.id.clone())
+                .collect()
+        },
+        metadata: MapMetadata {
+            id: args.id,
+            name: args.name,
+            team_sizes: args.team_sizes,
+        },
+    };
+    let map = match generate_map(&settings) {
+        Ok(map) => map,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let json = match map.to_json() {
+        Ok(json) => json,
+        Err(e) => {
+            eprintln!("Cannot serialize the map: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    if let Err(e) = fs::write(&args.output, json) {
+        eprintln!("Cannot write {}: {}", args.output.display(), e);
+        return ExitCode::FAILURE;
+    }
+    println!("Map written to {}", args.output.display());
+    ExitCode::SUCCESS
+}
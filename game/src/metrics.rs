@@ -0,0 +1,343 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Write,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use log::{debug, warn};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+    spawn,
+};
+
+use crate::game::{PlayerId, systems::System};
+
+/// Measures of a single tick, built by the `GameManager`
+#[derive(Debug, Clone, Default)]
+pub struct TickMetrics {
+    pub tick: u64,
+    pub duration: Duration,
+    /// Tick period from the rules
+    pub budget: Duration,
+    /// How late the tick started after its deadline
+    pub drift: Duration,
+    /// Ticks dropped by the clock after this one
+    pub skipped: u64,
+    /// Ticks run per second over the last second
+    pub rate: f64,
+    /// Time spent in each system that ran, in pipeline order
+    pub systems: Vec<(System, Duration)>,
+    pub champions: usize,
+    pub minions: usize,
+    pub monsters: usize,
+    pub towers: usize,
+    pub projectiles: usize,
+    pub animations: usize,
+    pub path_searches: usize,
+    pub path_nodes: usize,
+    /// Bytes queued for each player since the previous tick, messages
+    /// dropped on a full channel excluded
+    pub bytes_per_player: HashMap<PlayerId, usize>,
+}
+
+impl TickMetrics {
+    pub fn is_overrun(&self) -> bool {
+        self.duration > self.budget
+    }
+
+    /// System taking the most time during the tick
+    pub fn slowest_system(&self) -> Option<(System, Duration)> {
+        self.systems
+            .iter()
+            .copied()
+            .max_by_key(|(_, duration)| *duration)
+    }
+}
+
+/// Metrics of the whole game: the last tick, and counters since the start
+#[derive(Debug, Default)]
+pub struct Metrics {
+    last_tick: TickMetrics,
+    ticks_total: u64,
+    overruns_total: u64,
+    skipped_total: u64,
+    system_seconds_total: BTreeMap<&'static str, f64>,
+    bytes_sent_total: BTreeMap<PlayerId, u64>,
+}
+
+impl Metrics {
+    pub fn record(&mut self, tick: TickMetrics) {
+        self.ticks_total += 1;
+        if tick.is_overrun() {
+            self.overruns_total += 1;
+        }
+        self.skipped_total += tick.skipped;
+        for (system, duration) in tick.systems.iter() {
+            *self.system_seconds_total.entry(system.name()).or_default() += duration.as_secs_f64();
+        }
+        for (player_id, bytes) in tick.bytes_per_player.iter() {
+            *self.bytes_sent_total.entry(*player_id).or_default() += *bytes as u64;
+        }
+        self.last_tick = tick;
+    }
+
+    /// Metrics in the Prometheus text format
+    pub fn render(&self) -> String {
+        let tick = &self.last_tick;
+        let mut out = String::new();
+        write_metric(
+            &mut out,
+            "game_ticks_total",
+            "counter",
+            "Ticks run since the start",
+            &[(String::new(), self.ticks_total as f64)],
+        );
+        write_metric(
+            &mut out,
+            "game_tick_overruns_total",
+            "counter",
+            "Ticks longer than the tick period",
+            &[(String::new(), self.overruns_total as f64)],
+        );
+        write_metric(
+            &mut out,
+            "game_ticks_skipped_total",
+            "counter",
+            "Ticks dropped by the clock to keep the rate",
+            &[(String::new(), self.skipped_total as f64)],
+        );
+        write_metric(
+            &mut out,
+            "game_tick_budget_seconds",
+            "gauge",
+            "Tick period",
+            &[(String::new(), tick.budget.as_secs_f64())],
+        );
+        write_metric(
+            &mut out,
+            "game_tick_duration_seconds",
+            "gauge",
+            "Duration of the last tick",
+            &[(String::new(), tick.duration.as_secs_f64())],
+        );
+        write_metric(
+            &mut out,
+            "game_tick_drift_seconds",
+            "gauge",
+            "Delay between the deadline of the last tick and its start",
+            &[(String::new(), tick.drift.as_secs_f64())],
+        );
+        write_metric(
+            &mut out,
+            "game_tick_rate_hertz",
+            "gauge",
+            "Ticks run per second over the last second",
+            &[(String::new(), tick.rate)],
+        );
+        let systems: Vec<(String, f64)> = tick
+            .systems
+            .iter()
+            .map(|(system, duration)| {
+                (
+                    format!("system=\"{}\"", system.name()),
+                    duration.as_secs_f64(),
+                )
+            })
+            .collect();
+        write_metric(
+            &mut out,
+            "game_system_duration_seconds",
+            "gauge",
+            "Duration of each system during the last tick",
+            &systems,
+        );
+        let systems_total: Vec<(String, f64)> = self
+            .system_seconds_total
+            .iter()
+            .map(|(name, seconds)| (format!("system=\"{}\"", name), *seconds))
+            .collect();
+        write_metric(
+            &mut out,
+            "game_system_seconds_total",
+            "counter",
+            "Time spent in each system since the start",
+            &systems_total,
+        );
+        let entities: Vec<(String, f64)> = [
+            ("champion", tick.champions),
+            ("minion", tick.minions),
+            ("monster", tick.monsters),
+            ("tower", tick.towers),
+        ]
+        .iter()
+        .map(|(kind, count)| (format!("kind=\"{}\"", kind), *count as f64))
+        .collect();
+        write_metric(
+            &mut out,
+            "game_entities",
+            "gauge",
+            "Units alive after the last tick",
+            &entities,
+        );
+        write_metric(
+            &mut out,
+            "game_projectiles",
+            "gauge",
+            "Projectiles in flight after the last tick",
+            &[(String::new(), tick.projectiles as f64)],
+        );
+        write_metric(
+            &mut out,
+            "game_animations",
+            "gauge",
+            "Animations playing after the last tick",
+            &[(String::new(), tick.animations as f64)],
+        );
+        write_metric(
+            &mut out,
+            "game_pathfinding_searches",
+            "gauge",
+            "Paths asked during the last tick",
+            &[(String::new(), tick.path_searches as f64)],
+        );
+        write_metric(
+            &mut out,
+            "game_pathfinding_nodes",
+            "gauge",
+            "Cells expanded by the pathfinding during the last tick",
+            &[(String::new(), tick.path_nodes as f64)],
+        );
+        let mut players: Vec<_> = tick.bytes_per_player.iter().collect();
+        players.sort();
+        let bytes: Vec<(String, f64)> = players
+            .into_iter()
+            .map(|(player_id, bytes)| (format!("player=\"{}\"", player_id), *bytes as f64))
+            .collect();
+        write_metric(
+            &mut out,
+            "game_bytes_sent",
+            "gauge",
+            "Bytes queued for each player during the last tick",
+            &bytes,
+        );
+        let bytes_total: Vec<(String, f64)> = self
+            .bytes_sent_total
+            .iter()
+            .map(|(player_id, bytes)| (format!("player=\"{}\"", player_id), *bytes as f64))
+            .collect();
+        write_metric(
+            &mut out,
+            "game_bytes_sent_total",
+            "counter",
+            "Bytes queued for each player since the start",
+            &bytes_total,
+        );
+        out
+    }
+}
+
+fn write_metric(out: &mut String, name: &str, kind: &str, help: &str, samples: &[(String, f64)]) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+    for (labels, value) in samples {
+        if labels.is_empty() {
+            let _ = writeln!(out, "{} {}", name, value);
+        } else {
+            let _ = writeln!(out, "{}{{{}}} {}", name, labels, value);
+        }
+    }
+}
+
+/// Answers `GET /metrics` with the metrics in the Prometheus text format
+pub async fn serve(listener: TcpListener, metrics: Arc<Mutex<Metrics>>) {
+    loop {
+        let (mut stream, addr) = match listener.accept().await {
+            Ok(connection) => connection,
+            Err(e) => {
+                warn!("Error accepting a metrics connection: {}", e);
+                continue;
+            }
+        };
+        let metrics = Arc::clone(&metrics);
+        spawn(async move {
+            let mut request = [0; 1024];
+            let Ok(read) = stream.read(&mut request).await else {
+                return;
+            };
+            let response = if request[..read].starts_with(b"GET /metrics ") {
+                let body = match metrics.lock() {
+                    Ok(metrics) => metrics.render(),
+                    Err(_) => String::new(),
+                };
+                format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                )
+            } else {
+                "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                    .to_string()
+            };
+            if let Err(e) = stream.write_all(response.as_bytes()).await {
+                debug!("Error answering metrics request from {:?}: {}", addr, e);
+            }
+            let _ = stream.shutdown().await;
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_tick(duration: Duration) -> TickMetrics {
+        TickMetrics {
+            tick: 21,
+            duration,
+            budget: Duration::from_millis(40),
+            skipped: 2,
+            systems: vec![
+                (System::Buffs, Duration::from_millis(1)),
+                (System::Packets, Duration::from_millis(3)),
+            ],
+            champions: 2,
+            minions: 12,
+            projectiles: 3,
+            bytes_per_player: HashMap::from([(1, 400), (2, 300)]),
+            ..TickMetrics::default()
+        }
+    }
+
+    #[test]
+    fn test_record_counts_overruns_and_bytes() {
+        let mut metrics = Metrics::default();
+        metrics.record(create_tick(Duration::from_millis(5)));
+        metrics.record(create_tick(Duration::from_millis(41)));
+
+        assert_eq!(metrics.ticks_total, 2);
+        assert_eq!(metrics.overruns_total, 1);
+        assert_eq!(metrics.skipped_total, 4);
+        assert_eq!(metrics.bytes_sent_total[&1], 800);
+        assert_eq!(
+            create_tick(Duration::ZERO).slowest_system(),
+            Some((System::Packets, Duration::from_millis(3)))
+        );
+    }
+
+    #[test]
+    fn test_render_uses_the_prometheus_text_format() {
+        let mut metrics = Metrics::default();
+        metrics.record(create_tick(Duration::from_millis(5)));
+        let text = metrics.render();
+
+        assert!(text.contains("# TYPE game_ticks_total counter\ngame_ticks_total 1\n"));
+        assert!(text.contains("game_tick_duration_seconds 0.005\n"));
+        assert!(text.contains("game_tick_budget_seconds 0.04\n"));
+        assert!(text.contains("game_system_duration_seconds{system=\"packets\"} 0.003\n"));
+        assert!(text.contains("game_entities{kind=\"minion\"} 12\n"));
+        assert!(text.contains("game_projectiles 3\n"));
+        assert!(text.contains("game_bytes_sent{player=\"2\"} 300\n"));
+    }
+}
@@ -1,6 +1,13 @@
 use bytes::BufMut;
 use bytes::BytesMut;
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpellStatus {
+    pub rank: u8,
+    pub cooldown_remaining_ms: u32,
+    pub ready: bool,
+}
+
 #[derive(Debug)]
 pub struct BoardPacket {
     pub version: u8,
@@ -13,6 +20,11 @@ pub struct BoardPacket {
     pub level: u8,
     pub xp: u32,
     pub xp_needed: u32,
+    pub skill_points: u8,
+    /// Top left cell of the view on the whole board
+    pub view_row: u16,
+    pub view_col: u16,
+    pub spells: Vec<SpellStatus>,
     pub length: u16,
     pub encoded_board: Vec<u8>,
 }
@@ -26,6 +38,10 @@ impl BoardPacket {
         level: u8,
         xp: u32,
         xp_needed: u32,
+        skill_points: u8,
+        view_row: u16,
+        view_col: u16,
+        spells: Vec<SpellStatus>,
         encoded_board: Vec<u8>,
     ) -> Self {
         let length = encoded_board.len().try_into().unwrap();
@@ -40,6 +56,10 @@ impl BoardPacket {
             level,
             xp,
             xp_needed,
+            skill_points,
+            view_row,
+            view_col,
+            spells,
             length,
             encoded_board,
         }
@@ -57,6 +77,15 @@ impl BoardPacket {
         buffer.put_u8(self.level);
         buffer.put_u32(self.xp);
         buffer.put_u32(self.xp_needed);
+        buffer.put_u8(self.skill_points);
+        buffer.put_u16(self.view_row);
+        buffer.put_u16(self.view_col);
+        buffer.put_u8(self.spells.len() as u8);
+        for spell in &self.spells {
+            buffer.put_u8(spell.rank);
+            buffer.put_u32(spell.cooldown_remaining_ms);
+            buffer.put_u8(spell.ready as u8);
+        }
         buffer.put_u16(self.length);
         buffer.extend_from_slice(&self.encoded_board);
         buffer
@@ -78,6 +107,21 @@ mod tests {
         let level = 1;
         let xp = 0;
         let xp_needed = 35;
+        let skill_points = 1;
+        let view_row = 10;
+        let view_col = 24;
+        let spells = vec![
+            SpellStatus {
+                rank: 1,
+                cooldown_remaining_ms: 0,
+                ready: true,
+            },
+            SpellStatus {
+                rank: 2,
+                cooldown_remaining_ms: 1500,
+                ready: false,
+            },
+        ];
         let expected_length = encoded_board_data.len() as u16;
 
         let packet = BoardPacket::new(
@@ -88,6 +132,10 @@ mod tests {
             level,
             xp,
             xp_needed,
+            skill_points,
+            view_row,
+            view_col,
+            spells.clone(),
             encoded_board_data.clone(),
         );
 
@@ -101,6 +149,10 @@ mod tests {
         assert_eq!(packet.level, 1);
         assert_eq!(packet.xp, 0);
         assert_eq!(packet.xp_needed, 35);
+        assert_eq!(packet.skill_points, 1);
+        assert_eq!(packet.view_row, 10);
+        assert_eq!(packet.view_col, 24);
+        assert_eq!(packet.spells, spells);
         assert_eq!(packet.length, expected_length);
         assert_eq!(packet.encoded_board, encoded_board_data);
     }
@@ -115,6 +167,14 @@ mod tests {
         let level = 1;
         let xp = 0;
         let xp_needed = 35;
+        let skill_points = 0;
+        let view_row = 10;
+        let view_col = 300;
+        let spells = vec![SpellStatus {
+            rank: 3,
+            cooldown_remaining_ms: 2500,
+            ready: false,
+        }];
         let packet = BoardPacket::new(
             health,
             max_health,
@@ -123,6 +183,10 @@ mod tests {
             level,
             xp,
             xp_needed,
+            skill_points,
+            view_row,
+            view_col,
+            spells.clone(),
             encoded_board_data.clone(),
         );
 
@@ -140,6 +204,13 @@ mod tests {
         expected_buffer.put_u8(packet.level);
         expected_buffer.put_u32(packet.xp);
         expected_buffer.put_u32(packet.xp_needed);
+        expected_buffer.put_u8(packet.skill_points);
+        expected_buffer.put_u16(packet.view_row);
+        expected_buffer.put_u16(packet.view_col);
+        expected_buffer.put_u8(1); // Number of spells
+        expected_buffer.put_u8(3); // Spell rank
+        expected_buffer.put_u32(2500); // Spell cooldown remaining in ms
+        expected_buffer.put_u8(0); // Spell is not ready
         expected_buffer.put_u16(packet.length); // encoded_board_data.len() as u16 (as BigEndian)
         expected_buffer.extend_from_slice(&packet.encoded_board); // [0, 1, 1, 2, 3, 1, 1]
 
@@ -147,5 +218,13 @@ mod tests {
             serialized_buffer, expected_buffer,
             "Serialized buffer should match expected format"
         );
+        // Same bytes as the fixture decoded by the Go client, in shared/packet_test.go
+        assert_eq!(
+            &serialized_buffer[..],
+            &[
+                1, 9, 0, 0, 1, 44, 1, 144, 0, 100, 0, 100, 1, 0, 0, 0, 0, 0, 0, 0, 35, 0, 0, 10, 1,
+                44, 1, 3, 0, 0, 9, 196, 0, 0, 7, 0, 1, 1, 2, 3, 1, 1
+            ]
+        );
     }
 }
@@ -0,0 +1,80 @@
+use bytes::BufMut;
+use bytes::BytesMut;
+
+use crate::game::spell::CastError;
+
+#[derive(Debug)]
+pub struct CastFailedPacket {
+    pub version: u8,
+    pub code: u8,
+    pub slot: u8,
+    pub reason: u8,
+}
+
+impl CastFailedPacket {
+    pub fn new(slot: u8, error: &CastError) -> Self {
+        let reason = match error {
+            CastError::OnCooldown(_) => 0,
+            CastError::NotEnoughMana { .. } => 1,
+        };
+        CastFailedPacket {
+            version: 1,
+            code: 14,
+            slot,
+            reason,
+        }
+    }
+
+    pub fn serialize(&self) -> BytesMut {
+        let mut buffer = BytesMut::new();
+        buffer.put_u8(self.version);
+        buffer.put_u8(self.code);
+        buffer.put_u8(self.slot);
+        buffer.put_u8(self.reason);
+        buffer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_cast_failed_packet_new() {
+        let packet = CastFailedPacket::new(0, &CastError::OnCooldown(Duration::from_secs(2)));
+        assert_eq!(packet.version, 1);
+        assert_eq!(packet.code, 14);
+        assert_eq!(packet.slot, 0);
+        assert_eq!(packet.reason, 0);
+
+        let packet = CastFailedPacket::new(
+            1,
+            &CastError::NotEnoughMana {
+                required: 20,
+                available: 5,
+            },
+        );
+        assert_eq!(packet.slot, 1);
+        assert_eq!(packet.reason, 1);
+    }
+
+    #[test]
+    fn test_cast_failed_packet_serialize() {
+        let packet = CastFailedPacket::new(
+            1,
+            &CastError::NotEnoughMana {
+                required: 20,
+                available: 5,
+            },
+        );
+
+        let serialized_buffer = packet.serialize();
+
+        assert_eq!(
+            serialized_buffer,
+            BytesMut::from(&[1, 14, 1, 1][..]),
+            "Serialized buffer should match expected format"
+        );
+    }
+}
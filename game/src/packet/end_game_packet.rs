@@ -1,23 +1,58 @@
+use bytes::BufMut;
+use bytes::BytesMut;
+
 use crate::game::cell::Team;
+use crate::packet::scoreboard_packet::ScoreEntry;
 
 #[derive(Debug)]
 pub struct EndGamePacket {
     pub winner: Team,
+    /// Final scoreboard of the match
+    pub scores: Vec<ScoreEntry>,
 }
 
 impl EndGamePacket {
-    pub fn new(winner: Team) -> Self {
-        EndGamePacket { winner }
+    pub fn new(winner: Team, scores: Vec<ScoreEntry>) -> Self {
+        EndGamePacket { winner, scores }
     }
 
     pub fn serialize(&self) -> Vec<u8> {
-        let mut bytes = Vec::new();
-        bytes.push(1);
-        bytes.push(12);
-        bytes.push(match self.winner {
+        let mut buffer = BytesMut::new();
+        buffer.put_u8(1);
+        buffer.put_u8(12);
+        buffer.put_u8(match self.winner {
             Team::Red => 0,
             Team::Blue => 1,
         });
-        bytes
+        buffer.put_u8(self.scores.len() as u8);
+        for entry in &self.scores {
+            entry.put(&mut buffer);
+        }
+        buffer.to_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::scoreboard::PlayerScore;
+
+    #[test]
+    fn test_end_game_packet_serialize() {
+        let packet = EndGamePacket::new(Team::Red, Vec::new());
+        assert_eq!(packet.serialize(), vec![1, 12, 0, 0]);
+
+        let entry = ScoreEntry {
+            player_id: 2,
+            team: Team::Red,
+            score: PlayerScore {
+                kills: 1,
+                ..PlayerScore::default()
+            },
+        };
+        let serialized = EndGamePacket::new(Team::Blue, vec![entry]).serialize();
+        assert_eq!(serialized.len(), 4 + 23);
+        assert_eq!(&serialized[..7], &[1, 12, 1, 1, 0, 2, 0]);
+        assert_eq!(&serialized[7..9], &[0, 1]);
     }
 }
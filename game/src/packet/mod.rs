@@ -1,5 +1,10 @@
 pub mod action_packet;
 pub mod board_packet;
+pub mod cast_failed_packet;
 pub mod end_game_packet;
+pub mod move_to_packet;
+pub mod respawn_packet;
+pub mod scoreboard_packet;
 pub mod spell_selection_packet;
 pub mod start_packet;
+pub mod tick_rate_packet;
@@ -0,0 +1,55 @@
+#[derive(Debug, Clone, Copy)]
+pub struct MoveToPacket {
+    pub version: u8,
+    pub code: u8,
+    pub attack_move: bool,
+    pub row: u16,
+    pub col: u16,
+}
+
+impl MoveToPacket {
+    pub fn deserialize(bytes: &[u8]) -> Result<Self, &'static str> {
+        if bytes.len() != 7 {
+            return Err("Move to packet must be 7 bytes long");
+        }
+
+        Ok(MoveToPacket {
+            version: bytes[0],
+            code: bytes[1],
+            attack_move: bytes[2] != 0,
+            row: u16::from_be_bytes([bytes[3], bytes[4]]),
+            col: u16::from_be_bytes([bytes[5], bytes[6]]),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_move_to_packet_deserialize() {
+        // version=1, code=15, attack move, row=300, col=12
+        let valid_bytes: [u8; 7] = [1, 15, 1, 1, 44, 0, 12];
+        let packet = MoveToPacket::deserialize(&valid_bytes)
+            .expect("Deserializing a valid byte slice should succeed");
+
+        assert_eq!(packet.version, 1);
+        assert_eq!(packet.code, 15);
+        assert!(packet.attack_move);
+        assert_eq!(packet.row, 300);
+        assert_eq!(packet.col, 12);
+
+        // Same bytes as the attack move sent by the Go client, in shared/packet_test.go
+        let go_packet = MoveToPacket::deserialize(&[1, 15, 1, 0, 10, 1, 44]).unwrap();
+        assert_eq!((go_packet.row, go_packet.col), (10, 300));
+
+        let plain_move = MoveToPacket::deserialize(&[1, 15, 0, 0, 5, 0, 6]).unwrap();
+        assert!(!plain_move.attack_move);
+
+        assert_eq!(
+            MoveToPacket::deserialize(&[1, 15, 0]).unwrap_err(),
+            "Move to packet must be 7 bytes long"
+        );
+    }
+}
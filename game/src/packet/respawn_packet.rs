@@ -0,0 +1,54 @@
+use bytes::BufMut;
+use bytes::BytesMut;
+use std::time::Duration;
+
+#[derive(Debug)]
+pub struct RespawnPacket {
+    pub version: u8,
+    pub code: u8,
+    pub remaining_ms: u32,
+}
+
+impl RespawnPacket {
+    pub fn new(remaining: Duration) -> Self {
+        RespawnPacket {
+            version: 1,
+            code: 17,
+            remaining_ms: remaining.as_millis() as u32,
+        }
+    }
+
+    pub fn serialize(&self) -> BytesMut {
+        let mut buffer = BytesMut::new();
+        buffer.put_u8(self.version);
+        buffer.put_u8(self.code);
+        buffer.put_u32(self.remaining_ms);
+        buffer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_respawn_packet_new() {
+        let packet = RespawnPacket::new(Duration::from_millis(12_500));
+        assert_eq!(packet.version, 1);
+        assert_eq!(packet.code, 17);
+        assert_eq!(packet.remaining_ms, 12_500);
+    }
+
+    #[test]
+    fn test_respawn_packet_serialize() {
+        let packet = RespawnPacket::new(Duration::from_millis(1000));
+
+        let serialized_buffer = packet.serialize();
+
+        assert_eq!(
+            serialized_buffer,
+            BytesMut::from(&[1, 17, 0, 0, 3, 232][..]),
+            "Serialized buffer should match expected format"
+        );
+    }
+}
@@ -0,0 +1,110 @@
+use bytes::BufMut;
+use bytes::BytesMut;
+
+use crate::game::cell::Team;
+use crate::game::scoreboard::PlayerScore;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoreEntry {
+    pub player_id: u16,
+    pub team: Team,
+    pub score: PlayerScore,
+}
+
+impl ScoreEntry {
+    /// Appends the entry, also used by the end game packet
+    pub fn put(&self, buffer: &mut BytesMut) {
+        buffer.put_u16(self.player_id);
+        buffer.put_u8(match self.team {
+            Team::Red => 0,
+            Team::Blue => 1,
+        });
+        buffer.put_u16(self.score.kills);
+        buffer.put_u16(self.score.deaths);
+        buffer.put_u16(self.score.assists);
+        buffer.put_u16(self.score.minion_kills);
+        buffer.put_u16(self.score.monster_kills);
+        buffer.put_u16(self.score.towers_destroyed);
+        buffer.put_u32(self.score.damage_dealt);
+        buffer.put_u32(self.score.damage_taken);
+    }
+}
+
+#[derive(Debug)]
+pub struct ScoreboardPacket {
+    pub version: u8,
+    pub code: u8,
+    pub entries: Vec<ScoreEntry>,
+}
+
+impl ScoreboardPacket {
+    pub fn new(entries: Vec<ScoreEntry>) -> Self {
+        ScoreboardPacket {
+            version: 1,
+            code: 16,
+            entries,
+        }
+    }
+
+    pub fn serialize(&self) -> BytesMut {
+        let mut buffer = BytesMut::new();
+        buffer.put_u8(self.version);
+        buffer.put_u8(self.code);
+        buffer.put_u8(self.entries.len() as u8);
+        for entry in &self.entries {
+            entry.put(&mut buffer);
+        }
+        buffer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_entry() -> ScoreEntry {
+        ScoreEntry {
+            player_id: 1,
+            team: Team::Blue,
+            score: PlayerScore {
+                kills: 3,
+                deaths: 1,
+                assists: 2,
+                minion_kills: 40,
+                monster_kills: 4,
+                towers_destroyed: 1,
+                damage_dealt: 70000,
+                damage_taken: 300,
+            },
+        }
+    }
+
+    #[test]
+    fn test_scoreboard_packet_new() {
+        let packet = ScoreboardPacket::new(vec![sample_entry()]);
+        assert_eq!(packet.version, 1);
+        assert_eq!(packet.code, 16);
+        assert_eq!(packet.entries.len(), 1);
+    }
+
+    #[test]
+    fn test_scoreboard_packet_serialize() {
+        let packet = ScoreboardPacket::new(vec![sample_entry()]);
+
+        let serialized_buffer = packet.serialize();
+
+        let expected: &[u8] = &[
+            1, 16, 1, // version, code, entry count
+            0, 1, 1, // player id, team
+            0, 3, 0, 1, 0, 2, // kills, deaths, assists
+            0, 40, 0, 4, 0, 1, // minion kills, monster kills, towers destroyed
+            0, 1, 17, 112, // damage dealt
+            0, 0, 1, 44, // damage taken
+        ];
+        assert_eq!(
+            serialized_buffer,
+            BytesMut::from(expected),
+            "Serialized buffer should match expected format"
+        );
+    }
+}
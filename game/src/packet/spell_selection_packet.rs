@@ -5,11 +5,12 @@ pub struct SpellSelectionPacket {
     pub code: u8,
     pub spell1: u8,
     pub spell2: u8,
+    pub champion: u8,
 }
 
 impl SpellSelectionPacket {
     pub fn deserialize(buffer: &[u8]) -> io::Result<Self> {
-        if buffer.len() < 4 {
+        if buffer.len() < 5 {
             return Err(io::Error::new(
                 ErrorKind::InvalidData,
                 "SpellSelectionPacket buffer too short",
@@ -20,6 +21,32 @@ impl SpellSelectionPacket {
             code: buffer[1],
             spell1: buffer[2],
             spell2: buffer[3],
+            champion: buffer[4],
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spell_selection_packet_deserialize() {
+        let valid_bytes: [u8; 5] = [1, 13, 0, 1, 2]; // version=1, code=13, spells 0 and 1, champion 2
+        let packet = SpellSelectionPacket::deserialize(&valid_bytes)
+            .expect("Deserializing a valid byte slice should succeed");
+
+        assert_eq!(packet.version, 1);
+        assert_eq!(packet.code, 13);
+        assert_eq!(packet.spell1, 0);
+        assert_eq!(packet.spell2, 1);
+        assert_eq!(packet.champion, 2);
+
+        // Packet without the champion selection
+        let short_bytes: [u8; 4] = [1, 13, 0, 1];
+        assert!(
+            SpellSelectionPacket::deserialize(&short_bytes).is_err(),
+            "Deserializing a short byte slice should return an error"
+        );
+    }
+}
@@ -0,0 +1,76 @@
+use bytes::BufMut;
+use bytes::BytesMut;
+use std::time::Duration;
+
+use crate::tick_clock::TickTiming;
+
+#[derive(Debug)]
+pub struct TickRatePacket {
+    pub version: u8,
+    pub code: u8,
+    pub period_ms: u16,
+    /// Ticks per second over the last second, in hundredths
+    pub rate_centihertz: u16,
+    pub drift_ms: u16,
+}
+
+impl TickRatePacket {
+    pub fn new(period: Duration, timing: &TickTiming) -> Self {
+        TickRatePacket {
+            version: 1,
+            code: 18,
+            period_ms: period.as_millis().min(u16::MAX as u128) as u16,
+            rate_centihertz: (timing.rate * 100.0).round().clamp(0.0, u16::MAX as f64) as u16,
+            drift_ms: timing.drift.as_millis().min(u16::MAX as u128) as u16,
+        }
+    }
+
+    pub fn serialize(&self) -> BytesMut {
+        let mut buffer = BytesMut::new();
+        buffer.put_u8(self.version);
+        buffer.put_u8(self.code);
+        buffer.put_u16(self.period_ms);
+        buffer.put_u16(self.rate_centihertz);
+        buffer.put_u16(self.drift_ms);
+        buffer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tick_rate_packet_new() {
+        let timing = TickTiming {
+            slot: 80,
+            drift: Duration::from_micros(3_600),
+            skipped: 0,
+            rate: 24.876,
+        };
+        let packet = TickRatePacket::new(Duration::from_millis(40), &timing);
+        assert_eq!(packet.version, 1);
+        assert_eq!(packet.code, 18);
+        assert_eq!(packet.period_ms, 40);
+        assert_eq!(packet.rate_centihertz, 2488);
+        assert_eq!(packet.drift_ms, 3);
+    }
+
+    #[test]
+    fn test_tick_rate_packet_serialize() {
+        let timing = TickTiming {
+            rate: 25.0,
+            drift: Duration::from_millis(300),
+            ..TickTiming::default()
+        };
+        let packet = TickRatePacket::new(Duration::from_millis(40), &timing);
+
+        let serialized_buffer = packet.serialize();
+
+        assert_eq!(
+            serialized_buffer,
+            BytesMut::from(&[1, 18, 0, 40, 9, 196, 1, 44][..]),
+            "Serialized buffer should match expected format"
+        );
+    }
+}
@@ -0,0 +1,186 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+/// Window over which the actual tick rate is measured
+const RATE_WINDOW: Duration = Duration::from_secs(1);
+
+/// Most late ticks run back to back with `MissedTicks::CatchUp`, the older
+/// ones are skipped
+pub const MAX_CATCH_UP_TICKS: u64 = 5;
+
+/// What the clock does with the deadlines that passed while a tick ran late
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum MissedTicks {
+    /// Runs the missed ticks right away, the last five at most
+    CatchUp,
+    /// Drops the missed ticks and waits for the next deadline
+    Skip,
+}
+
+/// Timing of a tick, given by the clock when it is due
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TickTiming {
+    /// Slot of the tick in the schedule, skipped slots included
+    pub slot: u64,
+    /// How late the tick starts after its deadline
+    pub drift: Duration,
+    /// Slots dropped after this tick
+    pub skipped: u64,
+    /// Ticks run per second over the last second
+    pub rate: f64,
+}
+
+/// Fixed-rate schedule of the ticks. Deadlines sit on a grid of `period`
+/// from the start, so a slow tick never shifts the next ones
+#[derive(Debug)]
+pub struct TickClock {
+    period: Duration,
+    policy: MissedTicks,
+    start: Instant,
+    next_slot: u64,
+    skipped_total: u64,
+    recent_ticks: VecDeque<Instant>,
+}
+
+impl TickClock {
+    pub fn new(period: Duration, policy: MissedTicks, start: Instant) -> Self {
+        TickClock {
+            period,
+            policy,
+            start,
+            next_slot: 0,
+            skipped_total: 0,
+            recent_ticks: VecDeque::new(),
+        }
+    }
+
+    /// When the next tick is due
+    pub fn deadline(&self) -> Instant {
+        self.start + self.slot_offset(self.next_slot)
+    }
+
+    /// Ticks the clock once the deadline passed, `now` being the start of
+    /// the tick. Moves the deadline according to the missed tick policy
+    pub fn tick(&mut self, now: Instant) -> TickTiming {
+        let slot = self.next_slot;
+        let drift = now.saturating_duration_since(self.deadline());
+        // Later deadlines already passed as well
+        let behind = (drift.as_nanos() / self.period.as_nanos().max(1)) as u64;
+        let skipped = match self.policy {
+            MissedTicks::Skip => behind,
+            MissedTicks::CatchUp => behind.saturating_sub(MAX_CATCH_UP_TICKS),
+        };
+        self.next_slot += 1 + skipped;
+        self.skipped_total += skipped;
+
+        self.recent_ticks.push_back(now);
+        while self
+            .recent_ticks
+            .front()
+            .is_some_and(|first| now.duration_since(*first) > RATE_WINDOW)
+        {
+            self.recent_ticks.pop_front();
+        }
+
+        TickTiming {
+            slot,
+            drift,
+            skipped,
+            rate: self.rate(),
+        }
+    }
+
+    /// Ticks run per second over the last second, the nominal rate until
+    /// two ticks were run
+    pub fn rate(&self) -> f64 {
+        match (self.recent_ticks.front(), self.recent_ticks.back()) {
+            (Some(first), Some(last)) if last > first => {
+                (self.recent_ticks.len() - 1) as f64 / last.duration_since(*first).as_secs_f64()
+            }
+            _ => 1.0 / self.period.as_secs_f64(),
+        }
+    }
+
+    pub fn skipped_total(&self) -> u64 {
+        self.skipped_total
+    }
+
+    fn slot_offset(&self, slot: u64) -> Duration {
+        Duration::from_nanos((self.period.as_nanos() * slot as u128) as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PERIOD: Duration = Duration::from_millis(40);
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn test_deadlines_stay_on_the_grid() {
+        let start = Instant::now();
+        let mut clock = TickClock::new(PERIOD, MissedTicks::Skip, start);
+
+        assert_eq!(clock.deadline(), start);
+        let timing = clock.tick(start + ms(3));
+        assert_eq!(timing.slot, 0);
+        assert_eq!(timing.drift, ms(3));
+        assert_eq!(timing.skipped, 0);
+        // The 3ms late tick does not push the next deadline
+        assert_eq!(clock.deadline(), start + ms(40));
+    }
+
+    #[test]
+    fn test_skip_drops_the_missed_deadlines() {
+        let start = Instant::now();
+        let mut clock = TickClock::new(PERIOD, MissedTicks::Skip, start);
+        clock.tick(start);
+
+        // Tick 1 was due at 40ms and starts at 135ms: 80ms and 120ms passed
+        let timing = clock.tick(start + ms(135));
+        assert_eq!(timing.slot, 1);
+        assert_eq!(timing.drift, ms(95));
+        assert_eq!(timing.skipped, 2);
+        assert_eq!(clock.deadline(), start + ms(160));
+        assert_eq!(clock.skipped_total(), 2);
+    }
+
+    #[test]
+    fn test_catch_up_runs_the_missed_ticks_up_to_a_limit() {
+        let start = Instant::now();
+        let mut clock = TickClock::new(PERIOD, MissedTicks::CatchUp, start);
+        clock.tick(start);
+
+        let timing = clock.tick(start + ms(135));
+        assert_eq!(timing.skipped, 0);
+        // The missed deadlines are due right away
+        assert_eq!(clock.deadline(), start + ms(80));
+        assert_eq!(clock.tick(start + ms(136)).slot, 2);
+        assert_eq!(clock.tick(start + ms(137)).slot, 3);
+        assert_eq!(clock.deadline(), start + ms(160));
+
+        // 10 deadlines passed, only the last 5 are caught up
+        let timing = clock.tick(start + ms(160 + 400));
+        assert_eq!(timing.slot, 4);
+        assert_eq!(timing.skipped, 10 - MAX_CATCH_UP_TICKS);
+        assert_eq!(clock.deadline(), start + ms(40 * 10));
+    }
+
+    #[test]
+    fn test_rate_is_measured_over_the_last_second() {
+        let start = Instant::now();
+        let mut clock = TickClock::new(PERIOD, MissedTicks::Skip, start);
+        assert_eq!(clock.rate(), 25.0);
+
+        for slot in 0..50 {
+            clock.tick(start + ms(slot * 50));
+        }
+        assert!((clock.rate() - 20.0).abs() < 1e-9);
+    }
+}
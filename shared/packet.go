@@ -24,6 +24,11 @@ code 9: receive RLEboard
 code 10: receive Delta
 code 11: game close
 code 12: game end
+code 13: send spell selection
+code 14: receive cast failed
+code 16: receive scoreboard
+code 17: receive respawn countdown
+code 18: receive tick rate
 */
 
 type Packet interface {
@@ -386,16 +391,78 @@ func (gc *GameClosePacket) Serialize() []byte {
 	return buf.Bytes()
 }
 
+// ScoreEntry is the line of a player in the scoreboard, written on 23 byte
+type ScoreEntry struct {
+	PlayerID        int
+	Team            int // 0: red 1: blue
+	Kills           int
+	Deaths          int
+	Assists         int
+	MinionKills     int
+	MonsterKills    int
+	TowersDestroyed int
+	DamageDealt     int
+	DamageTaken     int
+}
+
+const scoreEntrySize = 23
+
+func writeScoreEntries(buf *bytes.Buffer, entries []ScoreEntry) {
+	buf.WriteByte(byte(len(entries)))
+	for _, entry := range entries {
+		buf.Write(binary.BigEndian.AppendUint16(nil, uint16(entry.PlayerID)))
+		buf.WriteByte(byte(entry.Team))
+		for _, counter := range []int{entry.Kills, entry.Deaths, entry.Assists, entry.MinionKills, entry.MonsterKills, entry.TowersDestroyed} {
+			buf.Write(binary.BigEndian.AppendUint16(nil, uint16(counter)))
+		}
+		buf.Write(binary.BigEndian.AppendUint32(nil, uint32(entry.DamageDealt)))
+		buf.Write(binary.BigEndian.AppendUint32(nil, uint32(entry.DamageTaken)))
+	}
+}
+
+// readScoreEntries reads the entry count at data[0] and the entries following it
+func readScoreEntries(data []byte) ([]ScoreEntry, error) {
+	if len(data) < 1 {
+		return nil, errors.New("invalid score entries length")
+	}
+	count := int(data[0])
+	if len(data) < 1+count*scoreEntrySize {
+		return nil, errors.New("invalid score entries length")
+	}
+	entries := make([]ScoreEntry, count)
+	for i := range count {
+		entry := data[1+i*scoreEntrySize : 1+(i+1)*scoreEntrySize]
+		counter := func(offset int) int {
+			return int(binary.BigEndian.Uint16(entry[offset : offset+2]))
+		}
+		entries[i] = ScoreEntry{
+			PlayerID:        counter(0),
+			Team:            int(entry[2]),
+			Kills:           counter(3),
+			Deaths:          counter(5),
+			Assists:         counter(7),
+			MinionKills:     counter(9),
+			MonsterKills:    counter(11),
+			TowersDestroyed: counter(13),
+			DamageDealt:     int(binary.BigEndian.Uint32(entry[15:19])),
+			DamageTaken:     int(binary.BigEndian.Uint32(entry[19:23])),
+		}
+	}
+	return entries, nil
+}
+
 type EndGamePacket struct {
 	version, code int
-	Win           bool
+	Winner        int // 0: red 1: blue
+	Scores        []ScoreEntry
 }
 
-func NewEndGamePacket(win bool) *EndGamePacket {
+func NewEndGamePacket(winner int, scores []ScoreEntry) *EndGamePacket {
 	return &EndGamePacket{
 		version: 1,
 		code:    12,
-		Win:     win,
+		Winner:  winner,
+		Scores:  scores,
 	}
 }
 
@@ -411,25 +478,53 @@ func (egp *EndGamePacket) Serialize() []byte {
 	var buf bytes.Buffer
 	buf.WriteByte(byte(egp.version))
 	buf.WriteByte(byte(egp.code))
-	if egp.Win {
-		buf.WriteByte(1)
-	} else {
-		buf.WriteByte(0)
+	buf.WriteByte(byte(egp.Winner))
+	writeScoreEntries(&buf, egp.Scores)
+	return buf.Bytes()
+}
+
+type ScoreboardPacket struct {
+	version, code int
+	Entries       []ScoreEntry
+}
+
+func NewScoreboardPacket(entries []ScoreEntry) *ScoreboardPacket {
+	return &ScoreboardPacket{
+		version: 1,
+		code:    16,
+		Entries: entries,
 	}
+}
+
+func (sp ScoreboardPacket) Version() int {
+	return sp.version
+}
+
+func (sp ScoreboardPacket) Code() int {
+	return sp.code
+}
+
+func (sp *ScoreboardPacket) Serialize() []byte {
+	var buf bytes.Buffer
+	buf.WriteByte(byte(sp.version))
+	buf.WriteByte(byte(sp.code))
+	writeScoreEntries(&buf, sp.Entries)
 	return buf.Bytes()
 }
 
 type SpellSelectionPacket struct {
 	version, code  int
 	Spell1, Spell2 int
+	Champion       int
 }
 
-func NewSpellSelectionPacket(spell1, spell2 int) *SpellSelectionPacket {
+func NewSpellSelectionPacket(spell1, spell2, champion int) *SpellSelectionPacket {
 	return &SpellSelectionPacket{
-		version: 1,
-		code:    13,
-		Spell1:  spell1,
-		Spell2:  spell2,
+		version:  1,
+		code:     13,
+		Spell1:   spell1,
+		Spell2:   spell2,
+		Champion: champion,
 	}
 }
 
@@ -447,6 +542,7 @@ func (ssp *SpellSelectionPacket) Serialize() []byte {
 	buf.WriteByte(byte(ssp.code))
 	buf.WriteByte(byte(ssp.Spell1))
 	buf.WriteByte(byte(ssp.Spell2))
+	buf.WriteByte(byte(ssp.Champion))
 	return buf.Bytes()
 }
 
@@ -488,6 +584,54 @@ func (ap ActionPacket) Serialize() []byte {
 
 }
 
+// MoveToPacket sends the champion to a cell of the whole board
+type MoveToPacket struct {
+	version, code int
+	// An attack move stops while an enemy is in attack range
+	AttackMove    bool
+	Row           int
+	Col           int
+}
+
+func NewMoveToPacket(attackMove bool, row, col int) *MoveToPacket {
+	return &MoveToPacket{
+		version:    1,
+		code:       15,
+		AttackMove: attackMove,
+		Row:        row,
+		Col:        col,
+	}
+}
+
+func (mp MoveToPacket) Version() int {
+	return mp.version
+}
+
+func (mp MoveToPacket) Code() int {
+	return mp.code
+}
+
+func (mp *MoveToPacket) Serialize() []byte {
+	var buf bytes.Buffer
+	buf.WriteByte(byte(mp.version))
+	buf.WriteByte(byte(mp.code))
+	if mp.AttackMove {
+		buf.WriteByte(1)
+	} else {
+		buf.WriteByte(0)
+	}
+	buf.Write(binary.BigEndian.AppendUint16(nil, uint16(mp.Row)))
+	buf.Write(binary.BigEndian.AppendUint16(nil, uint16(mp.Col)))
+	return buf.Bytes()
+}
+
+// SpellStatus is the state of a spell slot sent with the board
+type SpellStatus struct {
+	Rank       int
+	CooldownMs int
+	Ready      bool
+}
+
 type BoardPacket struct {
 	version, code int
 	Points        [2]int
@@ -498,6 +642,11 @@ type BoardPacket struct {
 	Level         int
 	Xp            int
 	XpNeeded      int
+	SkillPoints   int
+	// Top left cell of the view on the whole board
+	ViewRow       int
+	ViewCol       int
+	Spells        []SpellStatus
 	Length        int
 	EncodedBoard  []byte
 }
@@ -531,13 +680,130 @@ func (bp *BoardPacket) Serialize() []byte {
 	buf.WriteByte(byte(bp.code))
 	buf.WriteByte(byte(bp.Points[0]))
 	buf.WriteByte(byte(bp.Points[1]))
-	buf.WriteByte(byte(bp.Health))
-	buf.WriteByte(byte(bp.MaxHealth))
-	buf.WriteByte(byte(bp.Length))
+	buf.Write(binary.BigEndian.AppendUint16(nil, uint16(bp.Health)))
+	buf.Write(binary.BigEndian.AppendUint16(nil, uint16(bp.MaxHealth)))
+	buf.Write(binary.BigEndian.AppendUint16(nil, uint16(bp.Mana)))
+	buf.Write(binary.BigEndian.AppendUint16(nil, uint16(bp.MaxMana)))
+	buf.WriteByte(byte(bp.Level))
+	buf.Write(binary.BigEndian.AppendUint32(nil, uint32(bp.Xp)))
+	buf.Write(binary.BigEndian.AppendUint32(nil, uint32(bp.XpNeeded)))
+	buf.WriteByte(byte(bp.SkillPoints))
+	buf.Write(binary.BigEndian.AppendUint16(nil, uint16(bp.ViewRow)))
+	buf.Write(binary.BigEndian.AppendUint16(nil, uint16(bp.ViewCol)))
+	// Each spell is written on 6 byte: rank, cooldown and ready
+	buf.WriteByte(byte(len(bp.Spells)))
+	for _, spell := range bp.Spells {
+		buf.WriteByte(byte(spell.Rank))
+		buf.Write(binary.BigEndian.AppendUint32(nil, uint32(spell.CooldownMs)))
+		if spell.Ready {
+			buf.WriteByte(1)
+		} else {
+			buf.WriteByte(0)
+		}
+	}
+	buf.Write(binary.BigEndian.AppendUint16(nil, uint16(bp.Length)))
 	buf.Write(bp.EncodedBoard)
 	return buf.Bytes()
 }
 
+type CastFailedPacket struct {
+	version, code int
+	Slot          int
+	// Reason 0: on cooldown 1: not enough mana
+	Reason int
+}
+
+func NewCastFailedPacket(slot, reason int) *CastFailedPacket {
+	return &CastFailedPacket{
+		version: 1,
+		code:    14,
+		Slot:    slot,
+		Reason:  reason,
+	}
+}
+
+func (cf CastFailedPacket) Version() int {
+	return cf.version
+}
+
+func (cf CastFailedPacket) Code() int {
+	return cf.code
+}
+
+func (cf *CastFailedPacket) Serialize() []byte {
+	var buf bytes.Buffer
+	buf.WriteByte(byte(cf.version))
+	buf.WriteByte(byte(cf.code))
+	buf.WriteByte(byte(cf.Slot))
+	buf.WriteByte(byte(cf.Reason))
+	return buf.Bytes()
+}
+
+type RespawnPacket struct {
+	version, code int
+	RemainingMs   int
+}
+
+func NewRespawnPacket(remainingMs int) *RespawnPacket {
+	return &RespawnPacket{
+		version:     1,
+		code:        17,
+		RemainingMs: remainingMs,
+	}
+}
+
+func (rp RespawnPacket) Version() int {
+	return rp.version
+}
+
+func (rp RespawnPacket) Code() int {
+	return rp.code
+}
+
+func (rp *RespawnPacket) Serialize() []byte {
+	var buf bytes.Buffer
+	buf.WriteByte(byte(rp.version))
+	buf.WriteByte(byte(rp.code))
+	buf.Write(binary.BigEndian.AppendUint32(nil, uint32(rp.RemainingMs)))
+	return buf.Bytes()
+}
+
+type TickRatePacket struct {
+	version, code int
+	PeriodMs      int
+	// Ticks per second over the last second, in hundredths
+	RateCentihertz int
+	DriftMs        int
+}
+
+func NewTickRatePacket(periodMs, rateCentihertz, driftMs int) *TickRatePacket {
+	return &TickRatePacket{
+		version:        1,
+		code:           18,
+		PeriodMs:       periodMs,
+		RateCentihertz: rateCentihertz,
+		DriftMs:        driftMs,
+	}
+}
+
+func (tp TickRatePacket) Version() int {
+	return tp.version
+}
+
+func (tp TickRatePacket) Code() int {
+	return tp.code
+}
+
+func (tp *TickRatePacket) Serialize() []byte {
+	var buf bytes.Buffer
+	buf.WriteByte(byte(tp.version))
+	buf.WriteByte(byte(tp.code))
+	buf.Write(binary.BigEndian.AppendUint16(nil, uint16(tp.PeriodMs)))
+	buf.Write(binary.BigEndian.AppendUint16(nil, uint16(tp.RateCentihertz)))
+	buf.Write(binary.BigEndian.AppendUint16(nil, uint16(tp.DriftMs)))
+	return buf.Bytes()
+}
+
 type DeltaPacket struct {
 	version, code int
 	TickID        uint32
@@ -606,7 +872,7 @@ func (dp *DeltaPacket) Serialize() []byte {
 //   - For LoginPacket, it extracts the username and password.
 //   - For RespPacket, it returns a basic response packet.
 //   - For ActionPacket, it reads the action value.
-//   - For BoardPacket, it treats all remaining data as the encoded board.
+//   - For BoardPacket, it reads the champion status and spells, then the encoded board.
 //
 // 4. Returns an error for unsupported or malformed packet types.
 func DeSerialize(data []byte) (Packet, error) {
@@ -746,6 +1012,9 @@ func DeSerialize(data []byte) (Packet, error) {
 		}, nil
 
 	case 9: // BoardPacket
+		if len(data) < 27 {
+			return nil, errors.New("invalid board packet length")
+		}
 		// First two bytes are points
 		points := [2]int{}
 		points[0] = int(data[2])
@@ -757,11 +1026,32 @@ func DeSerialize(data []byte) (Packet, error) {
 		level := int(data[12])
 		xp := int(binary.BigEndian.Uint32(data[13:17]))
 		xpNeeded := int(binary.BigEndian.Uint32(data[17:21]))
-		length := int(binary.BigEndian.Uint16(data[21:23]))
+		skillPoints := int(data[21])
+		viewRow := int(binary.BigEndian.Uint16(data[22:24]))
+		viewCol := int(binary.BigEndian.Uint16(data[24:26]))
+		// Each spell is 6 byte: rank, cooldown and ready
+		spellCount := int(data[26])
+		boardStart := 27 + spellCount*6 + 2
+		if len(data) < boardStart {
+			return nil, errors.New("invalid board packet length")
+		}
+		spells := make([]SpellStatus, spellCount)
+		for i := range spellCount {
+			start := 27 + i*6
+			spells[i] = SpellStatus{
+				Rank:       int(data[start]),
+				CooldownMs: int(binary.BigEndian.Uint32(data[start+1 : start+5])),
+				Ready:      data[start+5] == 1,
+			}
+		}
+		length := int(binary.BigEndian.Uint16(data[boardStart-2 : boardStart]))
+		if len(data) < boardStart+length {
+			return nil, errors.New("invalid board packet length")
+		}
 		log.Printf("Deserialize health: %d | %d, mana: %d | %d", health, maxHealth, mana, maxMana)
 
 		// Rest of data is the encodedBoard
-		encodedBoard := data[23 : length+23]
+		encodedBoard := data[boardStart : boardStart+length]
 		return &BoardPacket{
 			version:      version,
 			code:         code,
@@ -773,6 +1063,10 @@ func DeSerialize(data []byte) (Packet, error) {
 			Level:        level,
 			Xp:           xp,
 			XpNeeded:     xpNeeded,
+			SkillPoints:  skillPoints,
+			ViewRow:      viewRow,
+			ViewCol:      viewCol,
+			Spells:       spells,
 			Length:       length,
 			EncodedBoard: encodedBoard,
 		}, nil
@@ -814,27 +1108,166 @@ func DeSerialize(data []byte) (Packet, error) {
 		}, nil
 
 	case 12: // EndGamePacket
-		win := data[2] == 1
+		if len(data) < 4 {
+			return nil, errors.New("invalid end game packet length")
+		}
+		scores, err := readScoreEntries(data[3:])
+		if err != nil {
+			return nil, err
+		}
 		return &EndGamePacket{
 			version: version,
 			code:    code,
-			Win:     win,
+			Winner:  int(data[2]),
+			Scores:  scores,
 		}, nil
 
 	case 13: // SpellSelectionPacket
-		if len(data) < 4 {
+		if len(data) < 5 {
 			return nil, errors.New("invalid spell selection packet length")
 		}
 		spell1 := int(data[2])
 		spell2 := int(data[3])
+		champion := int(data[4])
 		return &SpellSelectionPacket{
+			version:  version,
+			code:     code,
+			Spell1:   spell1,
+			Spell2:   spell2,
+			Champion: champion,
+		}, nil
+
+	case 14: // CastFailedPacket
+		if len(data) < 4 {
+			return nil, errors.New("invalid cast failed packet length")
+		}
+		return &CastFailedPacket{
 			version: version,
 			code:    code,
-			Spell1:  spell1,
-			Spell2:  spell2,
+			Slot:    int(data[2]),
+			Reason:  int(data[3]),
+		}, nil
+
+	case 15: // MoveToPacket
+		if len(data) < 7 {
+			return nil, errors.New("invalid move to packet length")
+		}
+		return &MoveToPacket{
+			version:    version,
+			code:       code,
+			AttackMove: data[2] == 1,
+			Row:        int(binary.BigEndian.Uint16(data[3:5])),
+			Col:        int(binary.BigEndian.Uint16(data[5:7])),
+		}, nil
+
+	case 16: // ScoreboardPacket
+		entries, err := readScoreEntries(data[2:])
+		if err != nil {
+			return nil, err
+		}
+		return &ScoreboardPacket{
+			version: version,
+			code:    code,
+			Entries: entries,
+		}, nil
+
+	case 17: // RespawnPacket
+		if len(data) < 6 {
+			return nil, errors.New("invalid respawn packet length")
+		}
+		return &RespawnPacket{
+			version:     version,
+			code:        code,
+			RemainingMs: int(binary.BigEndian.Uint32(data[2:6])),
+		}, nil
+
+	case 18: // TickRatePacket
+		if len(data) < 8 {
+			return nil, errors.New("invalid tick rate packet length")
+		}
+		return &TickRatePacket{
+			version:        version,
+			code:           code,
+			PeriodMs:       int(binary.BigEndian.Uint16(data[2:4])),
+			RateCentihertz: int(binary.BigEndian.Uint16(data[4:6])),
+			DriftMs:        int(binary.BigEndian.Uint16(data[6:8])),
 		}, nil
 
 	default:
 		return nil, errors.New("unknown message type")
 	}
 }
+
+// ErrIncompletePacket is returned by PacketSize when the packet continues
+// past the data read so far
+var ErrIncompletePacket = errors.New("incomplete packet")
+
+// PacketSize returns the size of the packet at the start of data, so that
+// packets sent back to back can be split before calling DeSerialize.
+// Packets ending with a string without length take the rest of the data.
+func PacketSize(data []byte) (int, error) {
+	if len(data) < 2 {
+		return 0, ErrIncompletePacket
+	}
+	if data[0] != 1 {
+		return 0, errors.New("invalid version")
+	}
+
+	var size int
+	switch data[1] {
+	case 0, 1: // LoginPacket and SignInPacket
+		if len(data) < 4 {
+			return 0, ErrIncompletePacket
+		}
+		pwStart := 4 + int(binary.BigEndian.Uint16(data[2:4]))
+		if len(data) < pwStart+2 {
+			return 0, ErrIncompletePacket
+		}
+		size = pwStart + 2 + int(binary.BigEndian.Uint16(data[pwStart:pwStart+2]))
+	case 2, 3, 4, 7, 8, 11:
+		size = 3
+	case 5, 6: // RoomJoinPacket and LookRoomPacket
+		size = len(data)
+	case 9: // BoardPacket
+		if len(data) < 27 {
+			return 0, ErrIncompletePacket
+		}
+		lengthStart := 27 + int(data[26])*6
+		if len(data) < lengthStart+2 {
+			return 0, ErrIncompletePacket
+		}
+		size = lengthStart + 2 + int(binary.BigEndian.Uint16(data[lengthStart:lengthStart+2]))
+	case 10: // DeltaPacket
+		if len(data) < 10 {
+			return 0, ErrIncompletePacket
+		}
+		size = 10 + int(binary.BigEndian.Uint16(data[8:10]))*3
+	case 12: // EndGamePacket
+		if len(data) < 4 {
+			return 0, ErrIncompletePacket
+		}
+		size = 4 + int(data[3])*scoreEntrySize
+	case 13:
+		size = 5
+	case 14:
+		size = 4
+	case 15:
+		size = 7
+	case 16: // ScoreboardPacket
+		if len(data) < 3 {
+			return 0, ErrIncompletePacket
+		}
+		size = 3 + int(data[2])*scoreEntrySize
+	case 17:
+		size = 6
+	case 18:
+		size = 8
+	default:
+		return 0, errors.New("unknown message type")
+	}
+
+	if len(data) < size {
+		return 0, ErrIncompletePacket
+	}
+	return size, nil
+}
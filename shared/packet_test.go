@@ -0,0 +1,249 @@
+package shared_test
+
+import (
+	"bytes"
+	"errors"
+	"reflect"
+	"testing"
+
+	"github.com/GrGLeo/ctf/shared"
+)
+
+// Board packet serialized by the Rust game server, see
+// test_board_packet_serialize in game/src/packet/board_packet.rs
+var rustBoardPacket = []byte{
+	1, 9, 0, 0, 1, 44, 1, 144, 0, 100, 0, 100, 1, 0, 0, 0, 0, 0, 0, 0, 35, 0, 0, 10, 1, 44,
+	1, 3, 0, 0, 9, 196, 0, 0, 7, 0, 1, 1, 2, 3, 1, 1,
+}
+
+func TestBoardPacketRoundTrip(t *testing.T) {
+	packet, err := shared.DeSerialize(rustBoardPacket)
+	if err != nil {
+		t.Fatalf("Expected no error decoding the board packet, got %v", err)
+	}
+	board, ok := packet.(*shared.BoardPacket)
+	if !ok {
+		t.Fatalf("Expected a BoardPacket, got %T", packet)
+	}
+
+	if board.Health != 300 || board.MaxHealth != 400 {
+		t.Errorf("Expected health 300 / 400, got %d / %d", board.Health, board.MaxHealth)
+	}
+	if board.Mana != 100 || board.MaxMana != 100 {
+		t.Errorf("Expected mana 100 / 100, got %d / %d", board.Mana, board.MaxMana)
+	}
+	if board.Level != 1 || board.Xp != 0 || board.XpNeeded != 35 {
+		t.Errorf("Expected level 1 with 0 / 35 xp, got %d with %d / %d", board.Level, board.Xp, board.XpNeeded)
+	}
+	if board.SkillPoints != 0 {
+		t.Errorf("Expected 0 skill points, got %d", board.SkillPoints)
+	}
+	if board.ViewRow != 10 || board.ViewCol != 300 {
+		t.Errorf("Expected the view at 10, 300, got %d, %d", board.ViewRow, board.ViewCol)
+	}
+	expectedSpells := []shared.SpellStatus{{Rank: 3, CooldownMs: 2500, Ready: false}}
+	if !reflect.DeepEqual(board.Spells, expectedSpells) {
+		t.Errorf("Expected spells %+v, got %+v", expectedSpells, board.Spells)
+	}
+	if !bytes.Equal(board.EncodedBoard, []byte{0, 1, 1, 2, 3, 1, 1}) {
+		t.Errorf("Unexpected encoded board %v", board.EncodedBoard)
+	}
+
+	if serialized := board.Serialize(); !bytes.Equal(serialized, rustBoardPacket) {
+		t.Errorf("Expected the packet to serialize back to %v, got %v", rustBoardPacket, serialized)
+	}
+}
+
+func TestBoardPacketTooShort(t *testing.T) {
+	// The spell count announces a spell missing from the packet
+	truncated := rustBoardPacket[:29]
+	if _, err := shared.DeSerialize(truncated); err == nil {
+		t.Fatal("Expected an error for a truncated board packet")
+	}
+}
+
+func TestPacketSizeSplitsPackets(t *testing.T) {
+	// A cast failed packet is appended to the board of the same tick
+	data := append(bytes.Clone(rustBoardPacket), 1, 14, 1, 1)
+
+	if _, err := shared.PacketSize(data[:28]); !errors.Is(err, shared.ErrIncompletePacket) {
+		t.Errorf("Expected ErrIncompletePacket for a partial board, got %v", err)
+	}
+	size, err := shared.PacketSize(data)
+	if err != nil {
+		t.Fatalf("Expected no error, got %v", err)
+	}
+	if size != len(rustBoardPacket) {
+		t.Fatalf("Expected a board of %d bytes, got %d", len(rustBoardPacket), size)
+	}
+
+	packet, err := shared.DeSerialize(data[size:])
+	if err != nil {
+		t.Fatalf("Expected no error decoding the cast failed packet, got %v", err)
+	}
+	castFailed, ok := packet.(*shared.CastFailedPacket)
+	if !ok {
+		t.Fatalf("Expected a CastFailedPacket, got %T", packet)
+	}
+	if castFailed.Slot != 1 || castFailed.Reason != 1 {
+		t.Errorf("Expected slot 1 refused for mana, got %+v", castFailed)
+	}
+	if !bytes.Equal(castFailed.Serialize(), data[size:]) {
+		t.Errorf("Expected the packet to serialize back to %v", data[size:])
+	}
+}
+
+// Scoreboard packet serialized by the Rust game server, see
+// test_scoreboard_packet_serialize in game/src/packet/scoreboard_packet.rs
+var rustScoreboardPacket = []byte{
+	1, 16, 1,
+	0, 1, 1,
+	0, 3, 0, 1, 0, 2,
+	0, 40, 0, 4, 0, 1,
+	0, 1, 17, 112,
+	0, 0, 1, 44,
+}
+
+var sampleScoreEntry = shared.ScoreEntry{
+	PlayerID:        1,
+	Team:            1,
+	Kills:           3,
+	Deaths:          1,
+	Assists:         2,
+	MinionKills:     40,
+	MonsterKills:    4,
+	TowersDestroyed: 1,
+	DamageDealt:     70000,
+	DamageTaken:     300,
+}
+
+func TestScoreboardPacketRoundTrip(t *testing.T) {
+	size, err := shared.PacketSize(rustScoreboardPacket)
+	if err != nil || size != len(rustScoreboardPacket) {
+		t.Fatalf("Expected a packet of %d bytes, got %d (%v)", len(rustScoreboardPacket), size, err)
+	}
+	packet, err := shared.DeSerialize(rustScoreboardPacket)
+	if err != nil {
+		t.Fatalf("Expected no error decoding the scoreboard packet, got %v", err)
+	}
+	scoreboard, ok := packet.(*shared.ScoreboardPacket)
+	if !ok {
+		t.Fatalf("Expected a ScoreboardPacket, got %T", packet)
+	}
+	if !reflect.DeepEqual(scoreboard.Entries, []shared.ScoreEntry{sampleScoreEntry}) {
+		t.Errorf("Unexpected entries %+v", scoreboard.Entries)
+	}
+	if serialized := scoreboard.Serialize(); !bytes.Equal(serialized, rustScoreboardPacket) {
+		t.Errorf("Expected the packet to serialize back to %v, got %v", rustScoreboardPacket, serialized)
+	}
+}
+
+func TestEndGamePacketRoundTrip(t *testing.T) {
+	// The end game packet ends with the entries of the scoreboard format
+	data := append([]byte{1, 12, 1}, rustScoreboardPacket[2:]...)
+
+	size, err := shared.PacketSize(data)
+	if err != nil || size != len(data) {
+		t.Fatalf("Expected a packet of %d bytes, got %d (%v)", len(data), size, err)
+	}
+	packet, err := shared.DeSerialize(data)
+	if err != nil {
+		t.Fatalf("Expected no error decoding the end game packet, got %v", err)
+	}
+	endGame, ok := packet.(*shared.EndGamePacket)
+	if !ok {
+		t.Fatalf("Expected an EndGamePacket, got %T", packet)
+	}
+	if endGame.Winner != 1 {
+		t.Errorf("Expected the blue team to win, got %d", endGame.Winner)
+	}
+	if !reflect.DeepEqual(endGame.Scores, []shared.ScoreEntry{sampleScoreEntry}) {
+		t.Errorf("Unexpected scores %+v", endGame.Scores)
+	}
+	if serialized := endGame.Serialize(); !bytes.Equal(serialized, data) {
+		t.Errorf("Expected the packet to serialize back to %v, got %v", data, serialized)
+	}
+
+	// No scoreboard
+	if _, err := shared.DeSerialize([]byte{1, 12, 0, 1}); err == nil {
+		t.Error("Expected an error for a missing score entry")
+	}
+}
+
+func TestRespawnPacketRoundTrip(t *testing.T) {
+	// See test_respawn_packet_serialize in game/src/packet/respawn_packet.rs
+	data := []byte{1, 17, 0, 0, 3, 232}
+
+	if size, err := shared.PacketSize(data); err != nil || size != len(data) {
+		t.Fatalf("Expected a packet of %d bytes, got %d (%v)", len(data), size, err)
+	}
+	packet, err := shared.DeSerialize(data)
+	if err != nil {
+		t.Fatalf("Expected no error decoding the respawn packet, got %v", err)
+	}
+	respawn, ok := packet.(*shared.RespawnPacket)
+	if !ok {
+		t.Fatalf("Expected a RespawnPacket, got %T", packet)
+	}
+	if respawn.RemainingMs != 1000 {
+		t.Errorf("Expected 1000ms before respawn, got %d", respawn.RemainingMs)
+	}
+	if !bytes.Equal(respawn.Serialize(), data) {
+		t.Errorf("Expected the packet to serialize back to %v", data)
+	}
+}
+
+func TestTickRatePacketRoundTrip(t *testing.T) {
+	// See test_tick_rate_packet_serialize in game/src/packet/tick_rate_packet.rs
+	data := []byte{1, 18, 0, 40, 9, 196, 1, 44}
+
+	if size, err := shared.PacketSize(data); err != nil || size != len(data) {
+		t.Fatalf("Expected a packet of %d bytes, got %d (%v)", len(data), size, err)
+	}
+	packet, err := shared.DeSerialize(data)
+	if err != nil {
+		t.Fatalf("Expected no error decoding the tick rate packet, got %v", err)
+	}
+	tickRate, ok := packet.(*shared.TickRatePacket)
+	if !ok {
+		t.Fatalf("Expected a TickRatePacket, got %T", packet)
+	}
+	if tickRate.PeriodMs != 40 || tickRate.RateCentihertz != 2500 || tickRate.DriftMs != 300 {
+		t.Errorf("Expected 40ms period, 25 ticks per second and 300ms drift, got %+v", tickRate)
+	}
+	if !bytes.Equal(tickRate.Serialize(), data) {
+		t.Errorf("Expected the packet to serialize back to %v", data)
+	}
+}
+
+func TestSpellSelectionPacketCarriesTheChampion(t *testing.T) {
+	// Read by SpellSelectionPacket::deserialize in game/src/packet/spell_selection_packet.rs
+	data := shared.NewSpellSelectionPacket(0, 1, 3).Serialize()
+	if !bytes.Equal(data, []byte{1, 13, 0, 1, 3}) {
+		t.Errorf("Unexpected spell selection packet %v", data)
+	}
+}
+
+func TestMoveToPacketRoundTrip(t *testing.T) {
+	// Read by MoveToPacket::deserialize in game/src/packet/move_to_packet.rs
+	data := shared.NewMoveToPacket(true, 10, 300).Serialize()
+	if !bytes.Equal(data, []byte{1, 15, 1, 0, 10, 1, 44}) {
+		t.Fatalf("Unexpected move to packet %v", data)
+	}
+	size, err := shared.PacketSize(data)
+	if err != nil || size != len(data) {
+		t.Fatalf("Expected a move to packet of %d bytes, got %d (%v)", len(data), size, err)
+	}
+
+	packet, err := shared.DeSerialize(data)
+	if err != nil {
+		t.Fatalf("Expected no error decoding the move to packet, got %v", err)
+	}
+	moveTo, ok := packet.(*shared.MoveToPacket)
+	if !ok {
+		t.Fatalf("Expected a MoveToPacket, got %T", packet)
+	}
+	if !moveTo.AttackMove || moveTo.Row != 10 || moveTo.Col != 300 {
+		t.Errorf("Expected an attack move to 10, 300, got %+v", moveTo)
+	}
+}